@@ -9,6 +9,11 @@ fn evm(c: &mut Criterion) {
     bench::transfer_multi::run(c);
     bench::evm_build::run(c);
     bench::gas_cost_estimator::run(c);
+    bench::logs::run(c);
+    bench::access_list::run(c);
+    bench::journal_reset::run(c);
+    bench::code_hash::run(c);
+    bench::commit_tx::run(c);
 }
 criterion_group!(benches, evm);
 criterion_main!(benches);
@@ -1,7 +1,12 @@
+pub mod access_list;
 pub mod analysis;
 pub mod burntpix;
+pub mod code_hash;
+pub mod commit_tx;
 pub mod evm_build;
 pub mod gas_cost_estimator;
+pub mod journal_reset;
+pub mod logs;
 pub mod snailtracer;
 pub mod transfer;
 pub mod transfer_multi;
@@ -17,6 +22,11 @@ pub enum BenchName {
     EvmBuild,
     TransferMulti,
     GasCostEstimator,
+    Logs,
+    AccessList,
+    JournalReset,
+    CodeHash,
+    CommitTx,
 }
 
 impl BenchName {
@@ -28,6 +38,11 @@ impl BenchName {
         BenchName::TransferMulti,
         BenchName::EvmBuild,
         BenchName::GasCostEstimator,
+        BenchName::Logs,
+        BenchName::AccessList,
+        BenchName::JournalReset,
+        BenchName::CodeHash,
+        BenchName::CommitTx,
     ];
 
     pub fn as_str(self) -> &'static str {
@@ -39,6 +54,11 @@ impl BenchName {
             BenchName::EvmBuild => "evm-build",
             BenchName::TransferMulti => "transfer-multi",
             BenchName::GasCostEstimator => "gas-cost-estimator",
+            BenchName::Logs => "logs",
+            BenchName::AccessList => "access-list",
+            BenchName::JournalReset => "journal-reset",
+            BenchName::CodeHash => "code-hash",
+            BenchName::CommitTx => "commit-tx",
         }
     }
 }
@@ -92,6 +112,21 @@ impl Cmd {
             BenchName::GasCostEstimator => {
                 gas_cost_estimator::run(&mut criterion);
             }
+            BenchName::Logs => {
+                logs::run(&mut criterion);
+            }
+            BenchName::AccessList => {
+                access_list::run(&mut criterion);
+            }
+            BenchName::JournalReset => {
+                journal_reset::run(&mut criterion);
+            }
+            BenchName::CodeHash => {
+                code_hash::run(&mut criterion);
+            }
+            BenchName::CommitTx => {
+                commit_tx::run(&mut criterion);
+            }
         }
     }
 }
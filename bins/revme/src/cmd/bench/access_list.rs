@@ -0,0 +1,47 @@
+use context::{
+    transaction::{AccessList, AccessListItem},
+    TxEnv,
+};
+use criterion::Criterion;
+use database::{BenchmarkDB, BENCH_CALLER, BENCH_TARGET};
+use revm::{
+    bytecode::Bytecode,
+    primitives::{Address, TxKind, B256, U256},
+    Context, ExecuteEvm, MainBuilder, MainContext,
+};
+
+pub fn run(criterion: &mut Criterion) {
+    let mut evm = Context::mainnet()
+        .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+        .modify_cfg_chained(|cfg| cfg.disable_nonce_check = true)
+        .build_mainnet();
+
+    // 1000 accounts with two storage keys each.
+    let access_list: Vec<_> = (0..1000u64)
+        .map(|i| AccessListItem {
+            address: Address::from_word(B256::from(U256::from(0x10000 + i))),
+            storage_keys: vec![B256::ZERO, B256::with_last_byte(1)],
+        })
+        .collect();
+
+    let tx = TxEnv::builder()
+        .caller(BENCH_CALLER)
+        .kind(TxKind::Call(BENCH_TARGET))
+        .access_list(AccessList(access_list))
+        .gas_limit(30_000_000)
+        .build()
+        .unwrap();
+
+    criterion.bench_function("access_list_1000", |b| {
+        b.iter_batched(
+            || {
+                // create a transaction input
+                tx.clone()
+            },
+            |input| {
+                let _ = evm.transact_one(input).unwrap();
+            },
+            criterion::BatchSize::SmallInput,
+        );
+    });
+}
@@ -0,0 +1,27 @@
+use context::{CodeHashCache, JournalEntry, JournalInner};
+use criterion::Criterion;
+use database::{BenchmarkDB, BENCH_TARGET};
+use revm::{bytecode::Bytecode, primitives::Bytes};
+
+pub fn run(criterion: &mut Criterion) {
+    let mut db = BenchmarkDB::new_bytecode(Bytecode::new());
+    // Contract of the maximum size, filled with `JUMPDEST`.
+    let code = Bytecode::new_legacy(Bytes::from(vec![0x5b; 24576]));
+    let mut journal = JournalInner::<JournalEntry>::new();
+    journal.load_account(&mut db, BENCH_TARGET).unwrap();
+
+    criterion.bench_function("set_code_24kb", |b| {
+        b.iter(|| {
+            journal.set_code(BENCH_TARGET, code.clone());
+            journal.journal.clear();
+        });
+    });
+
+    let mut cache = CodeHashCache::new();
+    criterion.bench_function("set_code_cached_24kb", |b| {
+        b.iter(|| {
+            journal.set_code_cached(BENCH_TARGET, code.clone(), &mut cache);
+            journal.journal.clear();
+        });
+    });
+}
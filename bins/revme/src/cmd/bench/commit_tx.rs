@@ -0,0 +1,40 @@
+use context::{JournalEntry, JournalInner};
+use criterion::Criterion;
+use database::{BenchmarkDB, BENCH_CALLER};
+use revm::{
+    bytecode::Bytecode,
+    primitives::{Address, HashSet, B256, U256},
+};
+
+/// Number of plain transfers committed in a block.
+const TRANSFER_COUNT: u64 = 1000;
+
+pub fn run(criterion: &mut Criterion) {
+    let mut db = BenchmarkDB::new_bytecode(Bytecode::new());
+    let receivers: Vec<_> = (0..TRANSFER_COUNT)
+        .map(|i| Address::from_word(B256::from(U256::from(0x10000 + i))))
+        .collect();
+    let precompiles: HashSet<_> = (1..=17).map(Address::with_last_byte).collect();
+    let mut journal = JournalInner::<JournalEntry>::new();
+    journal.precompiles = precompiles.clone();
+    journal.warm_preloaded_addresses = precompiles;
+
+    let mut block = |journal: &mut JournalInner<JournalEntry>,
+                     commit: fn(&mut JournalInner<JournalEntry>)| {
+        for receiver in &receivers {
+            journal.load_account(&mut db, BENCH_CALLER).unwrap();
+            journal
+                .transfer(&mut db, BENCH_CALLER, *receiver, U256::from(1))
+                .unwrap();
+            commit(journal);
+        }
+    };
+
+    criterion.bench_function("journal_commit_tx_1000_transfers", |b| {
+        b.iter(|| block(&mut journal, JournalInner::commit_tx));
+    });
+
+    criterion.bench_function("journal_commit_tx_fast_1000_transfers", |b| {
+        b.iter(|| block(&mut journal, JournalInner::commit_tx_fast));
+    });
+}
@@ -0,0 +1,39 @@
+use context::{JournalEntry, JournalInner};
+use criterion::Criterion;
+use database::BenchmarkDB;
+use revm::{
+    bytecode::Bytecode,
+    primitives::{Address, B256, U256},
+};
+use std::hint::black_box;
+
+/// Number of accounts loaded before each reset.
+const ACCOUNT_COUNT: u64 = 1000;
+
+pub fn run(criterion: &mut Criterion) {
+    let mut db = BenchmarkDB::new_bytecode(Bytecode::new());
+    let addresses: Vec<_> = (0..ACCOUNT_COUNT)
+        .map(|i| Address::from_word(B256::from(U256::from(0x10000 + i))))
+        .collect();
+    let mut journal = JournalInner::<JournalEntry>::new();
+    let mut load = |journal: &mut JournalInner<JournalEntry>| {
+        for address in &addresses {
+            journal.load_account(&mut db, *address).unwrap();
+        }
+    };
+
+    // `finalize` moves the state out, so it is reallocated on the next run.
+    criterion.bench_function("journal_finalize_1000", |b| {
+        b.iter(|| {
+            load(&mut journal);
+            black_box(journal.finalize());
+        });
+    });
+
+    criterion.bench_function("journal_reset_reuse_1000", |b| {
+        b.iter(|| {
+            load(&mut journal);
+            journal.reset_reuse();
+        });
+    });
+}
@@ -0,0 +1,73 @@
+//! Benchmark of a transaction that emits many logs.
+//!
+//! Allocations are not measured, as that would need a counting global allocator for the whole
+//! `revme` binary. A redesign of the log path was declined, as it has no avoidable allocation:
+//! - [`Log`](revm::primitives::Log) data is [`Bytes`], which is reference counted, so clones are
+//!   cheap.
+//! - Logs are moved from the `LOG` instruction into the journal and then into the execution
+//!   result, they are never cloned on the way. Taking them uses `mem::take` and reverting only
+//!   truncates.
+//! - Each `LOG` allocates its topics vector and copies its data out of memory. Both are needed as
+//!   the log outlives the memory of the frame.
+//!
+//! The benchmark tracks the time of this path.
+use context::TxEnv;
+use criterion::Criterion;
+use database::{BenchmarkDB, BENCH_CALLER, BENCH_TARGET};
+use revm::{
+    bytecode::{opcode, Bytecode},
+    primitives::{Bytes, TxKind},
+    Context, ExecuteEvm, MainBuilder, MainContext,
+};
+
+/// Number of logs emitted by the transaction.
+const LOG_COUNT: usize = 5000;
+
+pub fn run(criterion: &mut Criterion) {
+    // Each log has two topics and 32 bytes of data.
+    let log = [
+        opcode::PUSH1,
+        0x01,
+        opcode::PUSH1,
+        0x02,
+        opcode::PUSH1,
+        0x20,
+        opcode::PUSH1,
+        0x00,
+        opcode::LOG2,
+    ];
+    let code: Vec<u8> = log
+        .iter()
+        .copied()
+        .cycle()
+        .take(log.len() * LOG_COUNT)
+        .chain([opcode::STOP])
+        .collect();
+    let bytecode = Bytecode::new_raw(Bytes::from(code));
+
+    let mut evm = Context::mainnet()
+        .with_db(BenchmarkDB::new_bytecode(bytecode))
+        .modify_cfg_chained(|c| c.disable_nonce_check = true)
+        .build_mainnet();
+
+    let tx = TxEnv::builder()
+        .caller(BENCH_CALLER)
+        .kind(TxKind::Call(BENCH_TARGET))
+        .gas_limit(1_000_000_000)
+        .build()
+        .unwrap();
+
+    criterion.bench_function("logs", |b| {
+        b.iter_batched(
+            || {
+                // create a transaction input
+                tx.clone()
+            },
+            |input| {
+                let result = evm.transact_one(input).unwrap();
+                assert_eq!(result.logs().len(), LOG_COUNT);
+            },
+            criterion::BatchSize::SmallInput,
+        );
+    });
+}
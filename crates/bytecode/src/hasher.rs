@@ -0,0 +1,50 @@
+//! Streaming hasher for bytecode assembled in chunks.
+
+use primitives::{alloy_primitives::Keccak256, B256};
+
+/// Computes the code hash of bytecode that is fed in chunks.
+///
+/// The finalized hash equals [`Bytecode::hash_slow`](crate::Bytecode::hash_slow) of the
+/// concatenated bytes and can be used with `set_code_with_hash` without hashing the code again.
+#[derive(Clone, Debug, Default)]
+pub struct CodeHasher {
+    hasher: Keccak256,
+}
+
+impl CodeHasher {
+    /// Creates a new hasher with no input.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds the next chunk of the bytecode.
+    pub fn update(&mut self, chunk: impl AsRef<[u8]>) {
+        self.hasher.update(chunk);
+    }
+
+    /// Returns the code hash of all chunks fed so far.
+    ///
+    /// The hash of no input is [`KECCAK_EMPTY`](primitives::KECCAK_EMPTY).
+    pub fn finalize(self) -> B256 {
+        self.hasher.finalize()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Bytecode;
+    use primitives::{Bytes, KECCAK_EMPTY};
+
+    #[test]
+    fn code_hasher_matches_hash_slow() {
+        let code = Bytes::from_static(&[0x60, 0x01, 0x60, 0x02, 0x01, 0x5b, 0x00]);
+        let mut hasher = CodeHasher::new();
+        for chunk in code.chunks(3) {
+            hasher.update(chunk);
+        }
+        assert_eq!(hasher.finalize(), Bytecode::new_legacy(code).hash_slow());
+
+        assert_eq!(CodeHasher::new().finalize(), KECCAK_EMPTY);
+    }
+}
@@ -13,6 +13,8 @@ pub mod bytecode;
 mod decode_errors;
 /// EIP-7702 bytecode.
 pub mod eip7702;
+/// Streaming code hasher.
+mod hasher;
 /// Iterator for the bytecode.
 mod iter;
 /// Legacy bytecode.
@@ -24,6 +26,7 @@ pub mod utils;
 pub use bitvec;
 pub use bytecode::Bytecode;
 pub use decode_errors::BytecodeDecodeError;
+pub use hasher::CodeHasher;
 pub use iter::BytecodeIterator;
 pub use legacy::{JumpTable, LegacyAnalyzedBytecode, LegacyRawBytecode};
 pub use opcode::OpCode;
@@ -58,6 +58,33 @@ pub trait Cfg {
 
     /// Returns whether the priority fee check is disabled.
     fn is_priority_fee_check_disabled(&self) -> bool;
+
+    /// Returns the blob base fee used instead of the one derived from the block excess blob gas.
+    ///
+    /// If it is None, the block blob base fee is used.
+    ///
+    /// Default implementation returns `None`.
+    fn blob_base_fee_override(&self) -> Option<u128> {
+        None
+    }
+
+    /// Returns the maximum length of the revert data returned to the calling frame.
+    ///
+    /// Longer revert data is truncated. Returns `usize::MAX` if revert data is not limited.
+    ///
+    /// Default implementation returns `usize::MAX`.
+    fn max_revert_data(&self) -> usize {
+        usize::MAX
+    }
+
+    /// Returns the minimum priority fee per gas paid to the beneficiary.
+    ///
+    /// Returns zero if there is no minimum.
+    ///
+    /// Default implementation returns zero.
+    fn min_priority_fee(&self) -> u128 {
+        0
+    }
 }
 
 /// What bytecode analysis to perform
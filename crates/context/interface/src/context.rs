@@ -1,7 +1,8 @@
 //! Context trait and related types.
 pub use crate::journaled_state::StateLoad;
 use crate::{
-    result::FromStringError, Block, Cfg, Database, JournalTr, LocalContextTr, Transaction,
+    result::FromStringError, Block, Cfg, Database, HostHooks, JournalTr, LocalContextTr,
+    Transaction,
 };
 use auto_impl::auto_impl;
 use primitives::StorageValue;
@@ -26,8 +27,8 @@ pub trait ContextTr {
     type Db: Database;
     /// Journal type
     type Journal: JournalTr<Database = Self::Db>;
-    /// Chain type
-    type Chain;
+    /// Chain type, it overrides the hooks of the interpreter host.
+    type Chain: HostHooks;
     /// Local context type
     type Local: LocalContextTr;
 
@@ -0,0 +1,447 @@
+//! Hooks of the interpreter host that are implemented by the chain context, [`HostHooks`].
+use crate::{journaled_state::CollisionPolicy, result::HaltReason, CreateScheme};
+use primitives::{hardfork::SpecId, Address, Bytes, HashSet, StorageKey};
+use state::Bytecode;
+
+/// Result of applying a single EIP-7702 authorization, reported by [`HostHooks::on_authorization`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AuthResult {
+    /// Delegation is set and authority nonce is bumped.
+    Success,
+    /// Chain id is neither zero nor the chain's current id.
+    InvalidChainId,
+    /// Nonce is `u64::MAX` or does not match the authority nonce.
+    InvalidNonce,
+    /// Authority could not be recovered from the signature.
+    InvalidSignature,
+    /// Authority has code that is not a delegation designation.
+    AuthorityHasCode,
+}
+
+/// Operation that violates the static context of `STATICCALL`, checked with
+/// [`HostHooks::is_static_violation`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum StaticSensitiveOp {
+    /// `CALL` that transfers value.
+    CallWithValue,
+}
+
+/// Hooks and knobs of the interpreter host that chains can override.
+///
+/// The host of context types forwards these to [`ContextTr::chain`](crate::ContextTr::chain),
+/// so a chain changes them by implementing this trait for its chain context. Every method has a
+/// default that keeps the mainnet behavior, `()` is the chain context of mainnet.
+///
+/// Most of these diverge from consensus and are meant for test frameworks, research tooling and
+/// chains with custom rules. Hooks that count per transaction, like [`HostHooks::record_create`],
+/// are expected to be reset by the owner of the chain context between transactions.
+pub trait HostHooks {
+    /* Storage */
+
+    /// Called by the `SLOAD` and `SSTORE` instructions after the storage slot `key` of `address`
+    /// is accessed, in execution order and for every access including repeated ones.
+    ///
+    /// `is_write` is `true` for `SSTORE`. The access is reported even if the instruction then
+    /// halts because its gas is not covered.
+    ///
+    /// Default implementation does nothing.
+    #[inline]
+    fn on_storage_access(&mut self, _address: Address, _key: StorageKey, _is_write: bool) {}
+
+    /// Called by the `SLOAD` instruction after the gas of a cold storage load is charged.
+    ///
+    /// `gas` is the charged amount, the cold load cost since Berlin scaled by
+    /// [`HostHooks::gas_cost_multiplier`]. It is called once per slot and transaction, together
+    /// with the journal warming the slot.
+    ///
+    /// Default implementation does nothing.
+    #[inline]
+    fn on_cold_sload(&mut self, _address: Address, _key: StorageKey, _gas: u64) {}
+
+    /* Accounts */
+
+    /// Overrides whether the account is considered empty by the `CALL` and `SELFDESTRUCT` instructions.
+    ///
+    /// `default` is the EIP-161 aware emptiness computed by the journal
+    /// (`Account::state_clear_aware_is_empty`). Emptiness decides if the new account gas is charged.
+    ///
+    /// Default implementation returns `default`.
+    #[inline]
+    fn is_account_empty_override(&self, _address: Address, default: bool) -> bool {
+        default
+    }
+
+    /// Overrides the gas refund recorded by the `SELFDESTRUCT` instruction.
+    ///
+    /// `newly_destroyed` is `false` if the account was already destroyed in the current
+    /// transaction. Chains can use it to keep the refund after London.
+    ///
+    /// Default implementation returns `None` and the mainnet refund is recorded.
+    #[inline]
+    fn selfdestruct_refund(&self, _spec: SpecId, _newly_destroyed: bool) -> Option<i64> {
+        None
+    }
+
+    /* Static call */
+
+    /// Returns `true` if `op` is not allowed in a static context.
+    ///
+    /// Consulted before the interpreter halts with `CallNotAllowedInsideStatic`, so chains with
+    /// custom opcodes can define their own static safety.
+    ///
+    /// Default implementation returns `true`, all standard violations halt.
+    #[inline]
+    fn is_static_violation(&self, _op: StaticSensitiveOp) -> bool {
+        true
+    }
+
+    /* Create */
+
+    /// Overrides the address of the contract created by `CREATE` (not `CREATE2`).
+    ///
+    /// `nonce` is the caller nonce before it is bumped, or the one returned by
+    /// [`HostHooks::create_nonce`]. The caller nonce is still bumped and the returned address is
+    /// checked for collision as with the standard derivation.
+    ///
+    /// Default implementation returns `None` and the address is derived from the caller and nonce.
+    #[inline]
+    fn create_address_override(&self, _caller: Address, _nonce: u64) -> Option<Address> {
+        None
+    }
+
+    /// Overrides the nonce used to derive the address of the contract created by `CREATE`.
+    ///
+    /// This can supply nonces from an external sequence, so created addresses don't depend on the
+    /// execution order. Only the address derivation is affected, the caller nonce is still bumped.
+    /// The nonce is also passed to [`HostHooks::create_address_override`].
+    ///
+    /// Default implementation returns `None` and the caller nonce is used.
+    #[inline]
+    fn create_nonce(&self, _caller: Address) -> Option<u64> {
+        None
+    }
+
+    /// Called when the created account already has code or nonce.
+    ///
+    /// Returned [`CollisionPolicy`] decides if creation fails or the existing account is reused.
+    ///
+    /// Default implementation returns [`CollisionPolicy::Reject`] as required by consensus.
+    #[inline]
+    fn on_create_collision(&mut self, _target: Address) -> CollisionPolicy {
+        CollisionPolicy::Reject
+    }
+
+    /// Returns whether `caller` is allowed to create a contract with the given `scheme`.
+    ///
+    /// Consulted by the create instructions before any gas is charged. When `false` is returned
+    /// the current frame halts with `CreateNotAllowed`.
+    ///
+    /// Default implementation allows all creates.
+    #[inline]
+    fn can_create(&self, _caller: Address, _scheme: &CreateScheme) -> bool {
+        true
+    }
+
+    /// Returns the maximum number of `CREATE` and `CREATE2` instructions per transaction.
+    ///
+    /// The instruction that exceeds the limit halts the current frame with `CreateLimitExceeded`,
+    /// after its gas is charged. Creates are counted with [`HostHooks::record_create`].
+    ///
+    /// Default implementation returns `usize::MAX`, the number of creates is unlimited.
+    #[inline]
+    fn max_creates_per_tx(&self) -> usize {
+        usize::MAX
+    }
+
+    /// Called by the create instructions before a new create frame is requested.
+    ///
+    /// Returns the number of creates in the current transaction including this one. The chain
+    /// context is expected to increment its own count on each call.
+    ///
+    /// Default implementation doesn't count and returns zero.
+    #[inline]
+    fn record_create(&mut self) -> usize {
+        0
+    }
+
+    /// Called when a create frame succeeds and its runtime code is stored at `address`.
+    ///
+    /// `code` is the output of the init code, called after the EIP-3541, EIP-170 code size and
+    /// code deposit checks passed. Creations that fail these checks don't call it.
+    ///
+    /// Default implementation does nothing.
+    #[inline]
+    fn on_code_deployed(&mut self, _address: Address, _code: &Bytecode) {}
+
+    /* Calls */
+
+    /// Returns the maximum input size in bytes of the call instructions.
+    ///
+    /// Larger inputs halt the calling frame with `CallInputSizeLimit`. The size is checked before
+    /// memory expansion is charged, so the rejected input doesn't expand memory.
+    ///
+    /// Default implementation returns `usize::MAX`, the input size is not limited.
+    #[inline]
+    fn max_call_input_size(&self) -> usize {
+        usize::MAX
+    }
+
+    /// Returns how many times a call can reenter an address that is already in the call stack.
+    ///
+    /// The call instruction that would exceed it halts the calling frame with `ReentrancyLimit`.
+    /// Zero forbids any reentrancy.
+    ///
+    /// Default implementation returns `usize::MAX`, reentrancy is not limited and the call stack
+    /// is not consulted.
+    #[inline]
+    fn max_reentrancy(&self) -> usize {
+        usize::MAX
+    }
+
+    /// Returns `true` if the call targets are pushed to the call stack of the local context,
+    /// [`LocalContextTr::call_stack`](crate::LocalContextTr::call_stack), for every call.
+    ///
+    /// The call stack is needed to limit reentrancy. Chains that read it for other purposes
+    /// enable it explicitly.
+    ///
+    /// Default implementation returns `true` only if [`HostHooks::max_reentrancy`] limits
+    /// reentrancy.
+    #[inline]
+    fn track_call_stack(&self) -> bool {
+        self.max_reentrancy() != usize::MAX
+    }
+
+    /// Returns `true` if the gas of call frames is checked against
+    /// [`HostHooks::contract_gas_budget`] and reported with [`HostHooks::record_contract_gas`].
+    ///
+    /// Default implementation returns `false`, the gas of call frames is not tracked.
+    #[inline]
+    fn track_contract_gas(&self) -> bool {
+        false
+    }
+
+    /// Returns the remaining gas budget of `address` for the current transaction.
+    ///
+    /// Calls to an address with an exhausted budget (`Some(0)`) halt with
+    /// `ContractGasBudgetExhausted` without being executed, other calls have their gas limit
+    /// clamped to the budget. Gas consumed by the frames of an address is reported with
+    /// [`HostHooks::record_contract_gas`]. Only called if [`HostHooks::track_contract_gas`] is
+    /// enabled.
+    ///
+    /// Default implementation returns `None`, the budget is unlimited.
+    #[inline]
+    fn contract_gas_budget(&mut self, _address: Address) -> Option<u64> {
+        None
+    }
+
+    /// Called when a call frame of `address` returns, with the gas consumed by the frame itself,
+    /// excluding the gas consumed by its child frames.
+    ///
+    /// The chain context is expected to decrement the budget returned by
+    /// [`HostHooks::contract_gas_budget`].
+    ///
+    /// Default implementation does nothing.
+    #[inline]
+    fn record_contract_gas(&mut self, _address: Address, _gas_used: u64) {}
+
+    /* Authorization list */
+
+    /// Called for each entry of the EIP-7702 authorization list when it is applied.
+    ///
+    /// `authority` is `None` if it was not recovered, which is the case for
+    /// [`AuthResult::InvalidSignature`] and for the invalid chain id and nonce checks that come
+    /// before the recovery.
+    /// `delegate` is the address the authority delegates to.
+    ///
+    /// Default implementation does nothing.
+    #[inline]
+    fn on_authorization(
+        &mut self,
+        _authority: Option<Address>,
+        _delegate: Address,
+        _result: AuthResult,
+    ) {
+    }
+
+    /* Gas */
+
+    /// Multiplier applied to the gas cost of the call instructions, the contract creation base
+    /// and init code costs, and the `SLOAD` and `SSTORE` costs. Refunds are not scaled.
+    ///
+    /// Default implementation returns one, which keeps the standard gas costs.
+    #[inline]
+    fn gas_cost_multiplier(&self) -> u64 {
+        1
+    }
+
+    /// Overrides the gas charged by the `CALL` instruction for creating an account, if it
+    /// transfers value to an empty account. Before Spurious Dragon it is charged for empty
+    /// accounts without value.
+    ///
+    /// `CALLCODE`, `DELEGATECALL` and `STATICCALL` don't create the account and never charge it.
+    ///
+    /// Default implementation returns `None` and the mainnet cost is charged.
+    #[inline]
+    fn new_account_cost(&self) -> Option<u64> {
+        None
+    }
+
+    /// Minimum gas that the call instructions must forward to the called frame.
+    ///
+    /// The minimum is checked against the forwarded gas before the call stipend is added, so a
+    /// value transfer with zero gas is below any nonzero minimum. If it is not met the
+    /// interpreter halts with out of gas.
+    ///
+    /// Default implementation returns zero, there is no minimum.
+    #[inline]
+    fn min_forwarded_gas(&self) -> u64 {
+        0
+    }
+
+    /// Gas forwarded by the call instructions to `target`, the address popped from the stack,
+    /// instead of the gas limited by the 63/64 rule.
+    ///
+    /// The call cost is charged as usual and the forced amount is then deducted from the caller,
+    /// so the caller halts with out of gas if it has less gas remaining. This is meant for test
+    /// frameworks that want to starve a callee deterministically.
+    ///
+    /// Default implementation returns `None` and the gas is computed as in mainnet.
+    #[inline]
+    fn forced_call_gas(&self, _target: Address) -> Option<u64> {
+        None
+    }
+
+    /// Returns `true` if the call and create instructions forward gas without the EIP-150 63/64
+    /// reservation.
+    ///
+    /// Calls forward the requested gas limited by the remaining gas, and creates forward all
+    /// remaining gas. This models a chain without the 63/64 rule.
+    ///
+    /// Default implementation returns `false`, gas is forwarded as in mainnet.
+    #[inline]
+    fn strict_gas_forwarding(&self) -> bool {
+        false
+    }
+
+    /// Called by the `CREATE` and `CREATE2` instructions after their base cost is charged.
+    ///
+    /// `base` is the `CREATE` cost and `hashing` is the init code hashing cost that is only
+    /// charged by `CREATE2` (zero for `CREATE`). Their sum is the charged amount.
+    ///
+    /// Default implementation does nothing.
+    #[inline]
+    fn on_create_base_gas(&mut self, _is_create2: bool, _base: u64, _hashing: u64) {}
+
+    /// Called by the `CALL` and `CALLCODE` instructions after the call stipend of a value
+    /// transfer is added to the gas limit of the callee.
+    ///
+    /// `amount` is the call stipend, it is not charged to the caller. Calls without value don't
+    /// get a stipend and don't call this hook.
+    ///
+    /// Default implementation does nothing.
+    #[inline]
+    fn on_stipend(&mut self, _amount: u64) {}
+
+    /// Overrides the gas cost of the `LOG0` to `LOG4` instructions emitting `topics` topics and
+    /// `data_len` bytes of data. Memory expansion is charged separately.
+    ///
+    /// Default implementation returns `None` and the mainnet cost is charged.
+    #[inline]
+    fn log_cost(&self, _topics: usize, _data_len: usize) -> Option<u64> {
+        None
+    }
+
+    /// Overrides the gas cost of expanding memory from `current_words` to `new_words` words.
+    ///
+    /// Only used when the `CALL`, `CALLCODE`, `DELEGATECALL` and `STATICCALL` instructions expand
+    /// memory for their input and output. Memory growth of other instructions, such as `MSTORE`
+    /// or the copy instructions, is always charged the mainnet cost.
+    ///
+    /// Default implementation returns `None` and the quadratic mainnet cost is charged.
+    #[inline]
+    fn memory_expansion_cost(&self, _current_words: u64, _new_words: u64) -> Option<u64> {
+        None
+    }
+
+    /* Precompiles */
+
+    /// Returns chain specific precompile addresses that are added at runtime.
+    ///
+    /// They are added to the precompile addresses of the journal before each transaction, so
+    /// their accounts start warm like the standard precompiles. Precompiles are executed by the
+    /// precompile provider, which is expected to handle these addresses.
+    ///
+    /// Default implementation returns `None`, only the standard precompiles are warm.
+    #[inline]
+    fn extra_precompiles(&self) -> Option<&HashSet<Address>> {
+        None
+    }
+
+    /// Called with the call input before a precompile at `address` is executed.
+    ///
+    /// Default implementation does nothing.
+    #[inline]
+    fn on_precompile_input(&mut self, _address: Address, _input: &[u8]) {}
+
+    /// Called after a precompile at `address` has been executed with `input`.
+    ///
+    /// `output` is the returned bytes on success or the halt reason on failure. `gas_used` is
+    /// the gas charged by the precompile, which is the whole gas limit if it failed.
+    ///
+    /// Default implementation does nothing.
+    #[inline]
+    fn on_precompile_result(
+        &mut self,
+        _address: Address,
+        _input: &[u8],
+        _output: Result<&Bytes, HaltReason>,
+        _gas_used: u64,
+    ) {
+    }
+
+    /* Interpreter */
+
+    /// Returns the number of instructions that frames can still execute.
+    ///
+    /// It is read before a frame runs and the frame halts with `OpcodeBudgetExhausted` when it
+    /// is used up. Instructions are counted with [`HostHooks::record_opcodes`].
+    ///
+    /// Default implementation returns `None`, the number of instructions is unlimited.
+    #[inline]
+    fn opcode_budget(&self) -> Option<u64> {
+        None
+    }
+
+    /// Called when a frame with an [`HostHooks::opcode_budget`] stops running, with the number
+    /// of instructions it executed.
+    ///
+    /// The chain context is expected to decrement the budget by `count`.
+    ///
+    /// Default implementation does nothing.
+    #[inline]
+    fn record_opcodes(&mut self, _count: u64) {}
+
+    /// Returns `true` if the stack length is sampled after each instruction and reported with
+    /// [`HostHooks::on_stack_high_water`].
+    ///
+    /// Default implementation returns `false`, the stack length is not sampled.
+    #[inline]
+    fn track_stack_high_water(&self) -> bool {
+        false
+    }
+
+    /// Called when a frame returns with the highest number of stack items the frame reached.
+    ///
+    /// `frame_depth` is the call depth of the frame, starting at 0 for the first frame, and
+    /// `stack_depth` is at most the stack limit of 1024 items. It is only called if
+    /// [`HostHooks::track_stack_high_water`] is enabled.
+    ///
+    /// Default implementation does nothing.
+    #[inline]
+    fn on_stack_high_water(&mut self, _frame_depth: usize, _stack_depth: usize) {}
+}
+
+impl HostHooks for () {}
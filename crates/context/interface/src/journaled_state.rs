@@ -52,6 +52,15 @@ pub trait JournalTr {
     /// Logs the log in Journal state.
     fn log(&mut self, log: Log);
 
+    /// Logs the log, buffering it until the top level call succeeds if staged logs are enabled.
+    ///
+    /// Staged logs are not reverted by inner calls and are dropped if the top level call reverts.
+    ///
+    /// Default implementation calls [`JournalTr::log`].
+    fn log_staged(&mut self, log: Log) {
+        self.log(log);
+    }
+
     /// Marks the account for selfdestruction and transfers all the balance to the target.
     fn selfdestruct(
         &mut self,
@@ -69,6 +78,14 @@ pub trait JournalTr {
     /// Warms the account.
     fn warm_account(&mut self, address: Address);
 
+    /// Reserves capacity for at least `additional` more journal entries.
+    ///
+    /// Default implementation does nothing.
+    #[inline]
+    fn reserve_entries(&mut self, additional: usize) {
+        let _ = additional;
+    }
+
     /// Warms the coinbase account.
     fn warm_coinbase_account(&mut self, address: Address);
 
@@ -195,12 +212,39 @@ pub trait JournalTr {
         spec_id: SpecId,
     ) -> Result<JournalCheckpoint, TransferError>;
 
+    /// Creates a checkpoint of the account creation, resolving a collision with the given policy.
+    ///
+    /// Default implementation ignores the policy and rejects the collision.
+    fn create_account_checkpoint_with_policy(
+        &mut self,
+        caller: Address,
+        address: Address,
+        balance: U256,
+        spec_id: SpecId,
+        policy: CollisionPolicy,
+    ) -> Result<JournalCheckpoint, TransferError> {
+        let _ = policy;
+        self.create_account_checkpoint(caller, address, balance, spec_id)
+    }
+
     /// Returns the depth of the journal.
     fn depth(&self) -> usize;
 
     /// Take logs from journal.
     fn take_logs(&mut self) -> Vec<Log>;
 
+    /// Returns `true` if the current transaction changed the state.
+    ///
+    /// Warming and touching accounts are not state changes. Balance and nonce changes of the
+    /// `excluded` accounts are skipped.
+    ///
+    /// Changes are tracked until the transaction is committed with [`JournalTr::commit_tx`].
+    ///
+    /// Default implementation returns `true`, the transaction is assumed to change the state.
+    fn changes_state(&self, _excluded: &[Address]) -> bool {
+        true
+    }
+
     /// Commit current transaction journal and returns transaction logs.
     fn commit_tx(&mut self);
 
@@ -225,6 +269,22 @@ pub enum TransferError {
     CreateCollision,
 }
 
+/// How `CREATE` to an account that already has code or nonce is resolved.
+///
+/// In every case balance of the existing account is kept, its storage is cleared and the account
+/// is marked as created.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CollisionPolicy {
+    /// Creation fails with [`TransferError::CreateCollision`]. This is consensus behavior.
+    #[default]
+    Reject,
+    /// Code, nonce and storage of the existing account are reset, as if it was empty.
+    Overwrite,
+    /// Code and storage of the existing account are reset while its nonce is kept.
+    Merge,
+}
+
 /// SubRoutine checkpoint that will help us to go back from this
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -3,6 +3,7 @@ use core::{
     cell::{Ref, RefCell},
     ops::Range,
 };
+use primitives::Address;
 use std::{boxed::Box, rc::Rc, vec::Vec};
 
 /// Non-empty, item-pooling Vec.
@@ -191,6 +192,39 @@ pub trait LocalContextTr {
         }))
     }
 
+    /// Call targets of the current call stack, from the first frame to the innermost call.
+    ///
+    /// Default implementation does not track calls and returns an empty slice.
+    fn call_stack(&self) -> &[Address] {
+        &[]
+    }
+
+    /// Pushes the target of a call frame to the call stack.
+    ///
+    /// Default implementation does nothing.
+    fn push_call(&mut self, _address: Address) {}
+
+    /// Pops the innermost call target from the call stack.
+    ///
+    /// Default implementation does nothing.
+    fn pop_call(&mut self) {}
+
+    /// Gas used by the transactions run since the last reset, the cumulative gas used of the
+    /// receipt of the last transaction.
+    ///
+    /// It is kept by [`LocalContextTr::clear`] and reset with
+    /// [`LocalContextTr::set_cumulative_gas_used`] at the start of a block.
+    ///
+    /// Default implementation does not track gas and returns zero.
+    fn cumulative_gas_used(&self) -> u64 {
+        0
+    }
+
+    /// Sets the cumulative gas used.
+    ///
+    /// Default implementation does nothing.
+    fn set_cumulative_gas_used(&mut self, _gas_used: u64) {}
+
     /// Clear the local context.
     fn clear(&mut self);
 }
@@ -176,6 +176,13 @@ impl<HaltReasonTy> ExecutionResult<HaltReasonTy> {
     }
 }
 
+impl ExecutionResult<HaltReason> {
+    /// Returns true if execution halted because it ran out of gas.
+    pub fn is_out_of_gas(&self) -> bool {
+        matches!(self, Self::Halt { reason, .. } if reason.is_out_of_gas())
+    }
+}
+
 /// Output of a transaction execution
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -434,6 +441,11 @@ pub enum InvalidTransaction {
     Eip7873NotSupported,
     /// EIP-7873 initcode transaction should have `to` address.
     Eip7873MissingTarget,
+    /// Transaction still conflicted after the last retry of optimistic concurrent execution.
+    ConflictAfterRetries {
+        /// Number of retries after the first run.
+        max_retries: usize,
+    },
 }
 
 impl TransactionError for InvalidTransaction {}
@@ -528,6 +540,9 @@ impl fmt::Display for InvalidTransaction {
             Self::Eip7873MissingTarget => {
                 write!(f, "Eip7873 initcode transaction should have `to` address")
             }
+            Self::ConflictAfterRetries { max_retries } => {
+                write!(f, "transaction conflicted after {max_retries} retries")
+            }
         }
     }
 }
@@ -611,6 +626,28 @@ pub enum HaltReason {
     OutOfFunds,
     /// Call is too deep.
     CallTooDeep,
+    /// Opcode budget of the host is exhausted. Not possible to happen on mainnet.
+    OpcodeBudgetExhausted,
+    /// Maximum number of creates per transaction of the host is exceeded. Not possible to happen
+    /// on mainnet.
+    CreateLimitExceeded,
+    /// Gas budget of the called contract of the host is exhausted. Not possible to happen on
+    /// mainnet.
+    ContractGasBudgetExhausted,
+    /// Creation is vetoed by the host. Not possible to happen on mainnet.
+    CreateNotAllowed,
+    /// Call input is larger than the limit of the host. Not possible to happen on mainnet.
+    CallInputSizeLimit,
+    /// Call reenters an address more times than the limit of the host. Not possible to happen on
+    /// mainnet.
+    ReentrancyLimit,
+}
+
+impl HaltReason {
+    /// Returns true if this is one of the [`HaltReason::OutOfGas`] halts.
+    pub fn is_out_of_gas(&self) -> bool {
+        matches!(self, Self::OutOfGas(_))
+    }
 }
 
 /// Out of gas errors.
@@ -159,6 +159,19 @@ pub trait Transaction {
         min(max_price, base_fee.saturating_add(max_priority_fee))
     }
 
+    /// Returns the effective gas price with the priority fee raised to at least
+    /// `min_priority_fee`.
+    ///
+    /// The price is never raised above the max fee, the gas price field of the transaction.
+    fn effective_gas_price_with_min_priority_fee(
+        &self,
+        base_fee: u128,
+        min_priority_fee: u128,
+    ) -> u128 {
+        let min_price = min(self.gas_price(), base_fee.saturating_add(min_priority_fee));
+        self.effective_gas_price(base_fee).max(min_price)
+    }
+
     /// Returns the maximum balance that can be spent by the transaction.
     ///
     /// Return U256 or error if all values overflow U256 number.
@@ -48,6 +48,22 @@ pub struct CfgEnv<SPEC = SpecId> {
     /// Default values for Cancun is [`primitives::eip4844::BLOB_BASE_FEE_UPDATE_FRACTION_CANCUN`]
     /// and for Prague is [`primitives::eip4844::BLOB_BASE_FEE_UPDATE_FRACTION_PRAGUE`].
     pub blob_base_fee_update_fraction: Option<u64>,
+    /// Blob base fee used instead of the one derived from the block excess blob gas.
+    ///
+    /// It is used by the transaction validation, the caller balance check and the
+    /// `BLOBBASEFEE` instruction, so blob transactions can be simulated under
+    /// hypothetical blob fees. If `None`, the block blob base fee is used.
+    pub blob_base_fee_override: Option<u128>,
+    /// Maximum length of the revert data returned to the calling frame.
+    ///
+    /// Longer revert data is truncated, this is lossy and meant for sandboxing untrusted
+    /// contracts. If `None`, revert data is not limited.
+    pub max_revert_data: Option<usize>,
+    /// Minimum priority fee per gas paid to the beneficiary.
+    ///
+    /// The effective gas price of the transaction is raised to `basefee + min_priority_fee`, but
+    /// never above its max fee. Zero by default, which keeps the mainnet gas price.
+    pub min_priority_fee: u128,
     /// Configures the gas limit cap for the transaction.
     ///
     /// If `None`, default value defined by spec will be used.
@@ -145,6 +161,9 @@ impl<SPEC> CfgEnv<SPEC> {
             max_blobs_per_tx: None,
             tx_gas_limit_cap: None,
             blob_base_fee_update_fraction: None,
+            blob_base_fee_override: None,
+            max_revert_data: None,
+            min_priority_fee: 0,
             #[cfg(feature = "memory_limit")]
             memory_limit: (1 << 32) - 1,
             #[cfg(feature = "optional_balance_check")]
@@ -192,6 +211,9 @@ impl<SPEC> CfgEnv<SPEC> {
             tx_gas_limit_cap: self.tx_gas_limit_cap,
             max_blobs_per_tx: self.max_blobs_per_tx,
             blob_base_fee_update_fraction: self.blob_base_fee_update_fraction,
+            blob_base_fee_override: self.blob_base_fee_override,
+            max_revert_data: self.max_revert_data,
+            min_priority_fee: self.min_priority_fee,
             #[cfg(feature = "memory_limit")]
             memory_limit: self.memory_limit,
             #[cfg(feature = "optional_balance_check")]
@@ -225,6 +247,24 @@ impl<SPEC> CfgEnv<SPEC> {
         self.max_blobs_per_tx = None;
     }
 
+    /// Sets the blob base fee override, see [CfgEnv::blob_base_fee_override].
+    pub fn with_blob_base_fee_override(mut self, blob_base_fee: u128) -> Self {
+        self.blob_base_fee_override = Some(blob_base_fee);
+        self
+    }
+
+    /// Sets the maximum revert data length, see [CfgEnv::max_revert_data].
+    pub fn with_max_revert_data(mut self, max_revert_data: usize) -> Self {
+        self.max_revert_data = Some(max_revert_data);
+        self
+    }
+
+    /// Sets the minimum priority fee, see [CfgEnv::min_priority_fee].
+    pub fn with_min_priority_fee(mut self, min_priority_fee: u128) -> Self {
+        self.min_priority_fee = min_priority_fee;
+        self
+    }
+
     /// Sets the disable priority fee check flag.
     #[cfg(feature = "optional_priority_fee_check")]
     pub fn with_disable_priority_fee_check(mut self, disable: bool) -> Self {
@@ -344,6 +384,18 @@ impl<SPEC: Into<SpecId> + Copy> Cfg for CfgEnv<SPEC> {
             }
         }
     }
+
+    fn blob_base_fee_override(&self) -> Option<u128> {
+        self.blob_base_fee_override
+    }
+
+    fn max_revert_data(&self) -> usize {
+        self.max_revert_data.unwrap_or(usize::MAX)
+    }
+
+    fn min_priority_fee(&self) -> u128 {
+        self.min_priority_fee
+    }
 }
 
 impl<SPEC: Default> Default for CfgEnv<SPEC> {
@@ -2,7 +2,7 @@
 use crate::{block::BlockEnv, cfg::CfgEnv, journal::Journal, tx::TxEnv, LocalContext};
 use context_interface::{
     context::{ContextError, ContextSetters},
-    Block, Cfg, ContextTr, JournalTr, LocalContextTr, Transaction,
+    Block, Cfg, ContextTr, HostHooks, JournalTr, LocalContextTr, Transaction,
 };
 use database_interface::{Database, DatabaseRef, EmptyDB, WrapDatabaseRef};
 use derive_where::derive_where;
@@ -41,7 +41,7 @@ impl<
         DB: Database,
         CFG: Cfg,
         JOURNAL: JournalTr<Database = DB>,
-        CHAIN,
+        CHAIN: HostHooks,
         LOCAL: LocalContextTr,
     > ContextTr for Context<BLOCK, TX, CFG, DB, JOURNAL, CHAIN, LOCAL>
 {
@@ -135,7 +135,7 @@ impl<
         DB: Database,
         CFG: Cfg,
         JOURNAL: JournalTr<Database = DB>,
-        CHAIN,
+        CHAIN: HostHooks,
         LOCAL: LocalContextTr,
     > ContextSetters for Context<BLOCK, TX, CFG, DB, JOURNAL, CHAIN, LOCAL>
 {
@@ -2,16 +2,32 @@
 //!
 //! Entry submodule contains [`JournalEntry`] and [`JournalEntryTr`] traits.
 //! and inner submodule contains [`JournalInner`] struct that contains state.
+pub mod account_override;
+pub mod account_rlp;
+pub mod active_eips;
+pub mod changeset;
+pub mod code_hash_cache;
 pub mod entry;
+pub mod hook;
 pub mod inner;
-
+pub mod snapshot;
+pub mod witness;
+
+pub use account_override::AccountOverride;
+pub use account_rlp::AccountRlp;
+pub use active_eips::ActiveEips;
+pub use changeset::{AccountChange, AccountChangeKind, ChangeSet};
+pub use code_hash_cache::CodeHashCache;
 pub use entry::{JournalEntry, JournalEntryTr};
+pub use hook::{AccountCache, AccountLoadHook, BalanceHook, EntryHook, Hook};
 pub use inner::JournalInner;
+pub use snapshot::JournalSnapshot;
+pub use witness::{AccessWitness, AccountWitness};
 
 use bytecode::Bytecode;
 use context_interface::{
     context::{SStoreResult, SelfDestructResult, StateLoad},
-    journaled_state::{AccountLoad, JournalCheckpoint, JournalTr, TransferError},
+    journaled_state::{AccountLoad, CollisionPolicy, JournalCheckpoint, JournalTr, TransferError},
 };
 use core::ops::{Deref, DerefMut};
 use database_interface::Database;
@@ -132,6 +148,10 @@ impl<DB: Database, ENTRY: JournalEntryTr> JournalTr for Journal<DB, ENTRY> {
         self.inner.log(log)
     }
 
+    fn log_staged(&mut self, log: Log) {
+        self.inner.log_staged(log)
+    }
+
     fn selfdestruct(
         &mut self,
         address: Address,
@@ -144,6 +164,11 @@ impl<DB: Database, ENTRY: JournalEntryTr> JournalTr for Journal<DB, ENTRY> {
         self.inner.warm_preloaded_addresses.insert(address);
     }
 
+    #[inline]
+    fn reserve_entries(&mut self, additional: usize) {
+        self.inner.journal.reserve(additional);
+    }
+
     fn warm_coinbase_account(&mut self, address: Address) {
         self.inner.warm_coinbase_address = Some(address);
     }
@@ -280,11 +305,29 @@ impl<DB: Database, ENTRY: JournalEntryTr> JournalTr for Journal<DB, ENTRY> {
             .create_account_checkpoint(caller, address, balance, spec_id)
     }
 
+    #[inline]
+    fn create_account_checkpoint_with_policy(
+        &mut self,
+        caller: Address,
+        address: Address,
+        balance: U256,
+        spec_id: SpecId,
+        policy: CollisionPolicy,
+    ) -> Result<JournalCheckpoint, TransferError> {
+        self.inner
+            .create_account_checkpoint_with_policy(caller, address, balance, spec_id, policy)
+    }
+
     #[inline]
     fn take_logs(&mut self) -> Vec<Log> {
         self.inner.take_logs()
     }
 
+    #[inline]
+    fn changes_state(&self, excluded: &[Address]) -> bool {
+        self.inner.changes_state(excluded)
+    }
+
     #[inline]
     fn commit_tx(&mut self) {
         self.inner.commit_tx()
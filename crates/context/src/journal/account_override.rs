@@ -0,0 +1,25 @@
+//! Contains [`AccountOverride`] that is applied with [`JournalInner::apply_overrides`].
+//!
+//! [`JournalInner::apply_overrides`]: crate::JournalInner::apply_overrides
+use bytecode::Bytecode;
+use primitives::{HashMap, StorageKey, StorageValue, U256};
+
+/// Override of an account state, as in the `stateOverride` parameter of `eth_call`.
+///
+/// Fields that are `None` keep the loaded value.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccountOverride {
+    /// Balance of the account.
+    pub balance: Option<U256>,
+    /// Nonce of the account.
+    pub nonce: Option<u64>,
+    /// Code of the account, its hash is recomputed.
+    pub code: Option<Bytecode>,
+    /// Full storage of the account, slots that are not listed are zero.
+    pub state: Option<HashMap<StorageKey, StorageValue>>,
+    /// Storage slots to override, other slots keep their value.
+    ///
+    /// Applied after [`Self::state`] if both are set.
+    pub state_diff: Option<HashMap<StorageKey, StorageValue>>,
+}
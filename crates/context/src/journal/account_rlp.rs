@@ -0,0 +1,22 @@
+//! Contains [`AccountRlp`] that is returned by [`JournalInner::account_rlp_entries`].
+//!
+//! [`JournalInner::account_rlp_entries`]: crate::JournalInner::account_rlp_entries
+use primitives::{B256, U256};
+
+/// Fields of an account leaf in the state trie.
+///
+/// The storage root is not computed by the journal, it is `None` until it is filled in with the
+/// root of the slots returned by
+/// [`JournalInner::account_storage_for_root`](crate::JournalInner::account_storage_for_root).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccountRlp {
+    /// Account nonce.
+    pub nonce: u64,
+    /// Account balance.
+    pub balance: U256,
+    /// Root of the account storage trie, if computed.
+    pub storage_root: Option<B256>,
+    /// Hash of the account code.
+    pub code_hash: B256,
+}
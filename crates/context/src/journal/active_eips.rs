@@ -0,0 +1,38 @@
+//! Contains [`ActiveEips`] that is returned by [`JournalInner::active_eips`].
+//!
+//! [`JournalInner::active_eips`]: crate::JournalInner::active_eips
+use primitives::hardfork::SpecId;
+
+/// Snapshot of the behavior-changing EIPs that the journal applies for a spec.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ActiveEips {
+    /// [EIP-161](https://eips.ethereum.org/EIPS/eip-161): State trie clearing.
+    ///
+    /// Touched empty accounts are removed and created accounts start with nonce one.
+    pub eip161: bool,
+    /// [EIP-1153](https://eips.ethereum.org/EIPS/eip-1153): Transient storage.
+    pub eip1153: bool,
+    /// [EIP-6780](https://eips.ethereum.org/EIPS/eip-6780): SELFDESTRUCT only in same transaction.
+    pub eip6780: bool,
+    /// [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702): Set EOA account code.
+    pub eip7702: bool,
+}
+
+impl ActiveEips {
+    /// Returns EIPs active in the given spec.
+    pub const fn new(spec: SpecId) -> Self {
+        Self {
+            eip161: spec.is_enabled_in(SpecId::SPURIOUS_DRAGON),
+            eip1153: spec.is_enabled_in(SpecId::CANCUN),
+            eip6780: spec.is_enabled_in(SpecId::CANCUN),
+            eip7702: spec.is_enabled_in(SpecId::PRAGUE),
+        }
+    }
+}
+
+impl From<SpecId> for ActiveEips {
+    fn from(spec: SpecId) -> Self {
+        Self::new(spec)
+    }
+}
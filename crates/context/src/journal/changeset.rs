@@ -0,0 +1,68 @@
+//! Contains [`ChangeSet`] that is created by [`JournalInner::to_changeset`].
+//!
+//! [`JournalInner::to_changeset`]: crate::JournalInner::to_changeset
+use bytecode::Bytecode;
+use primitives::{Address, HashMap, StorageKey, StorageValue, U256};
+
+/// Minimal diff of the state made by the current transaction.
+///
+/// Accounts that were only loaded are not included, so this can be applied on top of the state
+/// before the transaction to replay it, for example on a follower node.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChangeSet {
+    /// Changed accounts.
+    pub accounts: HashMap<Address, AccountChange>,
+}
+
+/// Kind of the change of an account in [`ChangeSet`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AccountChangeKind {
+    /// Account is created, any previous account and its storage are replaced.
+    Created,
+    /// Account is modified, only the changed fields are set.
+    Modified,
+    /// Account is deleted together with its storage.
+    Deleted,
+}
+
+/// Change of a single account from [`ChangeSet`].
+///
+/// Fields are `Some` only if they differ from the previous value, for created accounts the
+/// previous value is an empty account. Fields of deleted accounts are always empty.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccountChange {
+    /// Kind of the change.
+    pub kind: AccountChangeKind,
+    /// New balance.
+    pub balance: Option<U256>,
+    /// New nonce.
+    pub nonce: Option<u64>,
+    /// New code.
+    pub code: Option<Bytecode>,
+    /// Written storage slots with their new values.
+    pub storage: HashMap<StorageKey, StorageValue>,
+}
+
+impl AccountChange {
+    /// Creates a change of the given kind without any changed fields.
+    pub fn new(kind: AccountChangeKind) -> Self {
+        Self {
+            kind,
+            balance: None,
+            nonce: None,
+            code: None,
+            storage: HashMap::default(),
+        }
+    }
+
+    /// Returns `true` if no field is changed.
+    pub fn is_empty(&self) -> bool {
+        self.balance.is_none()
+            && self.nonce.is_none()
+            && self.code.is_none()
+            && self.storage.is_empty()
+    }
+}
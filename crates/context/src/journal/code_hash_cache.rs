@@ -0,0 +1,47 @@
+//! Contains [`CodeHashCache`] that is used by [`JournalInner::set_code_cached`].
+//!
+//! [`JournalInner::set_code_cached`]: crate::JournalInner::set_code_cached
+use bytecode::Bytecode;
+use primitives::{HashMap, B256};
+
+/// Cache of bytecode hashes keyed by the pointer and length of the original bytes.
+///
+/// Clones of the same [`Bytecode`] share their bytes, so deploying a clone does not hash the
+/// code again. Cached bytecode is kept alive by the cache, so a pointer can't be reused by
+/// different code while it is cached.
+#[derive(Clone, Debug, Default)]
+pub struct CodeHashCache {
+    hashes: HashMap<(usize, usize), (Bytecode, B256)>,
+}
+
+impl CodeHashCache {
+    /// Creates a new empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the hash of the code, computing it only if the code is not cached.
+    #[inline]
+    pub fn hash(&mut self, code: &Bytecode) -> B256 {
+        let bytes = code.original_byte_slice();
+        self.hashes
+            .entry((bytes.as_ptr() as usize, bytes.len()))
+            .or_insert_with(|| (code.clone(), code.hash_slow()))
+            .1
+    }
+
+    /// Returns the number of cached hashes.
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    /// Returns `true` if the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+
+    /// Removes all cached hashes.
+    pub fn clear(&mut self) {
+        self.hashes.clear();
+    }
+}
@@ -5,8 +5,11 @@
 //! They are created when there is change to the state from loading (making it warm), changes to the balance,
 //! or removal of the storage slot. Check [`JournalEntryTr`] for more details.
 
-use primitives::{Address, StorageKey, StorageValue, KECCAK_EMPTY, PRECOMPILE3, U256};
-use state::{EvmState, TransientStorage};
+use bytecode::Bytecode;
+use core::mem;
+use primitives::{Address, StorageKey, StorageValue, B256, KECCAK_EMPTY, PRECOMPILE3, U256};
+use state::{EvmState, EvmStorage, EvmStorageSlot, TransientStorage};
+use std::boxed::Box;
 
 /// Trait for tracking and reverting state changes in the EVM.
 /// Journal entry contains information about state changes that can be reverted.
@@ -60,6 +63,45 @@ pub trait JournalEntryTr {
     /// Creates a journal entry for when an account's code is modified
     fn code_changed(address: Address) -> Self;
 
+    /// Creates a journal entry for when code, nonce and storage of an existing account are reset
+    /// on create collision. Records the previous code hash, code, nonce and storage for reverting.
+    ///
+    /// Storage is taken out of `had_storage`, leaving it empty, only if the entry is created.
+    ///
+    /// Default implementation returns `None`, the entry type can't revert the reset and create
+    /// collisions are always rejected.
+    fn code_and_nonce_reset(
+        _address: Address,
+        _had_code_hash: B256,
+        _had_code: Option<Bytecode>,
+        _had_nonce: u64,
+        _had_storage: &mut EvmStorage,
+    ) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
+
+    /// Returns `true` if this journal entry references the account with the given address.
+    ///
+    /// Reverting the entry requires all referenced accounts to be present in the state.
+    ///
+    /// Default implementation returns `true`, every entry is assumed to reference the account.
+    fn references_address(&self, _address: Address) -> bool {
+        true
+    }
+
+    /// Returns `true` if this journal entry records a change of the state.
+    ///
+    /// Warming, touching and transient storage changes are not state changes. Balance and nonce
+    /// changes of the `excluded` accounts are skipped, used to ignore the fee payment.
+    ///
+    /// Default implementation returns `true`, every entry is assumed to change the state.
+    fn changes_state(&self, _excluded: &[Address]) -> bool {
+        true
+    }
+
     /// Reverts the state change recorded by this journal entry
     ///
     /// More information on what is reverted can be found in [`JournalEntry`] enum.
@@ -213,6 +255,21 @@ pub enum JournalEntry {
         /// Address of account that had its code changed.
         address: Address,
     },
+    /// Code, nonce and storage of existing account reset on create collision
+    /// Action: Account code, nonce and storage reset
+    /// Revert: Revert to previous code, nonce and storage.
+    CodeAndNonceReset {
+        /// Previous code hash of account.
+        had_code_hash: B256,
+        /// Previous code of account, if it was loaded.
+        had_code: Option<Box<Bytecode>>,
+        /// Previous nonce of account.
+        had_nonce: u64,
+        /// Previous storage slots of account.
+        had_storage: Box<[(StorageKey, EvmStorageSlot)]>,
+        /// Address of account that had its code and nonce reset.
+        address: Address,
+    },
 }
 impl JournalEntryTr for JournalEntry {
     fn account_warmed(address: Address) -> Self {
@@ -287,6 +344,75 @@ impl JournalEntryTr for JournalEntry {
         JournalEntry::CodeChange { address }
     }
 
+    fn code_and_nonce_reset(
+        address: Address,
+        had_code_hash: B256,
+        had_code: Option<Bytecode>,
+        had_nonce: u64,
+        had_storage: &mut EvmStorage,
+    ) -> Option<Self> {
+        Some(JournalEntry::CodeAndNonceReset {
+            had_code_hash,
+            had_code: had_code.map(Box::new),
+            had_nonce,
+            had_storage: mem::take(had_storage).into_iter().collect(),
+            address,
+        })
+    }
+
+    fn references_address(&self, address: Address) -> bool {
+        match self {
+            JournalEntry::AccountDestroyed {
+                address: account,
+                target,
+                ..
+            } => *account == address || *target == address,
+            JournalEntry::BalanceTransfer { from, to, .. } => *from == address || *to == address,
+            JournalEntry::AccountWarmed { address: account }
+            | JournalEntry::AccountTouched { address: account }
+            | JournalEntry::BalanceChange {
+                address: account, ..
+            }
+            | JournalEntry::NonceChange { address: account }
+            | JournalEntry::AccountCreated {
+                address: account, ..
+            }
+            | JournalEntry::StorageChanged {
+                address: account, ..
+            }
+            | JournalEntry::StorageWarmed {
+                address: account, ..
+            }
+            | JournalEntry::TransientStorageChange {
+                address: account, ..
+            }
+            | JournalEntry::CodeChange { address: account }
+            | JournalEntry::CodeAndNonceReset {
+                address: account, ..
+            } => *account == address,
+        }
+    }
+
+    fn changes_state(&self, excluded: &[Address]) -> bool {
+        match self {
+            JournalEntry::AccountWarmed { .. }
+            | JournalEntry::AccountTouched { .. }
+            | JournalEntry::StorageWarmed { .. }
+            | JournalEntry::TransientStorageChange { .. } => false,
+            JournalEntry::BalanceChange { address, .. } | JournalEntry::NonceChange { address } => {
+                !excluded.contains(address)
+            }
+            JournalEntry::BalanceTransfer { from, to, .. } => {
+                !excluded.contains(from) || !excluded.contains(to)
+            }
+            JournalEntry::AccountDestroyed { .. }
+            | JournalEntry::AccountCreated { .. }
+            | JournalEntry::StorageChanged { .. }
+            | JournalEntry::CodeChange { .. }
+            | JournalEntry::CodeAndNonceReset { .. } => true,
+        }
+    }
+
     fn revert(
         self,
         state: &mut EvmState,
@@ -405,6 +531,19 @@ impl JournalEntryTr for JournalEntry {
                 acc.info.code_hash = KECCAK_EMPTY;
                 acc.info.code = None;
             }
+            JournalEntry::CodeAndNonceReset {
+                had_code_hash,
+                had_code,
+                had_nonce,
+                had_storage,
+                address,
+            } => {
+                let acc = state.get_mut(&address).unwrap();
+                acc.info.code_hash = had_code_hash;
+                acc.info.code = had_code.map(|code| *code);
+                acc.info.nonce = had_nonce;
+                acc.storage = had_storage.into_vec().into_iter().collect();
+            }
         }
     }
 }
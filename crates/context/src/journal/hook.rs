@@ -0,0 +1,148 @@
+//! Contains [`Hook`], the optional callbacks stored in [`JournalInner`].
+//!
+//! [`JournalInner`]: crate::JournalInner
+use core::{fmt, ptr};
+use primitives::{Address, U256};
+use state::Account;
+use std::{boxed::Box, vec::Vec};
+
+/// Optional callback stored in the [`JournalInner`](crate::JournalInner).
+///
+/// Callback is not part of the journal state and it is not serialized. A boxed callback can't be
+/// cloned, so **the clone of a hook is not set**: cloning the journal, or the context and EVM that
+/// contain it, drops its hooks. [`JournalInner::restore`](crate::JournalInner::restore) keeps the
+/// hooks of the restored journal. Two hooks are equal if both are not set or if they are the
+/// same callback.
+///
+/// The callback must not panic, as it is called in the middle of a state change and a panic
+/// would leave the journal and the state out of sync.
+pub struct Hook<F: ?Sized>(pub Option<Box<F>>);
+
+impl<F: ?Sized> Hook<F> {
+    /// Returns `true` if callback is set.
+    #[inline]
+    pub fn is_some(&self) -> bool {
+        self.0.is_some()
+    }
+}
+
+impl<F: ?Sized> Default for Hook<F> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<F: ?Sized> Clone for Hook<F> {
+    /// Returns a hook that is not set, see [`Hook`].
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl<F: ?Sized> PartialEq for Hook<F> {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.0, &other.0) {
+            (None, None) => true,
+            (Some(this), Some(other)) => ptr::addr_eq(&**this, &**other),
+            _ => false,
+        }
+    }
+}
+
+impl<F: ?Sized> Eq for Hook<F> {}
+
+impl<F: ?Sized> fmt::Debug for Hook<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Hook")
+            .field(&self.0.as_ref().map(|_| "FnMut"))
+            .finish()
+    }
+}
+
+/// Callback that observes journal entries in the order they are created.
+///
+/// It can be used to mirror the journal to external storage, for example a write-ahead log.
+/// Hook is not executed on reverts, reverted entries are dropped from the journal.
+pub type EntryHook<ENTRY> = Hook<dyn FnMut(&ENTRY) + Send + Sync>;
+
+impl<ENTRY> EntryHook<ENTRY> {
+    /// Creates a new hook with the given callback.
+    pub fn new(on_entry: impl FnMut(&ENTRY) + Send + Sync + 'static) -> Self {
+        Self(Some(Box::new(on_entry)))
+    }
+
+    /// Calls the callback with the entry and pushes it to the journal.
+    #[inline]
+    pub fn push(&mut self, journal: &mut Vec<ENTRY>, entry: ENTRY) {
+        if let Some(on_entry) = &mut self.0 {
+            on_entry(&entry);
+        }
+        journal.push(entry);
+    }
+}
+
+/// Callback that observes balance changes with the address, old and new balance, in the order
+/// they are made.
+///
+/// It is called for transfers, balance increments, caller fee deduction and selfdestruct
+/// transfers, so a balance feed can be built without diffing the state. Hook is not executed
+/// on reverts.
+pub type BalanceHook = Hook<dyn FnMut(Address, U256, U256) + Send + Sync>;
+
+impl BalanceHook {
+    /// Creates a new hook with the given callback.
+    pub fn new(on_balance_change: impl FnMut(Address, U256, U256) + Send + Sync + 'static) -> Self {
+        Self(Some(Box::new(on_balance_change)))
+    }
+
+    /// Calls the callback if it is set.
+    #[inline]
+    pub fn call(&mut self, address: Address, old: U256, new: U256) {
+        if let Some(on_balance_change) = &mut self.0 {
+            on_balance_change(address, old, new);
+        }
+    }
+}
+
+/// Callback that observes the accounts fetched from the database.
+///
+/// It is called when an account that is not in the journal state is loaded with `Database::basic`,
+/// including accounts that don't exist. Accessing an account that is already loaded, in the same
+/// or in a previous transaction, does not call it.
+pub type AccountLoadHook = Hook<dyn FnMut(Address) + Send + Sync>;
+
+impl AccountLoadHook {
+    /// Creates a new hook with the given callback.
+    pub fn new(on_account_load: impl FnMut(Address) + Send + Sync + 'static) -> Self {
+        Self(Some(Box::new(on_account_load)))
+    }
+
+    /// Calls the callback if it is set.
+    #[inline]
+    pub fn call(&mut self, address: Address) {
+        if let Some(on_account_load) = &mut self.0 {
+            on_account_load(address);
+        }
+    }
+}
+
+/// Cache that is consulted before the database when an account is loaded.
+///
+/// It is called when an account that is not in the journal state is loaded. If it returns an
+/// account, `Database::basic` is not called and the account is loaded as if it was returned by
+/// the database: only [`Account::info`] is used, it is warmed and journaled the same way.
+/// Returning `None` falls through to the database.
+pub type AccountCache = Hook<dyn FnMut(Address) -> Option<Account> + Send + Sync>;
+
+impl AccountCache {
+    /// Creates a new cache with the given callback.
+    pub fn new(get: impl FnMut(Address) -> Option<Account> + Send + Sync + 'static) -> Self {
+        Self(Some(Box::new(get)))
+    }
+
+    /// Returns the cached account, or `None` if callback is not set or has no account.
+    #[inline]
+    pub fn get(&mut self, address: Address) -> Option<Account> {
+        self.0.as_mut().and_then(|get| get(address))
+    }
+}
@@ -1,24 +1,31 @@
 //! Module containing the [`JournalInner`] that is part of [`crate::Journal`].
-use crate::entry::SelfdestructionRevertStatus;
+use crate::{
+    entry::SelfdestructionRevertStatus, AccessWitness, AccountCache, AccountChange,
+    AccountChangeKind, AccountLoadHook, AccountOverride, AccountRlp, AccountWitness, ActiveEips,
+    BalanceHook, ChangeSet, CodeHashCache, EntryHook, JournalSnapshot,
+};
 
 use super::JournalEntryTr;
 use bytecode::Bytecode;
 use context_interface::{
     context::{SStoreResult, SelfDestructResult, StateLoad},
-    journaled_state::{AccountLoad, JournalCheckpoint, TransferError},
+    journaled_state::{AccountLoad, CollisionPolicy, JournalCheckpoint, TransferError},
 };
 use core::mem;
 use database_interface::Database;
 use primitives::{
-    hardfork::SpecId::{self, *},
-    hash_map::Entry,
-    Address, HashMap, HashSet, Log, StorageKey, StorageValue, B256, KECCAK_EMPTY, U256,
+    hardfork::SpecId, hash_map::Entry, keccak256, Address, HashMap, HashSet, Log, StorageKey,
+    StorageValue, B256, I256, KECCAK_EMPTY, U256,
 };
-use state::{Account, EvmState, EvmStorageSlot, TransientStorage};
+use state::{Account, AccountStatus, EvmState, EvmStorageSlot, TransientStorage};
 use std::vec::Vec;
 /// Inner journal state that contains journal and state changes.
 ///
 /// Spec Id is a essential information for the Journal.
+///
+/// Hooks ([`Self::on_entry`], [`Self::on_balance_change`], [`Self::on_db_account_load`] and
+/// [`Self::account_cache`]) are not cloned: **the clone of a journal has no hooks set**.
+/// See [`Hook`](crate::Hook) for details.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct JournalInner<ENTRY> {
@@ -30,10 +37,55 @@ pub struct JournalInner<ENTRY> {
     pub transient_storage: TransientStorage,
     /// Emitted logs
     pub logs: Vec<Log>,
+    /// Logs buffered with [`Self::log_staged`] that are moved to [`Self::logs`] when the top
+    /// level call succeeds.
+    pub staged_logs: Vec<Log>,
+    /// Enables buffering of the logs in [`Self::log_staged`].
+    ///
+    /// This is non-standard behavior and is disabled by default.
+    pub staged_logs_enabled: bool,
     /// The current call stack depth
     pub depth: usize,
     /// The journal of state changes, one for each transaction
     pub journal: Vec<ENTRY>,
+    /// Callback invoked with every entry before it is pushed to [`Self::journal`].
+    ///
+    /// Not set by default. See [`EntryHook`] for details.
+    #[cfg_attr(feature = "serde", serde(skip, default = "EntryHook::default"))]
+    pub on_entry: EntryHook<ENTRY>,
+    /// Callback invoked with the old and new balance of every balance change.
+    ///
+    /// Not set by default. See [`BalanceHook`] for details.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub on_balance_change: BalanceHook,
+    /// Callback invoked with the address of every account fetched from the database.
+    ///
+    /// Not set by default. See [`AccountLoadHook`] for details.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub on_db_account_load: AccountLoadHook,
+    /// Cache consulted before the database when an account is loaded.
+    ///
+    /// Not set by default. See [`AccountCache`] for details.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub account_cache: AccountCache,
+    /// Number of cold account loads in the current transaction, including reverted ones.
+    pub cold_accounts_count: usize,
+    /// Number of cold storage slot loads in the current transaction, including reverted ones.
+    pub cold_slots_count: usize,
+    /// Storage slots in the order they were cold loaded in the current transaction.
+    ///
+    /// Only recorded if [`Self::slot_access_order_enabled`] is set. See
+    /// [`Self::slots_by_access_order`].
+    pub slot_access_order: Vec<(Address, StorageKey)>,
+    /// Enables recording of [`Self::slot_access_order`].
+    ///
+    /// Disabled by default.
+    pub slot_access_order_enabled: bool,
+    /// Number of balance additions that overflowed.
+    ///
+    /// See [`Self::overflow_events`].
+    #[cfg(feature = "fuzz-diagnostics")]
+    pub overflow_events: usize,
     /// Global transaction id that represent number of transactions executed (Including reverted ones).
     /// It can be different from number of `journal_history` as some transaction could be
     /// reverted or had a error on execution.
@@ -82,7 +134,19 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
             state: HashMap::default(),
             transient_storage: TransientStorage::default(),
             logs: Vec::new(),
+            staged_logs: Vec::new(),
+            staged_logs_enabled: false,
             journal: Vec::default(),
+            on_entry: EntryHook::default(),
+            on_balance_change: BalanceHook::default(),
+            on_db_account_load: AccountLoadHook::default(),
+            account_cache: AccountCache::default(),
+            cold_accounts_count: 0,
+            cold_slots_count: 0,
+            slot_access_order: Vec::new(),
+            slot_access_order_enabled: false,
+            #[cfg(feature = "fuzz-diagnostics")]
+            overflow_events: 0,
             transaction_id: 0,
             depth: 0,
             spec: SpecId::default(),
@@ -98,6 +162,18 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         mem::take(&mut self.logs)
     }
 
+    /// Returns `true` if the current transaction changed the state.
+    ///
+    /// Only the journal entries of the transaction are checked, see
+    /// [`JournalEntryTr::changes_state`]. Balance and nonce changes of the `excluded`
+    /// accounts are skipped.
+    #[inline]
+    pub fn changes_state(&self, excluded: &[Address]) -> bool {
+        self.journal
+            .iter()
+            .any(|entry| entry.changes_state(excluded))
+    }
+
     /// Prepare for next transaction, by committing the current journal to history, incrementing the transaction id
     /// and returning the logs.
     ///
@@ -106,14 +182,45 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
     ///
     /// `commit_tx` is used even for discarding transactions so transaction_id will be incremented.
     pub fn commit_tx(&mut self) {
+        self.commit_tx_fast();
+        // Load precompiles into warm_preloaded_addresses.
+        // TODO for precompiles we can use max transaction_id so they are always touched warm loaded.
+        // at least after state clear EIP.
+        reset_preloaded_addresses(&mut self.warm_preloaded_addresses, &self.precompiles);
+    }
+
+    /// Same as [`Self::commit_tx`] but skips resetting the warm preloaded addresses to the precompiles.
+    ///
+    /// # Precondition
+    ///
+    /// **The caller must guarantee that no address was warmed through the preloaded addresses
+    /// during the transaction**: no [`JournalTr::warm_account`][context_interface::JournalTr::warm_account]
+    /// call, which includes access list items without storage keys, and no change of precompiles.
+    /// Otherwise these addresses stay warm in the following transactions and gas is
+    /// undercharged.
+    ///
+    /// It is intended for tight loops over simple transactions, like blocks of plain transfers.
+    pub fn commit_tx_fast(&mut self) {
         // Clears all field from JournalInner. Doing it this way to avoid
         // missing any field.
         let Self {
             state,
             transient_storage,
             logs,
+            staged_logs,
+            staged_logs_enabled,
             depth,
             journal,
+            on_entry,
+            on_balance_change,
+            on_db_account_load,
+            account_cache,
+            cold_accounts_count,
+            cold_slots_count,
+            slot_access_order,
+            slot_access_order_enabled,
+            #[cfg(feature = "fuzz-diagnostics")]
+            overflow_events,
             transaction_id,
             spec,
             warm_preloaded_addresses,
@@ -124,6 +231,18 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         let _ = spec;
         let _ = precompiles;
         let _ = state;
+        let _ = staged_logs_enabled;
+        let _ = slot_access_order_enabled;
+        let _ = on_entry;
+        let _ = on_balance_change;
+        let _ = on_db_account_load;
+        let _ = account_cache;
+        #[cfg(feature = "fuzz-diagnostics")]
+        let _ = overflow_events;
+        // Preloaded addresses are reset by `commit_tx`.
+        let _ = warm_preloaded_addresses;
+        *cold_accounts_count = 0;
+        *cold_slots_count = 0;
         transient_storage.clear();
         *depth = 0;
 
@@ -132,13 +251,12 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
 
         // Clear coinbase address warming for next tx
         *warm_coinbase_address = None;
-        // Load precompiles into warm_preloaded_addresses.
-        // TODO for precompiles we can use max transaction_id so they are always touched warm loaded.
-        // at least after state clear EIP.
-        reset_preloaded_addresses(warm_preloaded_addresses, precompiles);
         // increment transaction id.
         *transaction_id += 1;
         logs.clear();
+        // Staged logs are emitted when the top level call succeeds.
+        staged_logs.clear();
+        slot_access_order.clear();
     }
 
     /// Discard the current transaction, by reverting the journal entries and incrementing the transaction id.
@@ -148,16 +266,38 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
             state,
             transient_storage,
             logs,
+            staged_logs,
+            staged_logs_enabled,
             depth,
             journal,
+            on_entry,
+            on_balance_change,
+            on_db_account_load,
+            account_cache,
+            cold_accounts_count,
+            cold_slots_count,
+            slot_access_order,
+            slot_access_order_enabled,
+            #[cfg(feature = "fuzz-diagnostics")]
+            overflow_events,
             transaction_id,
             spec,
             warm_preloaded_addresses,
             warm_coinbase_address,
             precompiles,
         } = self;
-
-        let is_spurious_dragon_enabled = spec.is_enabled_in(SPURIOUS_DRAGON);
+        let _ = staged_logs_enabled;
+        let _ = on_entry;
+        let _ = on_balance_change;
+        let _ = on_db_account_load;
+        let _ = account_cache;
+        #[cfg(feature = "fuzz-diagnostics")]
+        let _ = overflow_events;
+        let _ = slot_access_order_enabled;
+        *cold_accounts_count = 0;
+        *cold_slots_count = 0;
+
+        let is_spurious_dragon_enabled = ActiveEips::new(*spec).eip161;
         // iterate over all journals entries and revert our global state
         journal.drain(..).rev().for_each(|entry| {
             entry.revert(state, None, is_spurious_dragon_enabled);
@@ -165,6 +305,8 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         transient_storage.clear();
         *depth = 0;
         logs.clear();
+        staged_logs.clear();
+        slot_access_order.clear();
         *transaction_id += 1;
         // Clear coinbase address warming for next tx
         *warm_coinbase_address = None;
@@ -183,23 +325,47 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
             state,
             transient_storage,
             logs,
+            staged_logs,
+            staged_logs_enabled,
             depth,
             journal,
+            on_entry,
+            on_balance_change,
+            on_db_account_load,
+            account_cache,
+            cold_accounts_count,
+            cold_slots_count,
+            slot_access_order,
+            slot_access_order_enabled,
+            #[cfg(feature = "fuzz-diagnostics")]
+            overflow_events,
             transaction_id,
             spec,
             warm_preloaded_addresses,
             warm_coinbase_address,
             precompiles,
         } = self;
-        // Spec is not changed. And it is always set again in execution.
+        // Spec and staged logs flag are not changed.
         let _ = spec;
+        let _ = staged_logs_enabled;
+        let _ = slot_access_order_enabled;
+        let _ = on_entry;
+        let _ = on_balance_change;
+        let _ = on_db_account_load;
+        let _ = account_cache;
+        #[cfg(feature = "fuzz-diagnostics")]
+        let _ = overflow_events;
+        *cold_accounts_count = 0;
+        *cold_slots_count = 0;
         // Clear coinbase address warming for next tx
         *warm_coinbase_address = None;
         // Load precompiles into warm_preloaded_addresses.
         reset_preloaded_addresses(warm_preloaded_addresses, precompiles);
+        slot_access_order.clear();
 
         let state = mem::take(state);
         logs.clear();
+        staged_logs.clear();
         transient_storage.clear();
 
         // clear journal and journal history.
@@ -211,13 +377,163 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         state
     }
 
+    /// Same as [`Self::finalize`] but also returns the addresses of the accounts that were loaded
+    /// since the last finalize, for the caller to pre-load them in the next block.
+    ///
+    /// Warm here means hot in the cache, not warm in the EIP-2929 sense. Warmth that only lasts
+    /// a transaction is not included: precompiles, the coinbase and access list addresses are
+    /// only included if their account was actually loaded, and selfdestructed accounts are
+    /// excluded. Loading the accounts in the next block does not change gas, as accounts are cold
+    /// again at the start of every transaction.
+    #[inline]
+    pub fn finalize_keep_warm(&mut self) -> (EvmState, HashSet<Address>) {
+        let state = self.finalize();
+        let warm = state
+            .iter()
+            .filter(|(address, account)| {
+                !account.is_selfdestructed() && !self.precompiles.contains(*address)
+            })
+            .map(|(address, _)| *address)
+            .collect();
+        (state, warm)
+    }
+
+    /// Same as [`Self::finalize`] but returns only the changed accounts.
+    ///
+    /// Accounts that are touched or have changed storage slots are kept, accounts that were only
+    /// loaded are excluded from the returned state.
+    #[inline]
+    pub fn finalize_changes_only(&mut self) -> EvmState {
+        let mut state = self.finalize();
+        state.retain(|_, account| {
+            account.is_touched() || account.changed_storage_slots().next().is_some()
+        });
+        state
+    }
+
+    /// Resets the journal for a new run while keeping the allocated capacity.
+    ///
+    /// Same as [`Self::finalize`] but the state is cleared in place and discarded instead of
+    /// returned. This is meant for benchmark loops that don't want to allocate between runs.
+    #[inline]
+    pub fn reset_reuse(&mut self) {
+        // Clears all field from JournalInner. Doing it this way to avoid
+        // missing any field.
+        let Self {
+            state,
+            transient_storage,
+            logs,
+            staged_logs,
+            staged_logs_enabled,
+            depth,
+            journal,
+            on_entry,
+            on_balance_change,
+            on_db_account_load,
+            account_cache,
+            cold_accounts_count,
+            cold_slots_count,
+            slot_access_order,
+            slot_access_order_enabled,
+            #[cfg(feature = "fuzz-diagnostics")]
+            overflow_events,
+            transaction_id,
+            spec,
+            warm_preloaded_addresses,
+            warm_coinbase_address,
+            precompiles,
+        } = self;
+        // Spec and staged logs flag are not changed.
+        let _ = spec;
+        let _ = staged_logs_enabled;
+        let _ = slot_access_order_enabled;
+        let _ = on_entry;
+        let _ = on_balance_change;
+        let _ = on_db_account_load;
+        let _ = account_cache;
+        #[cfg(feature = "fuzz-diagnostics")]
+        let _ = overflow_events;
+        *cold_accounts_count = 0;
+        *cold_slots_count = 0;
+        // Clear coinbase address warming for next tx
+        *warm_coinbase_address = None;
+        // Load precompiles into warm_preloaded_addresses.
+        reset_preloaded_addresses(warm_preloaded_addresses, precompiles);
+
+        state.clear();
+        slot_access_order.clear();
+        logs.clear();
+        staged_logs.clear();
+        transient_storage.clear();
+        journal.clear();
+        *depth = 0;
+        *transaction_id = 0;
+    }
+
+    /// Removes the given accounts from the state and returns them, leaving the rest of the state
+    /// and the journal intact so execution can continue.
+    ///
+    /// This is useful to flush accounts to the database early. Addresses that are not loaded are
+    /// skipped. A taken account that is accessed again is loaded from the database, so its changes
+    /// should be committed there before execution continues.
+    ///
+    /// # Errors
+    ///
+    /// Reverting a journal entry requires its accounts to be present in the state, so accounts
+    /// that are referenced by a pending journal entry can't be taken. In that case nothing is
+    /// removed and the first such address is returned as an error. Journal is empty after
+    /// [`Self::commit_tx`] and all loaded accounts can be taken.
+    pub fn take_accounts(
+        &mut self,
+        addresses: &[Address],
+    ) -> Result<Vec<(Address, Account)>, Address> {
+        if let Some(address) = addresses.iter().find(|address| {
+            self.journal
+                .iter()
+                .any(|entry| entry.references_address(**address))
+        }) {
+            return Err(*address);
+        }
+
+        Ok(addresses
+            .iter()
+            .filter_map(|address| self.state.remove_entry(address))
+            .collect())
+    }
+
     /// Return reference to state.
     #[inline]
     pub fn state(&mut self) -> &mut EvmState {
         &mut self.state
     }
 
+    /// Returns the SpecId.
+    #[inline]
+    pub fn spec_id(&self) -> SpecId {
+        self.spec
+    }
+
+    /// Returns the number of cold account and cold storage slot loads, in that order, done in
+    /// the current transaction.
+    ///
+    /// Loads that were reverted are counted, as their cost was still paid.
+    #[inline]
+    pub fn cold_access_counts(&self) -> (usize, usize) {
+        (self.cold_accounts_count, self.cold_slots_count)
+    }
+
+    /// Returns the behavior-changing EIPs that are active for the current spec.
+    ///
+    /// This is derived from the SpecId on each call and is the same gating the journal uses.
+    #[inline]
+    pub fn active_eips(&self) -> ActiveEips {
+        ActiveEips::new(self.spec)
+    }
+
     /// Sets SpecId.
+    ///
+    /// Spec is read on each journal operation, so it can be changed between transactions
+    /// (after [`Self::commit_tx`]) and the next transaction will use the new rules.
     #[inline]
     pub fn set_spec_id(&mut self, spec: SpecId) {
         self.spec = spec;
@@ -229,15 +545,20 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
     #[inline]
     pub fn touch(&mut self, address: Address) {
         if let Some(account) = self.state.get_mut(&address) {
-            Self::touch_account(&mut self.journal, address, account);
+            Self::touch_account(&mut self.journal, &mut self.on_entry, address, account);
         }
     }
 
     /// Mark account as touched.
     #[inline]
-    fn touch_account(journal: &mut Vec<ENTRY>, address: Address, account: &mut Account) {
+    fn touch_account(
+        journal: &mut Vec<ENTRY>,
+        on_entry: &mut EntryHook<ENTRY>,
+        address: Address,
+        account: &mut Account,
+    ) {
         if !account.is_touched() {
-            journal.push(ENTRY::account_touched(address));
+            on_entry.push(journal, ENTRY::account_touched(address));
             account.mark_touch();
         }
     }
@@ -256,15 +577,93 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
             .expect("Account expected to be loaded") // Always assume that acc is already loaded
     }
 
+    /// Returns the loaded storage slots of the account sorted by the hash of their keys,
+    /// as they are laid out in the storage trie.
+    ///
+    /// Slots with zero present value are skipped as they are pruned from the trie.
+    /// Returns empty vector if the account is not loaded.
+    pub fn account_storage_for_root(&self, address: Address) -> Vec<(StorageKey, StorageValue)> {
+        let Some(account) = self.state.get(&address) else {
+            return Vec::new();
+        };
+        let mut slots: Vec<_> = account
+            .storage
+            .iter()
+            .filter(|(_, slot)| !slot.present_value.is_zero())
+            .map(|(key, slot)| (keccak256(key.to_be_bytes::<32>()), *key, slot.present_value))
+            .collect();
+        slots.sort_unstable_by_key(|(hash, _, _)| *hash);
+        slots
+            .into_iter()
+            .map(|(_, key, value)| (key, value))
+            .collect()
+    }
+
+    /// Returns the state trie leaves of the touched accounts, sorted by the hash of their
+    /// address as they are laid out in the trie.
+    ///
+    /// Selfdestructed accounts are skipped as they are removed from the trie, and since
+    /// Spurious Dragon (EIP-161) empty accounts are skipped too. Storage roots are not computed,
+    /// see [`AccountRlp`].
+    pub fn account_rlp_entries(&self) -> Vec<(Address, AccountRlp)> {
+        let is_state_clear = self.spec.is_enabled_in(SpecId::SPURIOUS_DRAGON);
+        let mut entries: Vec<_> = self
+            .state
+            .iter()
+            .filter(|(_, account)| {
+                account.is_touched()
+                    && !account.is_selfdestructed()
+                    && !(is_state_clear && account.is_empty())
+            })
+            .map(|(address, account)| {
+                let entry = AccountRlp {
+                    nonce: account.info.nonce,
+                    balance: account.info.balance,
+                    storage_root: None,
+                    code_hash: account.info.code_hash,
+                };
+                (keccak256(address), *address, entry)
+            })
+            .collect();
+        entries.sort_unstable_by_key(|(hash, _, _)| *hash);
+        entries
+            .into_iter()
+            .map(|(_, address, entry)| (address, entry))
+            .collect()
+    }
+
+    /// Returns the storage slots accessed in the current transaction whose present value equals
+    /// the original value.
+    ///
+    /// These slots were only read, or written back to their original value, so their access cost
+    /// was paid without changing the state. This should be called after execution and before
+    /// [`Self::commit_tx`].
+    pub fn read_only_slots(&self) -> impl Iterator<Item = (Address, StorageKey)> + '_ {
+        let transaction_id = self.transaction_id;
+        self.state.iter().flat_map(move |(address, account)| {
+            account
+                .storage
+                .iter()
+                .filter(move |(_, slot)| {
+                    slot.transaction_id == transaction_id && !slot.is_changed()
+                })
+                .map(move |(key, _)| (*address, *key))
+        })
+    }
+
     /// Set code and its hash to the account.
     ///
+    /// This is the entry point for callers that cache code hashes, the hash is not recomputed.
+    /// See [`Self::set_code_cached`] for a helper that uses a [`CodeHashCache`].
+    ///
     /// Note: Assume account is warm and that hash is calculated from code.
     #[inline]
     pub fn set_code_with_hash(&mut self, address: Address, code: Bytecode, hash: B256) {
         let account = self.state.get_mut(&address).unwrap();
-        Self::touch_account(&mut self.journal, address, account);
+        Self::touch_account(&mut self.journal, &mut self.on_entry, address, account);
 
-        self.journal.push(ENTRY::code_changed(address));
+        self.on_entry
+            .push(&mut self.journal, ENTRY::code_changed(address));
 
         account.info.code_hash = hash;
         account.info.code = Some(code);
@@ -288,6 +687,23 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         self.set_code_with_hash(address, code, hash)
     }
 
+    /// Same as [`Self::set_code`] but the hash is looked up in the `cache` and computed only
+    /// if the code is not cached.
+    ///
+    /// Assume account is warm.
+    #[inline]
+    pub fn set_code_cached(&mut self, address: Address, code: Bytecode, cache: &mut CodeHashCache) {
+        if let Bytecode::Eip7702(eip7702_bytecode) = &code {
+            if eip7702_bytecode.address().is_zero() {
+                self.set_code_with_hash(address, Bytecode::default(), KECCAK_EMPTY);
+                return;
+            }
+        }
+
+        let hash = cache.hash(&code);
+        self.set_code_with_hash(address, code, hash)
+    }
+
     /// Add journal entry for caller accounting.
     #[inline]
     pub fn caller_accounting_journal_entry(
@@ -297,14 +713,24 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         bump_nonce: bool,
     ) {
         // account balance changed.
-        self.journal
-            .push(ENTRY::balance_changed(address, old_balance));
+        self.on_entry.push(
+            &mut self.journal,
+            ENTRY::balance_changed(address, old_balance),
+        );
+        if self.on_balance_change.is_some() {
+            if let Some(account) = self.state.get(&address) {
+                self.on_balance_change
+                    .call(address, old_balance, account.info.balance);
+            }
+        }
         // account is touched.
-        self.journal.push(ENTRY::account_touched(address));
+        self.on_entry
+            .push(&mut self.journal, ENTRY::account_touched(address));
 
         if bump_nonce {
             // nonce changed.
-            self.journal.push(ENTRY::nonce_changed(address));
+            self.on_entry
+                .push(&mut self.journal, ENTRY::nonce_changed(address));
         }
     }
 
@@ -321,23 +747,35 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         let account = self.load_account(db, address)?.data;
         let old_balance = account.info.balance;
         account.info.balance = account.info.balance.saturating_add(balance);
+        let new_balance = account.info.balance;
 
         // march account as touched.
         if !account.is_touched() {
             account.mark_touch();
-            self.journal.push(ENTRY::account_touched(address));
+            self.on_entry
+                .push(&mut self.journal, ENTRY::account_touched(address));
+        }
+
+        #[cfg(feature = "fuzz-diagnostics")]
+        if old_balance.checked_add(balance).is_none() {
+            self.overflow_events += 1;
         }
 
         // add journal entry for balance increment.
-        self.journal
-            .push(ENTRY::balance_changed(address, old_balance));
+        self.on_entry.push(
+            &mut self.journal,
+            ENTRY::balance_changed(address, old_balance),
+        );
+        self.on_balance_change
+            .call(address, old_balance, new_balance);
         Ok(())
     }
 
     /// Increments the nonce of the account.
     #[inline]
     pub fn nonce_bump_journal_entry(&mut self, address: Address) {
-        self.journal.push(ENTRY::nonce_changed(address));
+        self.on_entry
+            .push(&mut self.journal, ENTRY::nonce_changed(address));
     }
 
     /// Transfers balance from two accounts. Returns error if sender balance is not enough.
@@ -352,7 +790,7 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         if balance.is_zero() {
             self.load_account(db, to)?;
             let to_account = self.state.get_mut(&to).unwrap();
-            Self::touch_account(&mut self.journal, to, to_account);
+            Self::touch_account(&mut self.journal, &mut self.on_entry, to, to_account);
             return Ok(None);
         }
         // load accounts
@@ -361,9 +799,10 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
 
         // sub balance from
         let from_account = self.state.get_mut(&from).unwrap();
-        Self::touch_account(&mut self.journal, from, from_account);
+        Self::touch_account(&mut self.journal, &mut self.on_entry, from, from_account);
         let from_balance = &mut from_account.info.balance;
 
+        let from_old_balance = *from_balance;
         let Some(from_balance_decr) = from_balance.checked_sub(balance) else {
             return Ok(Some(TransferError::OutOfFunds));
         };
@@ -371,16 +810,28 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
 
         // add balance to
         let to_account = &mut self.state.get_mut(&to).unwrap();
-        Self::touch_account(&mut self.journal, to, to_account);
+        Self::touch_account(&mut self.journal, &mut self.on_entry, to, to_account);
         let to_balance = &mut to_account.info.balance;
+        let to_old_balance = *to_balance;
         let Some(to_balance_incr) = to_balance.checked_add(balance) else {
+            #[cfg(feature = "fuzz-diagnostics")]
+            {
+                self.overflow_events += 1;
+            }
             return Ok(Some(TransferError::OverflowPayment));
         };
         *to_balance = to_balance_incr;
         // Overflow of U256 balance is not possible to happen on mainnet. We don't bother to return funds from from_acc.
 
-        self.journal
-            .push(ENTRY::balance_transfer(from, to, balance));
+        self.on_entry.push(
+            &mut self.journal,
+            ENTRY::balance_transfer(from, to, balance),
+        );
+        // when sending to itself, `to_old_balance` already contains the decrement.
+        self.on_balance_change
+            .call(from, from_old_balance, from_balance_decr);
+        self.on_balance_change
+            .call(to, to_old_balance, to_balance_incr);
 
         Ok(None)
     }
@@ -407,6 +858,28 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         target_address: Address,
         balance: U256,
         spec_id: SpecId,
+    ) -> Result<JournalCheckpoint, TransferError> {
+        self.create_account_checkpoint_with_policy(
+            caller,
+            target_address,
+            balance,
+            spec_id,
+            CollisionPolicy::Reject,
+        )
+    }
+
+    /// Same as [`Self::create_account_checkpoint`] but collision is resolved with the given
+    /// [`CollisionPolicy`].
+    ///
+    /// Code, nonce and storage that are reset by the policy are journaled and restored on revert.
+    #[inline]
+    pub fn create_account_checkpoint_with_policy(
+        &mut self,
+        caller: Address,
+        target_address: Address,
+        balance: U256,
+        spec_id: SpecId,
+        policy: CollisionPolicy,
     ) -> Result<JournalCheckpoint, TransferError> {
         // Enter subroutine
         let checkpoint = self.checkpoint();
@@ -422,34 +895,65 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         // Newly created account is present, as we just loaded it.
         let target_acc = self.state.get_mut(&target_address).unwrap();
         let last_journal = &mut self.journal;
+        let on_entry = &mut self.on_entry;
 
         // New account can be created if:
         // Bytecode is not empty.
         // Nonce is not zero
         // Account is not precompile.
         if target_acc.info.code_hash != KECCAK_EMPTY || target_acc.info.nonce != 0 {
-            self.checkpoint_revert(checkpoint);
-            return Err(TransferError::CreateCollision);
+            // Entry types that can't revert the reset reject the collision.
+            let entry = match policy {
+                CollisionPolicy::Reject => None,
+                _ => ENTRY::code_and_nonce_reset(
+                    target_address,
+                    target_acc.info.code_hash,
+                    target_acc.info.code.clone(),
+                    target_acc.info.nonce,
+                    &mut target_acc.storage,
+                ),
+            };
+            let Some(entry) = entry else {
+                self.checkpoint_revert(checkpoint);
+                return Err(TransferError::CreateCollision);
+            };
+            on_entry.push(last_journal, entry);
+            // Slots loaded from now on are empty, as the account is created.
+            let info = &mut target_acc.info;
+            info.code_hash = KECCAK_EMPTY;
+            info.code = None;
+            if policy == CollisionPolicy::Overwrite {
+                info.nonce = 0;
+            }
         }
 
         // set account status to create.
         let is_created_globally = target_acc.mark_created_locally();
 
         // this entry will revert set nonce.
-        last_journal.push(ENTRY::account_created(target_address, is_created_globally));
+        on_entry.push(
+            last_journal,
+            ENTRY::account_created(target_address, is_created_globally),
+        );
         target_acc.info.code = None;
         // EIP-161: State trie clearing (invariant-preserving alternative)
-        if spec_id.is_enabled_in(SPURIOUS_DRAGON) {
+        if ActiveEips::new(spec_id).eip161 {
             // nonce is going to be reset to zero in AccountCreated journal entry.
-            target_acc.info.nonce = 1;
+            // Nonce of merged account is kept.
+            target_acc.info.nonce = target_acc.info.nonce.max(1);
         }
 
         // touch account. This is important as for pre SpuriousDragon account could be
         // saved even empty.
-        Self::touch_account(last_journal, target_address, target_acc);
+        Self::touch_account(last_journal, on_entry, target_address, target_acc);
 
         // Add balance to created account, as we already have target here.
+        let target_old_balance = target_acc.info.balance;
         let Some(new_balance) = target_acc.info.balance.checked_add(balance) else {
+            #[cfg(feature = "fuzz-diagnostics")]
+            {
+                self.overflow_events += 1;
+            }
             self.checkpoint_revert(checkpoint);
             return Err(TransferError::OverflowPayment);
         };
@@ -459,7 +963,14 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         self.state.get_mut(&caller).unwrap().info.balance -= balance;
 
         // add journal entry of transferred balance
-        last_journal.push(ENTRY::balance_transfer(caller, target_address, balance));
+        on_entry.push(
+            last_journal,
+            ENTRY::balance_transfer(caller, target_address, balance),
+        );
+        self.on_balance_change
+            .call(caller, caller_balance, caller_balance - balance);
+        self.on_balance_change
+            .call(target_address, target_old_balance, new_balance);
 
         Ok(checkpoint)
     }
@@ -479,16 +990,25 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
     #[inline]
     pub fn checkpoint_commit(&mut self) {
         self.depth -= 1;
+        // Top level call succeeded, staged logs are emitted.
+        if self.depth == 0 && !self.staged_logs.is_empty() {
+            self.logs.append(&mut self.staged_logs);
+        }
     }
 
     /// Reverts all changes to state until given checkpoint.
     #[inline]
     pub fn checkpoint_revert(&mut self, checkpoint: JournalCheckpoint) {
-        let is_spurious_dragon_enabled = self.spec.is_enabled_in(SPURIOUS_DRAGON);
+        let is_spurious_dragon_enabled = self.active_eips().eip161;
         let state = &mut self.state;
         let transient_storage = &mut self.transient_storage;
         self.depth -= 1;
         self.logs.truncate(checkpoint.log_i);
+        // Staged logs are not part of the checkpoint, they are discarded only if
+        // the whole transaction fails.
+        if self.depth == 0 {
+            self.staged_logs.clear();
+        }
 
         // iterate over last N journals sets and revert our global state
         self.journal
@@ -499,6 +1019,12 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
             });
     }
 
+    /// Returns the last journal entry of the current transaction.
+    #[inline]
+    pub fn last_entry(&self) -> Option<&ENTRY> {
+        self.journal.last()
+    }
+
     /// Performs selfdestruct action.
     /// Transfers balance from address to target. Check if target exist/is_cold
     ///
@@ -528,8 +1054,25 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
             let acc_balance = self.state.get(&address).unwrap().info.balance;
 
             let target_account = self.state.get_mut(&target).unwrap();
-            Self::touch_account(&mut self.journal, target, target_account);
+            Self::touch_account(
+                &mut self.journal,
+                &mut self.on_entry,
+                target,
+                target_account,
+            );
+            let target_old_balance = target_account.info.balance;
+            #[cfg(feature = "fuzz-diagnostics")]
+            if target_old_balance.checked_add(acc_balance).is_none() {
+                self.overflow_events += 1;
+            }
             target_account.info.balance += acc_balance;
+            if !acc_balance.is_zero() {
+                self.on_balance_change.call(
+                    target,
+                    target_old_balance,
+                    target_account.info.balance,
+                );
+            }
         }
 
         let acc = self.state.get_mut(&address).unwrap();
@@ -543,10 +1086,10 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
             SelfdestructionRevertStatus::RepeatedSelfdestruction
         };
 
-        let is_cancun_enabled = spec.is_enabled_in(CANCUN);
+        let is_eip6780_enabled = ActiveEips::new(spec).eip6780;
 
         // EIP-6780 (Cancun hard-fork): selfdestruct only if contract is created in the same tx
-        let journal_entry = if acc.is_created_locally() || !is_cancun_enabled {
+        let journal_entry = if acc.is_created_locally() || !is_eip6780_enabled {
             acc.mark_selfdestructed_locally();
             acc.info.balance = U256::ZERO;
             Some(ENTRY::account_destroyed(
@@ -567,7 +1110,10 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         };
 
         if let Some(entry) = journal_entry {
-            self.journal.push(entry);
+            self.on_entry.push(&mut self.journal, entry);
+            if !balance.is_zero() {
+                self.on_balance_change.call(address, balance, U256::ZERO);
+            }
         };
 
         Ok(StateLoad {
@@ -605,7 +1151,7 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         address: Address,
     ) -> Result<StateLoad<AccountLoad>, DB::Error> {
         let spec = self.spec;
-        let is_eip7702_enabled = spec.is_enabled_in(SpecId::PRAGUE);
+        let is_eip7702_enabled = ActiveEips::new(spec).eip7702;
         let account = self.load_account_optional(db, address, is_eip7702_enabled, [])?;
         let is_empty = account.state_clear_aware_is_empty(spec);
 
@@ -627,6 +1173,66 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         Ok(account_load)
     }
 
+    /// Applies state overrides to the accounts, as done by the `stateOverride` parameter of
+    /// `eth_call`.
+    ///
+    /// Overrides are applied as the baseline state: they are not journaled, overridden storage
+    /// slots are not changed slots and accounts and slots stay cold. This is meant to be called
+    /// before the transaction is executed. Accounts that are not loaded are fetched from the
+    /// database first.
+    ///
+    /// A full storage override ([`AccountOverride::state`]) clears the loaded slots and marks the
+    /// account as created, so slots that are not overridden are read as zero instead of from
+    /// the database.
+    pub fn apply_overrides<DB: Database>(
+        &mut self,
+        db: &mut DB,
+        overrides: impl IntoIterator<Item = (Address, AccountOverride)>,
+    ) -> Result<(), DB::Error> {
+        let transaction_id = self.transaction_id;
+        for (address, account_override) in overrides {
+            let account = match self.state.entry(address) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(vac) => {
+                    let mut account = if let Some(info) = db.basic(address)? {
+                        info.into()
+                    } else {
+                        Account::new_not_existing(transaction_id)
+                    };
+                    account.mark_cold();
+                    vac.insert(account)
+                }
+            };
+
+            let AccountOverride {
+                balance,
+                nonce,
+                code,
+                state,
+                state_diff,
+            } = account_override;
+            if let Some(balance) = balance {
+                account.info.balance = balance;
+            }
+            if let Some(nonce) = nonce {
+                account.info.nonce = nonce;
+            }
+            if let Some(code) = code {
+                account.info.set_code(code);
+            }
+            if state.is_some() {
+                account.storage.clear();
+                account.mark_created();
+            }
+            for (key, value) in state.into_iter().chain(state_diff).flatten() {
+                let mut slot = EvmStorageSlot::new(value, transaction_id);
+                slot.mark_cold();
+                account.storage.insert(key, slot);
+            }
+        }
+        Ok(())
+    }
+
     /// Loads account and its code. If account is already loaded it will load its code.
     ///
     /// It will mark account as warm loaded. If not existing Database will be queried for data.
@@ -672,10 +1278,15 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
                 }
             }
             Entry::Vacant(vac) => {
-                let account = if let Some(account) = db.basic(address)? {
-                    account.into()
+                let account = if let Some(account) = self.account_cache.get(address) {
+                    account.info.into()
                 } else {
-                    Account::new_not_existing(self.transaction_id)
+                    self.on_db_account_load.call(address);
+                    if let Some(account) = db.basic(address)? {
+                        account.into()
+                    } else {
+                        Account::new_not_existing(self.transaction_id)
+                    }
                 };
 
                 // Precompiles among some other account(coinbase included) are warm loaded so we need to take that into account
@@ -691,7 +1302,9 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
 
         // journal loading of cold account.
         if load.is_cold {
-            self.journal.push(ENTRY::account_warmed(address));
+            self.on_entry
+                .push(&mut self.journal, ENTRY::account_warmed(address));
+            self.cold_accounts_count += 1;
         }
         if load_code {
             let info = &mut load.data.info;
@@ -706,14 +1319,17 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         }
 
         for storage_key in storage_keys.into_iter() {
-            sload_with_account(
-                load.data,
-                db,
-                &mut self.journal,
-                self.transaction_id,
-                address,
-                storage_key,
-            )?;
+            let slot = load_slot(load.data, db, self.transaction_id, address, storage_key)?;
+            if slot.is_cold {
+                self.on_entry.push(
+                    &mut self.journal,
+                    ENTRY::storage_warmed(address, storage_key),
+                );
+                if self.slot_access_order_enabled {
+                    self.slot_access_order.push((address, storage_key));
+                }
+            }
+            self.cold_slots_count += slot.is_cold as usize;
         }
         Ok(load)
     }
@@ -733,14 +1349,17 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
         // assume acc is warm
         let account = self.state.get_mut(&address).unwrap();
         // only if account is created in this tx we can assume that storage is empty.
-        sload_with_account(
-            account,
-            db,
-            &mut self.journal,
-            self.transaction_id,
-            address,
-            key,
-        )
+        let slot = load_slot(account, db, self.transaction_id, address, key)?;
+        if slot.is_cold {
+            // add it to journal as cold loaded.
+            self.on_entry
+                .push(&mut self.journal, ENTRY::storage_warmed(address, key));
+            if self.slot_access_order_enabled {
+                self.slot_access_order.push((address, key));
+            }
+        }
+        self.cold_slots_count += slot.is_cold as usize;
+        Ok(slot)
     }
 
     /// Stores storage slot.
@@ -775,8 +1394,10 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
             ));
         }
 
-        self.journal
-            .push(ENTRY::storage_changed(address, key, present.data));
+        self.on_entry.push(
+            &mut self.journal,
+            ENTRY::storage_changed(address, key, present.data),
+        );
         // insert value into present state.
         slot.present_value = new;
         Ok(StateLoad::new(
@@ -831,16 +1452,382 @@ impl<ENTRY: JournalEntryTr> JournalInner<ENTRY> {
 
         if let Some(had_value) = had_value {
             // insert in journal only if value was changed.
-            self.journal
-                .push(ENTRY::transient_storage_changed(address, key, had_value));
+            self.on_entry.push(
+                &mut self.journal,
+                ENTRY::transient_storage_changed(address, key, had_value),
+            );
         }
     }
 
+    /// Removes all transient storage of the account.
+    ///
+    /// Every removed slot is journaled, so values are restored on [`Self::checkpoint_revert`].
+    ///
+    /// This is non-standard behavior intended for testing, transient storage is otherwise
+    /// cleared only at the end of the transaction.
+    pub fn clear_transient_for(&mut self, address: Address) {
+        let journal = &mut self.journal;
+        let on_entry = &mut self.on_entry;
+        self.transient_storage
+            .retain(|&(slot_address, key), value| {
+                if slot_address != address {
+                    return true;
+                }
+                on_entry.push(
+                    journal,
+                    ENTRY::transient_storage_changed(address, key, *value),
+                );
+                false
+            });
+    }
+
+    /// Returns a copy of the whole transient storage.
+    ///
+    /// The map is cloned, so the cost grows with the number of non-zero transient slots.
+    /// Prefer [`Self::checkpoint`] and [`Self::checkpoint_revert`] when the state to restore
+    /// lines up with a checkpoint.
+    pub fn transient_snapshot_full(&self) -> TransientStorage {
+        self.transient_storage.clone()
+    }
+
+    /// Replaces the whole transient storage with a snapshot from [`Self::transient_snapshot_full`].
+    ///
+    /// The replacement is not journaled. Transient storage entries journaled before the restore
+    /// are still reverted on [`Self::checkpoint_revert`] and are applied on top of the snapshot.
+    ///
+    /// This is non-standard behavior intended for testing.
+    pub fn restore_transient(&mut self, snapshot: TransientStorage) {
+        self.transient_storage = snapshot;
+    }
+
     /// Pushes log into subroutine.
     #[inline]
     pub fn log(&mut self, log: Log) {
         self.logs.push(log);
     }
+
+    /// Returns the logs emitted by `address`, in emission order.
+    ///
+    /// Logs of reverted calls are already removed. Logs buffered with [`Self::log_staged`] are
+    /// not included until the top level call succeeds.
+    #[inline]
+    pub fn logs_for(&self, address: Address) -> impl Iterator<Item = &Log> + '_ {
+        self.logs.iter().filter(move |log| log.address == address)
+    }
+
+    /// Applies the [`ChangeSet`] to the state as the new baseline, without journaling it.
+    ///
+    /// Applied values become the original values of the accounts and storage slots, so they are
+    /// not reverted and are not part of the changes of the current transaction. Accounts and slots
+    /// are warm in the current transaction.
+    ///
+    /// Modified accounts keep their unchanged fields, so they should be loaded before. Modified
+    /// accounts that are not in the state are inserted with empty values for unchanged fields.
+    pub fn apply_changeset(&mut self, changeset: ChangeSet) {
+        let transaction_id = self.transaction_id;
+        for (address, change) in changeset.accounts {
+            let account = match change.kind {
+                AccountChangeKind::Deleted => {
+                    self.state
+                        .insert(address, Account::new_not_existing(transaction_id));
+                    continue;
+                }
+                AccountChangeKind::Created => {
+                    self.state.insert(address, Account::default());
+                    self.state.get_mut(&address).unwrap()
+                }
+                AccountChangeKind::Modified => self.state.entry(address).or_default(),
+            };
+            account.transaction_id = transaction_id;
+            account.status -= AccountStatus::Cold;
+
+            let info = &mut account.info;
+            if let Some(balance) = change.balance {
+                info.balance = balance;
+            }
+            if let Some(nonce) = change.nonce {
+                info.nonce = nonce;
+            }
+            if let Some(code) = change.code {
+                info.code_hash = code.hash_slow();
+                info.code = Some(code);
+            }
+            account.storage.extend(
+                change
+                    .storage
+                    .into_iter()
+                    .map(|(key, value)| (key, EvmStorageSlot::new(value, transaction_id))),
+            );
+        }
+    }
+
+    /// Buffers the log until the top level call of the transaction returns.
+    ///
+    /// Staged logs are appended to [`Self::logs`] when the top level checkpoint is committed,
+    /// so they are taken with [`Self::take_logs`] together with the other logs of the
+    /// transaction. They are not affected by reverts of inner calls and are discarded if the top
+    /// level call reverts or on [`Self::discard_tx`].
+    ///
+    /// If [`Self::staged_logs_enabled`] is not set or there is no call to revert, this is the
+    /// same as [`Self::log`].
+    #[inline]
+    pub fn log_staged(&mut self, log: Log) {
+        if self.staged_logs_enabled && self.depth != 0 {
+            self.staged_logs.push(log);
+        } else {
+            self.log(log);
+        }
+    }
+}
+
+impl<ENTRY: JournalEntryTr + Clone> JournalInner<ENTRY> {
+    /// Takes a snapshot of the journal that can be restored with [`Self::restore`].
+    ///
+    /// The whole journal state is cloned, it is meant to be taken between transactions.
+    #[inline]
+    pub fn snapshot(&self) -> JournalSnapshot<ENTRY> {
+        JournalSnapshot(self.clone())
+    }
+
+    /// Restores the journal to the state of the `snapshot`, discarding all changes made since it
+    /// was taken. Hooks and the account cache are kept.
+    #[inline]
+    pub fn restore(&mut self, snapshot: &JournalSnapshot<ENTRY>) {
+        let mut inner = snapshot.0.clone();
+        mem::swap(&mut inner.on_entry, &mut self.on_entry);
+        mem::swap(&mut inner.on_balance_change, &mut self.on_balance_change);
+        mem::swap(&mut inner.on_db_account_load, &mut self.on_db_account_load);
+        mem::swap(&mut inner.account_cache, &mut self.account_cache);
+        *self = inner;
+    }
+
+    /// Same as [`Self::checkpoint_revert`] but returns the topmost reverted entry, the last state
+    /// change before the revert.
+    ///
+    /// Returns `None` if there was no entry since the checkpoint.
+    #[inline]
+    pub fn checkpoint_revert_with_last_entry(
+        &mut self,
+        checkpoint: JournalCheckpoint,
+    ) -> Option<ENTRY> {
+        let last_entry = self
+            .journal
+            .get(checkpoint.journal_i..)
+            .and_then(<[ENTRY]>::last)
+            .cloned();
+        self.checkpoint_revert(checkpoint);
+        last_entry
+    }
+
+    /// Applies a state change and reverts it with `entry`, asserting that the state is restored.
+    ///
+    /// This is an invariant test for [`JournalEntryTr::revert`] of custom journal entries.
+    /// Journal entries record previous values, so the change itself is made by `apply`, the way
+    /// the journal would make it before pushing `entry`. Accounts referenced by the entry must be
+    /// loaded first.
+    ///
+    /// ```ignore
+    /// journal.load_account(&mut db, address)?;
+    /// let nonce = journal.account(address).info.nonce;
+    /// journal.apply_and_revert_entry(MyEntry::nonce_changed(address), |state, _| {
+    ///     state.get_mut(&address).unwrap().info.nonce = nonce + 1;
+    /// });
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the state or transient storage after the revert differs from before `apply`.
+    #[cfg(any(test, feature = "test_utils"))]
+    pub fn apply_and_revert_entry(
+        &mut self,
+        entry: ENTRY,
+        apply: impl FnOnce(&mut EvmState, &mut TransientStorage),
+    ) {
+        let state = self.state.clone();
+        let transient_storage = self.transient_storage.clone();
+        apply(&mut self.state, &mut self.transient_storage);
+        let is_spurious_dragon_enabled = self.active_eips().eip161;
+        entry.revert(
+            &mut self.state,
+            Some(&mut self.transient_storage),
+            is_spurious_dragon_enabled,
+        );
+        assert_eq!(
+            self.state, state,
+            "revert of the entry didn't restore the state"
+        );
+        assert_eq!(
+            self.transient_storage, transient_storage,
+            "revert of the entry didn't restore the transient storage"
+        );
+    }
+
+    /// Returns the storage slots accessed in the current transaction in the order they were first
+    /// accessed.
+    ///
+    /// Unlike iterating [`Self::state`], which is in hash order, this follows the access
+    /// sequence. Slots are accessed by `SLOAD`, `SSTORE` and access lists. A slot that is accessed
+    /// again, even after a revert, keeps its first position. Slots of accounts removed with
+    /// [`Self::take_accounts`] are skipped.
+    ///
+    /// Empty unless [`Self::slot_access_order_enabled`] is set.
+    pub fn slots_by_access_order(&self) -> Vec<(Address, StorageKey)> {
+        let mut seen = HashSet::<(Address, StorageKey)>::default();
+        self.slot_access_order
+            .iter()
+            .filter(|(address, key)| {
+                self.state
+                    .get(address)
+                    .is_some_and(|account| account.storage.contains_key(key))
+            })
+            .filter(|slot| seen.insert(**slot))
+            .copied()
+            .collect()
+    }
+
+    /// Returns the EIP-7702 delegations of the loaded accounts as `(account, delegate)` pairs.
+    ///
+    /// Only accounts in [`Self::state`] are scanned, so delegations of accounts that were not
+    /// loaded are not returned. Delegations set in the current transaction are included.
+    pub fn active_delegations(&self) -> impl Iterator<Item = (Address, Address)> + '_ {
+        self.state
+            .iter()
+            .filter_map(|(address, account)| match &account.info.code {
+                Some(Bytecode::Eip7702(code)) => Some((*address, code.address())),
+                _ => None,
+            })
+    }
+
+    /// Returns the number of balance additions that overflowed `U256`.
+    ///
+    /// Counted are the saturating addition of [`Self::balance_incr`], the checked additions of
+    /// [`Self::transfer`] and [`Self::create_account_checkpoint`], and the balance moved to the
+    /// target of [`Self::selfdestruct`]. Such balances are not possible on mainnet, the counter is
+    /// meant for fuzzing harnesses looking for these edge cases.
+    ///
+    /// The counter is not reset by [`Self::finalize`] or [`Self::reset_reuse`], set
+    /// [`Self::overflow_events`] field to zero to restart it.
+    #[cfg(feature = "fuzz-diagnostics")]
+    pub fn overflow_events(&self) -> usize {
+        self.overflow_events
+    }
+
+    /// Returns the values of all loaded accounts and storage slots as of the start of the
+    /// current transaction.
+    ///
+    /// Values are obtained by reverting the journal of the current transaction on a copy of the
+    /// state, so this should be called after execution and before [`Self::commit_tx`] or
+    /// [`Self::finalize`].
+    pub fn access_witness(&self) -> AccessWitness {
+        let mut state = self.state.clone();
+        let is_spurious_dragon_enabled = self.active_eips().eip161;
+        for entry in self.journal.iter().rev() {
+            entry
+                .clone()
+                .revert(&mut state, None, is_spurious_dragon_enabled);
+        }
+
+        let accounts = state
+            .into_iter()
+            .map(|(address, account)| {
+                let storage = account
+                    .storage
+                    .into_iter()
+                    .map(|(key, slot)| (key, slot.present_value))
+                    .collect();
+                (
+                    address,
+                    AccountWitness {
+                        info: account.info,
+                        storage,
+                    },
+                )
+            })
+            .collect();
+
+        AccessWitness { accounts }
+    }
+
+    /// Returns the net balance change over the current transaction, the present balance minus
+    /// the balance at the start of the transaction.
+    ///
+    /// Only accounts with a nonzero net change are returned, so a selfdestructed account is
+    /// included even though it is not touched. Starting balances are obtained with
+    /// [`Self::access_witness`], so value transfers, fee deductions, rewards and selfdestruct
+    /// transfers are all included. This should be called after execution and before
+    /// [`Self::commit_tx`] or [`Self::finalize`].
+    pub fn balance_deltas(&self) -> impl Iterator<Item = (Address, I256)> + '_ {
+        let witness = self.access_witness();
+        self.state.iter().filter_map(move |(address, account)| {
+            let before = witness.accounts[address].info.balance;
+            // Two's complement difference, balances are far below `2^255`.
+            let delta = I256::from_raw(account.info.balance.wrapping_sub(before));
+            (!delta.is_zero()).then_some((*address, delta))
+        })
+    }
+
+    /// Returns the changes of the current transaction as a minimal [`ChangeSet`].
+    ///
+    /// Unlike [`Self::finalize`] only the changes are returned: accounts and storage slots that
+    /// were only read, or written back to their previous value, are omitted. Selfdestructed
+    /// accounts and, after EIP-161, touched empty accounts are deleted. Previous values are
+    /// obtained with [`Self::access_witness`], so this should be called after execution and
+    /// before [`Self::commit_tx`] or [`Self::finalize`].
+    pub fn to_changeset(&self) -> ChangeSet {
+        let witness = self.access_witness();
+        let is_spurious_dragon_enabled = self.active_eips().eip161;
+        let mut accounts = HashMap::default();
+        for (address, account) in &self.state {
+            let before = &witness.accounts[address];
+            let is_deleted = account.is_selfdestructed_locally()
+                || (is_spurious_dragon_enabled && account.is_touched() && account.is_empty());
+            let change = if is_deleted {
+                // Account that didn't exist before the transaction has nothing to delete.
+                if before.info.is_empty() && account.is_loaded_as_not_existing() {
+                    continue;
+                }
+                AccountChange::new(AccountChangeKind::Deleted)
+            } else if account.is_created_locally() {
+                account_change(
+                    AccountChangeKind::Created,
+                    &AccountWitness::default(),
+                    account,
+                )
+            } else {
+                let change = account_change(AccountChangeKind::Modified, before, account);
+                if change.is_empty() {
+                    continue;
+                }
+                change
+            };
+            accounts.insert(*address, change);
+        }
+        ChangeSet { accounts }
+    }
+}
+
+/// Returns the change of the account compared to its previous values.
+fn account_change(
+    kind: AccountChangeKind,
+    before: &AccountWitness,
+    account: &Account,
+) -> AccountChange {
+    let info = &account.info;
+    let mut change = AccountChange::new(kind);
+    change.balance = (info.balance != before.info.balance).then_some(info.balance);
+    change.nonce = (info.nonce != before.info.nonce).then_some(info.nonce);
+    if info.code_hash != before.info.code_hash {
+        change.code = Some(info.code.clone().unwrap_or_default());
+    }
+    change.storage = account
+        .storage
+        .iter()
+        .filter(|(key, slot)| {
+            slot.present_value != before.storage.get(*key).copied().unwrap_or_default()
+        })
+        .map(|(key, slot)| (*key, slot.present_value))
+        .collect();
+    change
 }
 
 /// Loads storage slot with account.
@@ -853,7 +1840,27 @@ pub fn sload_with_account<DB: Database, ENTRY: JournalEntryTr>(
     address: Address,
     key: StorageKey,
 ) -> Result<StateLoad<StorageValue>, DB::Error> {
-    let is_newly_created = account.is_created();
+    let slot = load_slot(account, db, transaction_id, address, key)?;
+    if slot.is_cold {
+        // add it to journal as cold loaded.
+        journal.push(ENTRY::storage_warmed(address, key));
+    }
+    Ok(slot)
+}
+
+/// Loads storage slot with account without journaling it.
+#[inline]
+fn load_slot<DB: Database>(
+    account: &mut Account,
+    db: &mut DB,
+    transaction_id: usize,
+    address: Address,
+    key: StorageKey,
+) -> Result<StateLoad<StorageValue>, DB::Error> {
+    // Account that was selfdestructed in previous transaction had its storage cleared when it was
+    // cold loaded (see `load_account_optional`), so database values are stale.
+    let is_storage_cleared = account.is_created()
+        || (account.is_selfdestructed() && !account.is_selfdestructed_locally());
     let (value, is_cold) = match account.storage.entry(key) {
         Entry::Occupied(occ) => {
             let slot = occ.into_mut();
@@ -862,7 +1869,7 @@ pub fn sload_with_account<DB: Database, ENTRY: JournalEntryTr>(
         }
         Entry::Vacant(vac) => {
             // if storage was cleared, we don't need to ping db.
-            let value = if is_newly_created {
+            let value = if is_storage_cleared {
                 StorageValue::ZERO
             } else {
                 db.storage(address, key)?
@@ -874,11 +1881,6 @@ pub fn sload_with_account<DB: Database, ENTRY: JournalEntryTr>(
         }
     };
 
-    if is_cold {
-        // add it to journal as cold loaded.
-        journal.push(ENTRY::storage_warmed(address, key));
-    }
-
     Ok(StateLoad::new(value, is_cold))
 }
 
@@ -894,3 +1896,1090 @@ fn reset_preloaded_addresses(
     }
     warm_preloaded_addresses.clone_from(precompiles);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::JournalEntry;
+    use database::{CacheDB, EmptyDB};
+    use primitives::{address, hardfork::SpecId::*};
+    use state::AccountInfo;
+
+    const ALICE: Address = address!("0x1000000000000000000000000000000000000001");
+    const BOB: Address = address!("0x1000000000000000000000000000000000000002");
+    const CAROL: Address = address!("0x1000000000000000000000000000000000000003");
+
+    fn db_with_accounts() -> CacheDB<EmptyDB> {
+        let mut db = CacheDB::new(EmptyDB::default());
+        for address in [ALICE, BOB, CAROL] {
+            db.insert_account_info(
+                address,
+                AccountInfo {
+                    balance: U256::from(100),
+                    ..Default::default()
+                },
+            );
+        }
+        db
+    }
+
+    #[test]
+    fn take_accounts_after_commit_tx() {
+        let mut db = db_with_accounts();
+        let mut journal = JournalInner::<JournalEntry>::new();
+        journal.load_account(&mut db, ALICE).unwrap();
+        journal.load_account(&mut db, BOB).unwrap();
+        journal.commit_tx();
+
+        let taken = journal.take_accounts(&[ALICE]).unwrap();
+        assert_eq!(taken.len(), 1);
+        assert_eq!(taken[0].0, ALICE);
+        assert_eq!(taken[0].1.info.balance, U256::from(100));
+        assert!(!journal.state.contains_key(&ALICE));
+        assert!(journal.state.contains_key(&BOB));
+
+        // Not loaded accounts are skipped.
+        assert!(journal.take_accounts(&[ALICE]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn take_accounts_rejects_journaled_accounts() {
+        let mut db = db_with_accounts();
+        let mut journal = JournalInner::<JournalEntry>::new();
+        journal.load_account(&mut db, ALICE).unwrap();
+        journal.commit_tx();
+
+        // Transfer creates journal entries for both accounts.
+        journal
+            .transfer(&mut db, ALICE, BOB, U256::from(1))
+            .unwrap();
+        assert_eq!(journal.take_accounts(&[ALICE]), Err(ALICE));
+        assert_eq!(journal.take_accounts(&[BOB, ALICE]), Err(BOB));
+
+        // Nothing is removed when the request is rejected.
+        assert!(journal.state.contains_key(&ALICE));
+        assert!(journal.state.contains_key(&BOB));
+
+        // Once the transaction is committed accounts can be taken.
+        journal.commit_tx();
+        let taken = journal.take_accounts(&[ALICE, BOB]).unwrap();
+        assert_eq!(taken[0].1.info.balance, U256::from(99));
+        assert_eq!(taken[1].1.info.balance, U256::from(101));
+        assert!(journal.state.is_empty());
+    }
+
+    #[test]
+    fn spec_change_between_transactions() {
+        let mut db = db_with_accounts();
+        let mut journal = JournalInner::<JournalEntry>::new();
+
+        // First transaction runs under London, selfdestruct removes the existing account.
+        journal.set_spec_id(LONDON);
+        assert_eq!(journal.spec_id(), LONDON);
+        journal.load_account(&mut db, ALICE).unwrap();
+        journal.selfdestruct(&mut db, ALICE, CAROL).unwrap();
+        let alice = journal.account(ALICE);
+        assert!(alice.is_selfdestructed());
+        assert_eq!(alice.info.balance, U256::ZERO);
+        journal.commit_tx();
+
+        // Next transaction runs under Cancun, EIP-6780 only transfers the balance.
+        journal.set_spec_id(CANCUN);
+        assert_eq!(journal.spec_id(), CANCUN);
+        journal.load_account(&mut db, BOB).unwrap();
+        journal.selfdestruct(&mut db, BOB, CAROL).unwrap();
+        let bob = journal.account(BOB);
+        assert!(!bob.is_selfdestructed());
+        assert_eq!(bob.info.balance, U256::ZERO);
+        assert_eq!(journal.account(CAROL).info.balance, U256::from(300));
+    }
+
+    #[test]
+    fn access_witness_of_transfer() {
+        let mut db = db_with_accounts();
+        db.insert_account_storage(ALICE, StorageKey::from(1), StorageValue::from(7))
+            .unwrap();
+        let new_account = address!("0x2000000000000000000000000000000000000000");
+        let mut journal = JournalInner::<JournalEntry>::new();
+
+        journal.load_account(&mut db, ALICE).unwrap();
+        journal.sload(&mut db, ALICE, StorageKey::from(1)).unwrap();
+        journal
+            .sstore(&mut db, ALICE, StorageKey::from(1), StorageValue::from(8))
+            .unwrap();
+        journal
+            .transfer(&mut db, ALICE, new_account, U256::from(10))
+            .unwrap();
+
+        let witness = journal.access_witness();
+        assert_eq!(witness.accounts.len(), 2);
+
+        let alice = &witness.accounts[&ALICE];
+        assert_eq!(alice.info.balance, U256::from(100));
+        assert_eq!(alice.storage.len(), 1);
+        assert_eq!(alice.storage[&StorageKey::from(1)], StorageValue::from(7));
+
+        let new_account = &witness.accounts[&new_account];
+        assert_eq!(new_account.info, AccountInfo::default());
+        assert!(new_account.storage.is_empty());
+
+        // Present state is not changed.
+        assert_eq!(journal.account(ALICE).info.balance, U256::from(90));
+    }
+
+    #[test]
+    fn account_storage_for_root_is_sorted_and_pruned() {
+        let mut db = db_with_accounts();
+        let mut journal = JournalInner::<JournalEntry>::new();
+        journal.load_account(&mut db, ALICE).unwrap();
+        for key in 0..10u64 {
+            journal
+                .sstore(
+                    &mut db,
+                    ALICE,
+                    StorageKey::from(key),
+                    StorageValue::from(key),
+                )
+                .unwrap();
+        }
+
+        let slots = journal.account_storage_for_root(ALICE);
+        // Slot 0 has zero value and is excluded.
+        assert_eq!(slots.len(), 9);
+        assert!(slots
+            .iter()
+            .all(|(key, value)| key == value && !key.is_zero()));
+        assert!(
+            slots
+                .windows(2)
+                .all(|w| keccak256(w[0].0.to_be_bytes::<32>())
+                    < keccak256(w[1].0.to_be_bytes::<32>()))
+        );
+
+        // Slot cleared in this transaction is excluded too.
+        journal
+            .sstore(&mut db, ALICE, StorageKey::from(1), StorageValue::ZERO)
+            .unwrap();
+        let slots = journal.account_storage_for_root(ALICE);
+        assert_eq!(slots.len(), 8);
+        assert!(!slots.iter().any(|(key, _)| *key == StorageKey::from(1)));
+
+        assert!(journal.account_storage_for_root(BOB).is_empty());
+    }
+
+    #[test]
+    fn selfdestructed_account_is_empty_in_next_tx() {
+        let mut db = db_with_accounts();
+        db.insert_account_storage(ALICE, StorageKey::from(1), StorageValue::from(7))
+            .unwrap();
+        db.insert_account_storage(ALICE, StorageKey::from(2), StorageValue::from(8))
+            .unwrap();
+        let mut journal = JournalInner::<JournalEntry>::new();
+        journal.set_spec_id(LONDON);
+
+        // Selfdestruct takes effect at the end of transaction, storage is still readable.
+        journal.load_account(&mut db, ALICE).unwrap();
+        journal.sload(&mut db, ALICE, StorageKey::from(1)).unwrap();
+        journal.selfdestruct(&mut db, ALICE, CAROL).unwrap();
+        assert_eq!(
+            journal
+                .sload(&mut db, ALICE, StorageKey::from(2))
+                .unwrap()
+                .data,
+            StorageValue::from(8)
+        );
+        journal.commit_tx();
+
+        // Next transaction sees an empty account, both loaded and not loaded slots are cleared.
+        let alice = journal.load_account(&mut db, ALICE).unwrap();
+        assert!(alice.is_cold);
+        assert_eq!(alice.data.info, AccountInfo::default());
+        for key in [1, 2, 3] {
+            assert_eq!(
+                journal
+                    .sload(&mut db, ALICE, StorageKey::from(key))
+                    .unwrap()
+                    .data,
+                StorageValue::ZERO
+            );
+        }
+    }
+
+    #[test]
+    fn selfdestruct_into_account_created_in_same_tx() {
+        let created_a = address!("0x4000000000000000000000000000000000000001");
+        let created_b = address!("0x4000000000000000000000000000000000000002");
+        let mut db = db_with_accounts();
+        let mut journal = JournalInner::<JournalEntry>::new();
+        journal.set_spec_id(CANCUN);
+        journal.load_account(&mut db, ALICE).unwrap();
+
+        for (address, value) in [(created_a, 30), (created_b, 5)] {
+            journal.load_account(&mut db, address).unwrap();
+            journal
+                .create_account_checkpoint(ALICE, address, U256::from(value), CANCUN)
+                .unwrap();
+            journal.checkpoint_commit();
+        }
+
+        let result = journal.selfdestruct(&mut db, created_a, created_b).unwrap();
+        assert!(result.data.had_value);
+        assert!(result.data.target_exists);
+        assert_eq!(journal.state[&created_a].info.balance, U256::ZERO);
+        assert_eq!(journal.state[&created_b].info.balance, U256::from(35));
+
+        let state = journal.finalize();
+        // Created in the same tx, so `created_a` is destroyed under EIP-6780.
+        assert!(state[&created_a].is_selfdestructed());
+        // Beneficiary survives with the transferred value.
+        let created_b = &state[&created_b];
+        assert!(!created_b.is_selfdestructed());
+        assert_eq!(created_b.info.balance, U256::from(35));
+        assert_eq!(created_b.info.nonce, 1);
+        assert_eq!(state[&ALICE].info.balance, U256::from(65));
+    }
+
+    #[test]
+    fn active_eips_follow_spec() {
+        let mut journal = JournalInner::<JournalEntry>::new();
+        journal.set_spec_id(HOMESTEAD);
+        assert_eq!(journal.active_eips(), ActiveEips::default());
+
+        journal.set_spec_id(LONDON);
+        let eips = journal.active_eips();
+        assert!(eips.eip161 && !eips.eip1153 && !eips.eip6780 && !eips.eip7702);
+
+        journal.set_spec_id(CANCUN);
+        let eips = journal.active_eips();
+        assert!(eips.eip161 && eips.eip1153 && eips.eip6780 && !eips.eip7702);
+
+        journal.set_spec_id(PRAGUE);
+        assert_eq!(journal.active_eips(), ActiveEips::new(PRAGUE));
+        assert!(journal.active_eips().eip7702);
+    }
+
+    #[test]
+    fn staged_logs_are_committed_on_success() {
+        let staged =
+            |i: u8| Log::new_unchecked(Address::with_last_byte(i), vec![], Default::default());
+        let mut journal = JournalInner::<JournalEntry>::new();
+        journal.staged_logs_enabled = true;
+
+        // Inner call revert does not discard staged logs and they don't move the checkpoint.
+        journal.checkpoint();
+        journal.log(staged(0));
+        let inner = journal.checkpoint();
+        journal.log_staged(staged(1));
+        journal.log(staged(2));
+        journal.checkpoint_revert(inner);
+        assert_eq!(journal.logs, vec![staged(0)]);
+        journal.log_staged(staged(3));
+        assert_eq!(journal.logs, vec![staged(0)]);
+
+        // Emitted when the top level call succeeds.
+        journal.checkpoint_commit();
+        assert_eq!(journal.take_logs(), vec![staged(0), staged(1), staged(3)]);
+        journal.commit_tx();
+        assert!(journal.take_logs().is_empty());
+
+        // Top level revert discards staged logs.
+        let top = journal.checkpoint();
+        journal.log_staged(staged(4));
+        journal.checkpoint_revert(top);
+        journal.commit_tx();
+        assert!(journal.take_logs().is_empty());
+
+        // Discarded transaction discards staged logs.
+        journal.checkpoint();
+        journal.log_staged(staged(5));
+        journal.discard_tx();
+        assert!(journal.logs.is_empty() && journal.staged_logs.is_empty());
+
+        // Without a call or without the flag logs are emitted immediately.
+        journal.log_staged(staged(6));
+        journal.staged_logs_enabled = false;
+        journal.checkpoint();
+        journal.log_staged(staged(7));
+        assert_eq!(journal.logs, vec![staged(6), staged(7)]);
+    }
+
+    #[test]
+    fn on_entry_mirrors_journal() {
+        let entries = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let mut db = db_with_accounts();
+        let mut journal = JournalInner::<JournalEntry>::new();
+        journal.on_entry = EntryHook::new({
+            let entries = entries.clone();
+            move |entry: &JournalEntry| entries.lock().unwrap().push(entry.clone())
+        });
+
+        journal.load_account(&mut db, ALICE).unwrap();
+        journal.checkpoint();
+        journal
+            .transfer(&mut db, ALICE, BOB, U256::from(10))
+            .unwrap();
+        journal
+            .sstore(&mut db, ALICE, StorageKey::from(1), StorageValue::from(2))
+            .unwrap();
+        journal.tstore(ALICE, StorageKey::from(1), StorageValue::from(3));
+        journal.checkpoint_commit();
+
+        // Entries are observed in the same order as they are journaled.
+        assert!(journal.journal.len() > 5);
+        assert_eq!(*entries.lock().unwrap(), journal.journal);
+
+        // Hook is not cloned, and journals with different hooks are not equal.
+        let cloned = journal.clone();
+        assert!(!cloned.on_entry.is_some());
+        assert_ne!(cloned, journal);
+        journal.on_entry = EntryHook::default();
+        assert_eq!(cloned, journal);
+    }
+
+    #[test]
+    fn clear_transient_for_is_reverted() {
+        let mut journal = JournalInner::<JournalEntry>::new();
+        let key = StorageKey::from(1);
+        journal.tstore(ALICE, key, StorageValue::from(1));
+        journal.tstore(ALICE, StorageKey::from(2), StorageValue::from(2));
+        journal.tstore(BOB, key, StorageValue::from(3));
+
+        let checkpoint = journal.checkpoint();
+        journal.clear_transient_for(ALICE);
+        assert_eq!(journal.tload(ALICE, key), StorageValue::ZERO);
+        assert_eq!(
+            journal.tload(ALICE, StorageKey::from(2)),
+            StorageValue::ZERO
+        );
+        assert_eq!(journal.tload(BOB, key), StorageValue::from(3));
+
+        journal.checkpoint_revert(checkpoint);
+        assert_eq!(journal.tload(ALICE, key), StorageValue::from(1));
+        assert_eq!(
+            journal.tload(ALICE, StorageKey::from(2)),
+            StorageValue::from(2)
+        );
+        assert_eq!(journal.tload(BOB, key), StorageValue::from(3));
+    }
+
+    #[test]
+    fn create_collision_policies() {
+        let target = address!("0x1000000000000000000000000000000000000004");
+        let code = Bytecode::new_legacy([0x00].into());
+        let mut db = db_with_accounts();
+        db.insert_account_info(
+            target,
+            AccountInfo {
+                balance: U256::from(7),
+                nonce: 3,
+                code_hash: code.hash_slow(),
+                code: Some(code.clone()),
+            },
+        );
+        let key = StorageKey::from;
+        db.insert_account_storage(target, key(1), StorageValue::from(5))
+            .unwrap();
+        db.insert_account_storage(target, key(2), StorageValue::from(6))
+            .unwrap();
+        let new_journal = |db: &mut CacheDB<EmptyDB>| {
+            let mut journal = JournalInner::<JournalEntry>::new();
+            journal.set_spec_id(PRAGUE);
+            journal.load_account(db, ALICE).unwrap();
+            journal.load_account_optional(db, target, true, []).unwrap();
+            journal
+                .sstore(db, target, key(1), StorageValue::from(9))
+                .unwrap();
+            journal
+        };
+
+        let mut journal = new_journal(&mut db);
+        assert_eq!(
+            journal.create_account_checkpoint(ALICE, target, U256::from(10), PRAGUE),
+            Err(TransferError::CreateCollision)
+        );
+        assert_eq!(journal.account(target).info.nonce, 3);
+
+        for (policy, nonce) in [(CollisionPolicy::Overwrite, 1), (CollisionPolicy::Merge, 3)] {
+            let mut journal = new_journal(&mut db);
+            let checkpoint = journal
+                .create_account_checkpoint_with_policy(
+                    ALICE,
+                    target,
+                    U256::from(10),
+                    PRAGUE,
+                    policy,
+                )
+                .unwrap();
+            let account = journal.account(target);
+            assert!(account.is_created());
+            assert_eq!(account.info.code_hash, KECCAK_EMPTY);
+            assert_eq!(account.info.code, None);
+            assert_eq!(account.info.nonce, nonce);
+            assert_eq!(account.info.balance, U256::from(17));
+            // Storage is cleared, slots are not loaded from the database.
+            assert!(account.storage.is_empty());
+            for slot in [key(1), key(2)] {
+                let load = journal.sload(&mut db, target, slot).unwrap();
+                assert_eq!(load.data, StorageValue::ZERO);
+            }
+
+            journal.checkpoint_revert(checkpoint);
+            let account = journal.account(target);
+            assert!(!account.is_created());
+            assert_eq!(account.info.code_hash, code.hash_slow());
+            assert_eq!(account.info.code, Some(code.clone()));
+            assert_eq!(account.info.nonce, 3);
+            assert_eq!(account.info.balance, U256::from(7));
+            assert_eq!(account.storage.len(), 1);
+            assert_eq!(
+                account.storage[&key(1)].present_value,
+                StorageValue::from(9)
+            );
+            assert_eq!(journal.account(ALICE).info.balance, U256::from(100));
+        }
+    }
+
+    #[test]
+    fn reset_reuse_keeps_capacity() {
+        let mut db = db_with_accounts();
+        let mut journal = JournalInner::<JournalEntry>::new();
+        for address in [ALICE, BOB, CAROL] {
+            journal.load_account(&mut db, address).unwrap();
+        }
+        journal.tstore(ALICE, StorageKey::from(1), StorageValue::from(1));
+        journal.commit_tx();
+        let (state_capacity, journal_capacity) =
+            (journal.state.capacity(), journal.journal.capacity());
+
+        journal.reset_reuse();
+        assert!(journal.state.is_empty() && journal.transient_storage.is_empty());
+        assert_eq!(journal.transaction_id, 0);
+        assert_eq!(journal.state.capacity(), state_capacity);
+        assert_eq!(journal.journal.capacity(), journal_capacity);
+
+        // Accounts are loaded cold again.
+        assert!(journal.load_account(&mut db, ALICE).unwrap().is_cold);
+    }
+
+    #[test]
+    fn finalize_changes_only_excludes_loaded_accounts() {
+        let mut db = db_with_accounts();
+        let mut journal = JournalInner::<JournalEntry>::new();
+        journal.load_account(&mut db, BOB).unwrap();
+        journal
+            .transfer(&mut db, ALICE, CAROL, U256::from(10))
+            .unwrap();
+        journal.commit_tx();
+
+        let state = journal.finalize_changes_only();
+        assert!(state.contains_key(&ALICE) && state.contains_key(&CAROL));
+        assert!(!state.contains_key(&BOB));
+        assert!(journal.state.is_empty());
+    }
+
+    #[test]
+    fn cold_access_counts_per_tx() {
+        let mut db = db_with_accounts();
+        let mut journal = JournalInner::<JournalEntry>::new();
+        journal.warm_preloaded_addresses.insert(CAROL);
+
+        journal.load_account(&mut db, ALICE).unwrap();
+        journal.load_account(&mut db, ALICE).unwrap();
+        journal.load_account(&mut db, CAROL).unwrap();
+        let checkpoint = journal.checkpoint();
+        journal.load_account(&mut db, BOB).unwrap();
+        journal.sload(&mut db, BOB, StorageKey::from(1)).unwrap();
+        journal.checkpoint_revert(checkpoint);
+        journal.sload(&mut db, ALICE, StorageKey::from(1)).unwrap();
+        journal.sload(&mut db, ALICE, StorageKey::from(1)).unwrap();
+
+        // Warm preloaded and repeated loads are not counted, reverted loads are.
+        assert_eq!(journal.cold_access_counts(), (2, 2));
+
+        journal.commit_tx();
+        assert_eq!(journal.cold_access_counts(), (0, 0));
+    }
+
+    #[test]
+    fn checkpoint_revert_returns_last_entry() {
+        let mut db = db_with_accounts();
+        let mut journal = JournalInner::<JournalEntry>::new();
+        journal.load_account(&mut db, ALICE).unwrap();
+        assert_eq!(
+            journal.last_entry(),
+            Some(&JournalEntry::AccountWarmed { address: ALICE })
+        );
+
+        let checkpoint = journal.checkpoint();
+        journal
+            .sstore(&mut db, ALICE, StorageKey::from(1), StorageValue::from(2))
+            .unwrap();
+        let sstore_entry = JournalEntry::StorageChanged {
+            key: StorageKey::from(1),
+            had_value: StorageValue::ZERO,
+            address: ALICE,
+        };
+        assert_eq!(journal.last_entry(), Some(&sstore_entry));
+        assert_eq!(
+            journal.checkpoint_revert_with_last_entry(checkpoint),
+            Some(sstore_entry)
+        );
+        assert_eq!(
+            journal.last_entry(),
+            Some(&JournalEntry::AccountWarmed { address: ALICE })
+        );
+
+        // Nothing to revert.
+        let checkpoint = journal.checkpoint();
+        assert_eq!(journal.checkpoint_revert_with_last_entry(checkpoint), None);
+    }
+
+    #[test]
+    fn set_code_cached_hashes_once() {
+        let mut db = db_with_accounts();
+        let mut journal = JournalInner::<JournalEntry>::new();
+        let mut cache = CodeHashCache::new();
+        let code = Bytecode::new_legacy([0x60, 0x01, 0x00].into());
+        for address in [ALICE, BOB] {
+            journal.load_account(&mut db, address).unwrap();
+            journal.set_code_cached(address, code.clone(), &mut cache);
+            assert_eq!(journal.account(address).info.code_hash, code.hash_slow());
+        }
+        assert_eq!(cache.len(), 1);
+
+        // Same bytes in a different allocation are hashed again.
+        let other = Bytecode::new_legacy([0x60, 0x01, 0x00].into());
+        journal.load_account(&mut db, CAROL).unwrap();
+        journal.set_code_cached(CAROL, other, &mut cache);
+        assert_eq!(journal.account(CAROL).info.code_hash, code.hash_slow());
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn transient_snapshot_round_trip() {
+        let mut journal = JournalInner::<JournalEntry>::new();
+        let key = StorageKey::from(1);
+        journal.tstore(ALICE, key, StorageValue::from(1));
+        journal.tstore(BOB, key, StorageValue::from(2));
+        let snapshot = journal.transient_snapshot_full();
+
+        journal.tstore(ALICE, key, StorageValue::from(3));
+        journal.tstore(BOB, key, StorageValue::ZERO);
+        journal.tstore(CAROL, key, StorageValue::from(4));
+
+        journal.restore_transient(snapshot.clone());
+        assert_eq!(journal.transient_storage, snapshot);
+        assert_eq!(journal.tload(ALICE, key), StorageValue::from(1));
+        assert_eq!(journal.tload(BOB, key), StorageValue::from(2));
+        // Slots missing from the snapshot are dropped.
+        assert_eq!(journal.tload(CAROL, key), StorageValue::ZERO);
+
+        journal.restore_transient(TransientStorage::default());
+        assert!(journal.transient_storage.is_empty());
+    }
+
+    #[test]
+    fn balance_deltas_of_transfer() {
+        let mut db = db_with_accounts();
+        let mut journal = JournalInner::<JournalEntry>::new();
+        journal.load_account(&mut db, CAROL).unwrap();
+        journal
+            .transfer(&mut db, ALICE, BOB, U256::from(10))
+            .unwrap();
+        let mut deltas: Vec<_> = journal.balance_deltas().collect();
+        deltas.sort();
+        assert_eq!(
+            deltas,
+            vec![
+                (ALICE, I256::try_from(-10).unwrap()),
+                (BOB, I256::try_from(10).unwrap())
+            ]
+        );
+
+        journal.balance_incr(&mut db, BOB, U256::from(3)).unwrap();
+        journal.selfdestruct(&mut db, CAROL, ALICE).unwrap();
+
+        let mut deltas: Vec<_> = journal.balance_deltas().collect();
+        deltas.sort();
+        let mut expected = vec![
+            (ALICE, I256::try_from(90).unwrap()),
+            (BOB, I256::try_from(13).unwrap()),
+            (CAROL, I256::try_from(-100).unwrap()),
+        ];
+        expected.sort();
+        assert_eq!(deltas, expected);
+
+        // Accounts without balance change in the current transaction are not included.
+        journal.commit_tx();
+        journal.load_account(&mut db, ALICE).unwrap();
+        assert_eq!(journal.balance_deltas().count(), 0);
+    }
+
+    #[test]
+    fn commit_tx_fast_keeps_preloaded_addresses() {
+        let mut journal = JournalInner::<JournalEntry>::new();
+        journal.precompiles.insert(ALICE);
+        journal.warm_preloaded_addresses.insert(ALICE);
+
+        journal.warm_preloaded_addresses.insert(BOB);
+        journal.commit_tx_fast();
+        assert_eq!(journal.transaction_id, 1);
+        // Precondition is violated, the warm address leaks into the next transaction.
+        assert!(journal.warm_preloaded_addresses.contains(&BOB));
+
+        journal.commit_tx();
+        assert_eq!(journal.transaction_id, 2);
+        assert_eq!(journal.warm_preloaded_addresses, journal.precompiles);
+    }
+
+    #[test]
+    fn on_balance_change_of_transfer() {
+        use std::sync::{Arc, Mutex};
+
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let mut db = db_with_accounts();
+        let mut journal = JournalInner::<JournalEntry>::new();
+        let recorded = changes.clone();
+        journal.on_balance_change = BalanceHook::new(move |address, old, new| {
+            recorded.lock().unwrap().push((address, old, new))
+        });
+
+        journal
+            .transfer(&mut db, ALICE, BOB, U256::from(10))
+            .unwrap();
+        assert_eq!(
+            *changes.lock().unwrap(),
+            vec![
+                (ALICE, U256::from(100), U256::from(90)),
+                (BOB, U256::from(100), U256::from(110)),
+            ]
+        );
+
+        // Zero value transfer and failed transfer don't change balances.
+        journal.transfer(&mut db, ALICE, BOB, U256::ZERO).unwrap();
+        journal
+            .transfer(&mut db, ALICE, BOB, U256::from(1000))
+            .unwrap();
+        assert_eq!(changes.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn changeset_of_transfer() {
+        let mut db = db_with_accounts();
+        let mut journal = JournalInner::<JournalEntry>::new();
+        journal.load_account(&mut db, CAROL).unwrap();
+        journal
+            .transfer(&mut db, ALICE, BOB, U256::from(10))
+            .unwrap();
+
+        let mut alice = AccountChange::new(AccountChangeKind::Modified);
+        alice.balance = Some(U256::from(90));
+        let mut bob = AccountChange::new(AccountChangeKind::Modified);
+        bob.balance = Some(U256::from(110));
+        assert_eq!(
+            journal.to_changeset(),
+            ChangeSet {
+                accounts: [(ALICE, alice), (BOB, bob)].into_iter().collect(),
+            }
+        );
+    }
+
+    #[test]
+    fn logs_for_filters_by_address() {
+        let mut journal = JournalInner::<JournalEntry>::new();
+        let log = |address: Address, data: u8| {
+            Log::new_unchecked(address, vec![], primitives::Bytes::from(vec![data]))
+        };
+        journal.log(log(ALICE, 1));
+        journal.log(log(BOB, 2));
+        journal.log(log(ALICE, 3));
+
+        let alice_logs: Vec<_> = journal.logs_for(ALICE).collect();
+        assert_eq!(alice_logs, [&log(ALICE, 1), &log(ALICE, 3)]);
+        assert_eq!(journal.logs_for(CAROL).count(), 0);
+    }
+
+    #[test]
+    fn apply_changeset_round_trip() {
+        let mut db = db_with_accounts();
+        let mut journal = JournalInner::<JournalEntry>::new();
+        journal
+            .transfer(&mut db, ALICE, BOB, U256::from(10))
+            .unwrap();
+        journal.load_account(&mut db, CAROL).unwrap();
+        journal
+            .sstore(&mut db, CAROL, StorageKey::from(1), StorageValue::from(5))
+            .unwrap();
+        let changeset = journal.to_changeset();
+
+        // Follower has the same state before the transaction, unchanged fields are kept.
+        let mut follower = JournalInner::<JournalEntry>::new();
+        for address in [ALICE, BOB, CAROL] {
+            follower.load_account(&mut db, address).unwrap();
+        }
+        let journal_len = follower.journal.len();
+        follower.apply_changeset(changeset);
+
+        for address in [ALICE, BOB, CAROL] {
+            assert_eq!(follower.state[&address].info, journal.state[&address].info);
+        }
+        let slot = &follower.state[&CAROL].storage[&StorageKey::from(1)];
+        assert_eq!(slot.present_value, StorageValue::from(5));
+        // Applied values are the baseline, nothing to revert or commit.
+        assert!(!slot.is_changed());
+        assert_eq!(follower.journal.len(), journal_len);
+        assert!(follower.to_changeset().accounts.is_empty());
+        // Applied slots are warm.
+        assert!(
+            !follower
+                .sload(&mut db, CAROL, StorageKey::from(1))
+                .unwrap()
+                .is_cold
+        );
+    }
+
+    #[test]
+    fn read_only_slots_of_reads_and_writes() {
+        let mut db = db_with_accounts();
+        let mut journal = JournalInner::<JournalEntry>::new();
+        journal.load_account(&mut db, ALICE).unwrap();
+        journal.load_account(&mut db, BOB).unwrap();
+        let key = StorageKey::from;
+
+        journal.sload(&mut db, ALICE, key(1)).unwrap();
+        journal
+            .sstore(&mut db, ALICE, key(2), StorageValue::from(7))
+            .unwrap();
+        journal.sload(&mut db, BOB, key(3)).unwrap();
+        // Written back to the original value.
+        journal
+            .sstore(&mut db, BOB, key(4), StorageValue::from(7))
+            .unwrap();
+        journal
+            .sstore(&mut db, BOB, key(4), StorageValue::ZERO)
+            .unwrap();
+
+        let mut slots: Vec<_> = journal.read_only_slots().collect();
+        slots.sort();
+        assert_eq!(slots, [(ALICE, key(1)), (BOB, key(3)), (BOB, key(4))]);
+
+        // Slots of the previous transaction are not included.
+        journal.commit_tx();
+        assert_eq!(journal.read_only_slots().count(), 0);
+    }
+
+    #[test]
+    fn finalize_keep_warm_of_loaded_accounts() {
+        let mut db = db_with_accounts();
+        let mut journal = JournalInner::<JournalEntry>::new();
+        journal.precompiles = HashSet::from_iter([CAROL]);
+        journal.warm_preloaded_addresses = journal.precompiles.clone();
+        journal
+            .transfer(&mut db, ALICE, BOB, U256::from(10))
+            .unwrap();
+        journal.load_account(&mut db, CAROL).unwrap();
+        // Access list warmth without loading the account.
+        let dave = address!("0x1000000000000000000000000000000000000004");
+        journal.warm_preloaded_addresses.insert(dave);
+
+        let (state, warm) = journal.finalize_keep_warm();
+        assert_eq!(warm, HashSet::from_iter([ALICE, BOB]));
+        assert!(state.contains_key(&CAROL));
+        assert!(!state.contains_key(&dave));
+    }
+
+    #[test]
+    fn apply_and_revert_entry_of_builtin_entries() {
+        let mut db = db_with_accounts();
+        let mut journal = JournalInner::<JournalEntry>::new();
+        journal.load_account(&mut db, ALICE).unwrap();
+        let key = StorageKey::from(1);
+        journal.sload(&mut db, ALICE, key).unwrap();
+
+        journal.apply_and_revert_entry(
+            JournalEntry::balance_changed(ALICE, U256::from(100)),
+            |state, _| {
+                state.get_mut(&ALICE).unwrap().info.balance = U256::from(50);
+            },
+        );
+        journal.apply_and_revert_entry(
+            JournalEntry::storage_changed(ALICE, key, StorageValue::ZERO),
+            |state, _| {
+                let slot = state
+                    .get_mut(&ALICE)
+                    .unwrap()
+                    .storage
+                    .get_mut(&key)
+                    .unwrap();
+                slot.present_value = StorageValue::from(2);
+            },
+        );
+        journal.apply_and_revert_entry(JournalEntry::nonce_changed(ALICE), |state, _| {
+            state.get_mut(&ALICE).unwrap().info.nonce += 1;
+        });
+        journal.apply_and_revert_entry(JournalEntry::code_changed(ALICE), |state, _| {
+            let code = Bytecode::new_legacy([0x00].into());
+            state.get_mut(&ALICE).unwrap().info.set_code(code);
+        });
+        journal.apply_and_revert_entry(
+            JournalEntry::transient_storage_changed(ALICE, key, StorageValue::ZERO),
+            |_, transient_storage| {
+                transient_storage.insert((ALICE, key), StorageValue::from(3));
+            },
+        );
+    }
+
+    #[test]
+    fn slots_by_access_order_of_accesses() {
+        let mut db = db_with_accounts();
+        let mut journal = JournalInner::<JournalEntry>::new();
+        let key = StorageKey::from;
+        journal.load_account(&mut db, ALICE).unwrap();
+        journal.load_account(&mut db, BOB).unwrap();
+        journal.sload(&mut db, ALICE, key(5)).unwrap();
+        assert!(journal.slots_by_access_order().is_empty());
+        journal.slot_access_order_enabled = true;
+
+        journal.sload(&mut db, ALICE, key(3)).unwrap();
+        journal
+            .sstore(&mut db, BOB, key(1), StorageValue::from(1))
+            .unwrap();
+        journal.sload(&mut db, ALICE, key(3)).unwrap();
+        let checkpoint = journal.checkpoint();
+        journal.sload(&mut db, ALICE, key(2)).unwrap();
+        journal.checkpoint_revert(checkpoint);
+        journal.sload(&mut db, ALICE, key(2)).unwrap();
+        assert_eq!(
+            journal.slots_by_access_order(),
+            [(ALICE, key(3)), (BOB, key(1)), (ALICE, key(2))]
+        );
+        journal.commit_tx();
+        assert!(journal.slots_by_access_order().is_empty());
+
+        // Next transaction accesses the slots of the first one again.
+        journal.load_account(&mut db, ALICE).unwrap();
+        journal.sload(&mut db, ALICE, key(1)).unwrap();
+        journal.sload(&mut db, ALICE, key(3)).unwrap();
+        journal
+            .load_account_optional(&mut db, BOB, false, [key(1), key(4)])
+            .unwrap();
+
+        assert_eq!(
+            journal.slots_by_access_order(),
+            [
+                (ALICE, key(1)),
+                (ALICE, key(3)),
+                (BOB, key(1)),
+                (BOB, key(4))
+            ]
+        );
+
+        journal.finalize();
+        assert!(journal.slots_by_access_order().is_empty());
+    }
+
+    #[test]
+    fn on_db_account_load_of_repeated_access() {
+        use std::sync::{Arc, Mutex};
+
+        let loads = Arc::new(Mutex::new(Vec::new()));
+        let mut db = db_with_accounts();
+        let mut journal = JournalInner::<JournalEntry>::new();
+        let recorded = loads.clone();
+        journal.on_db_account_load =
+            AccountLoadHook::new(move |address| recorded.lock().unwrap().push(address));
+
+        journal
+            .transfer(&mut db, ALICE, BOB, U256::from(10))
+            .unwrap();
+        journal.load_account(&mut db, ALICE).unwrap();
+        // Cold in the next transaction but still cached.
+        journal.commit_tx();
+        journal.load_account(&mut db, BOB).unwrap();
+        let dave = address!("0x1000000000000000000000000000000000000004");
+        journal.load_account(&mut db, dave).unwrap();
+        assert_eq!(*loads.lock().unwrap(), vec![ALICE, BOB, dave]);
+    }
+
+    #[test]
+    fn active_delegations_of_delegated_account() {
+        let mut db = db_with_accounts();
+        let mut journal = JournalInner::<JournalEntry>::new();
+        journal.load_account(&mut db, ALICE).unwrap();
+        journal.load_account(&mut db, BOB).unwrap();
+        journal.set_code(ALICE, Bytecode::new_eip7702(CAROL));
+
+        // Reverted delegation is not active.
+        let checkpoint = journal.checkpoint();
+        journal.set_code(BOB, Bytecode::new_eip7702(CAROL));
+        journal.checkpoint_revert(checkpoint);
+
+        assert_eq!(
+            journal.active_delegations().collect::<Vec<_>>(),
+            [(ALICE, CAROL)]
+        );
+    }
+
+    #[test]
+    fn account_cache_serves_account() {
+        use std::sync::{Arc, Mutex};
+
+        let loads = Arc::new(Mutex::new(Vec::new()));
+        let mut db = db_with_accounts();
+        let mut journal = JournalInner::<JournalEntry>::new();
+        let recorded = loads.clone();
+        journal.on_db_account_load =
+            AccountLoadHook::new(move |address| recorded.lock().unwrap().push(address));
+        journal.account_cache = AccountCache::new(|address| {
+            (address == ALICE).then(|| {
+                AccountInfo {
+                    balance: U256::from(500),
+                    ..Default::default()
+                }
+                .into()
+            })
+        });
+
+        let load = journal.load_account(&mut db, ALICE).unwrap();
+        assert!(load.is_cold);
+        assert_eq!(load.data.info.balance, U256::from(500));
+        journal.load_account(&mut db, BOB).unwrap();
+        // Only the account missing from the cache is fetched from the database.
+        assert_eq!(*loads.lock().unwrap(), vec![BOB]);
+
+        // Cached account is journaled like a fetched one.
+        journal
+            .transfer(&mut db, ALICE, BOB, U256::from(10))
+            .unwrap();
+        let state = journal.finalize();
+        assert_eq!(state[&ALICE].info.balance, U256::from(490));
+        assert_eq!(state[&BOB].info.balance, U256::from(110));
+    }
+
+    #[test]
+    #[cfg(feature = "fuzz-diagnostics")]
+    fn overflow_events_of_balance_overflow() {
+        let mut db = db_with_accounts();
+        let mut journal = JournalInner::<JournalEntry>::new();
+        journal
+            .balance_incr(&mut db, ALICE, U256::from(10))
+            .unwrap();
+        assert_eq!(journal.overflow_events(), 0);
+
+        // Saturating increment and transfer to the saturated account.
+        journal.balance_incr(&mut db, ALICE, U256::MAX).unwrap();
+        assert_eq!(journal.overflow_events(), 1);
+        assert_eq!(
+            journal
+                .transfer(&mut db, BOB, ALICE, U256::from(1))
+                .unwrap(),
+            Some(TransferError::OverflowPayment)
+        );
+        assert_eq!(journal.overflow_events(), 2);
+    }
+
+    #[test]
+    fn apply_overrides_of_balance_and_storage() {
+        let mut db = db_with_accounts();
+        let key = StorageKey::from;
+        db.insert_account_storage(BOB, key(1), StorageValue::from(7))
+            .unwrap();
+        db.insert_account_storage(CAROL, key(1), StorageValue::from(7))
+            .unwrap();
+        let mut journal = JournalInner::<JournalEntry>::new();
+        let code = Bytecode::new_legacy(primitives::Bytes::from_static(&[0x00]));
+        let storage = |slots: &[(u64, u64)]| {
+            Some(
+                slots
+                    .iter()
+                    .map(|(k, v)| (key(*k), StorageValue::from(*v)))
+                    .collect(),
+            )
+        };
+        journal
+            .apply_overrides(
+                &mut db,
+                [
+                    (
+                        ALICE,
+                        AccountOverride {
+                            balance: Some(U256::from(1000)),
+                            code: Some(code.clone()),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        BOB,
+                        AccountOverride {
+                            state_diff: storage(&[(2, 5)]),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        CAROL,
+                        AccountOverride {
+                            state: storage(&[(2, 5)]),
+                            ..Default::default()
+                        },
+                    ),
+                ],
+            )
+            .unwrap();
+        assert!(journal.journal.is_empty());
+        assert_eq!(journal.state[&ALICE].info.code_hash, code.hash_slow());
+
+        // Overrides are the baseline of the transaction and are loaded cold.
+        assert!(journal.load_account(&mut db, ALICE).unwrap().is_cold);
+        assert_eq!(
+            journal
+                .transfer(&mut db, ALICE, BOB, U256::from(500))
+                .unwrap(),
+            None
+        );
+        let slot = journal.sload(&mut db, BOB, key(2)).unwrap();
+        assert_eq!((slot.data, slot.is_cold), (StorageValue::from(5), true));
+        assert_eq!(
+            journal.sload(&mut db, BOB, key(1)).unwrap().data,
+            StorageValue::from(7)
+        );
+        // Full storage override clears the database slots.
+        journal.load_account(&mut db, CAROL).unwrap();
+        assert_eq!(
+            journal.sload(&mut db, CAROL, key(1)).unwrap().data,
+            StorageValue::ZERO
+        );
+        assert_eq!(
+            journal.sload(&mut db, CAROL, key(2)).unwrap().data,
+            StorageValue::from(5)
+        );
+
+        let state = journal.finalize();
+        assert_eq!(state[&ALICE].info.balance, U256::from(500));
+        assert_eq!(state[&BOB].info.balance, U256::from(600));
+        assert!(!state[&BOB].storage[&key(2)].is_changed());
+    }
+
+    #[test]
+    fn account_rlp_entries_of_touched_accounts() {
+        let mut db = db_with_accounts();
+        let mut journal = JournalInner::<JournalEntry>::new();
+        journal
+            .transfer(&mut db, ALICE, BOB, U256::from(10))
+            .unwrap();
+        // Loaded but not touched.
+        journal.load_account(&mut db, CAROL).unwrap();
+        // Touched but empty.
+        let dave = address!("0x1000000000000000000000000000000000000004");
+        journal.transfer(&mut db, ALICE, dave, U256::ZERO).unwrap();
+
+        let entry = |balance| AccountRlp {
+            nonce: 0,
+            balance: U256::from(balance),
+            storage_root: None,
+            code_hash: KECCAK_EMPTY,
+        };
+        let mut expected = vec![(ALICE, entry(90)), (BOB, entry(110))];
+        expected.sort_by_key(|(address, _)| keccak256(address));
+        assert_eq!(journal.account_rlp_entries(), expected);
+    }
+}
@@ -0,0 +1,10 @@
+//! Contains [`JournalSnapshot`] that is taken with [`JournalInner::snapshot`].
+use crate::JournalInner;
+
+/// Copy of the journal state that can be restored with [`JournalInner::restore`].
+///
+/// It contains the loaded state, logs, transient storage and the journal itself. Hooks and the
+/// account cache are not part of the snapshot and are kept by the restored journal. Database is
+/// not part of the snapshot either, changes committed to it are not undone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JournalSnapshot<ENTRY>(pub(crate) JournalInner<ENTRY>);
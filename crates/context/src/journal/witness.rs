@@ -0,0 +1,27 @@
+//! Contains [`AccessWitness`] that is created by [`JournalInner::access_witness`].
+//!
+//! [`JournalInner::access_witness`]: crate::JournalInner::access_witness
+use primitives::{Address, HashMap, StorageKey, StorageValue};
+use state::AccountInfo;
+
+/// Pre-transaction values of all accounts and storage slots loaded in the journal.
+///
+/// This is the read set needed to build a witness for stateless execution.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccessWitness {
+    /// Accounts with their values as of the start of the transaction.
+    pub accounts: HashMap<Address, AccountWitness>,
+}
+
+/// Pre-transaction values of a single account from [`AccessWitness`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccountWitness {
+    /// Account info as of the start of the transaction.
+    ///
+    /// Accounts that did not exist have default (empty) info.
+    pub info: AccountInfo,
+    /// Loaded storage slots with their values as of the start of the transaction.
+    pub storage: HashMap<StorageKey, StorageValue>,
+}
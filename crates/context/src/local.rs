@@ -1,6 +1,7 @@
 //! Local context that is filled by execution.
 use context_interface::LocalContextTr;
 use core::cell::RefCell;
+use primitives::Address;
 use std::{rc::Rc, vec::Vec};
 
 /// Local context that is filled by execution.
@@ -8,12 +9,18 @@ use std::{rc::Rc, vec::Vec};
 pub struct LocalContext {
     /// Interpreter shared memory buffer. A reused memory buffer for calls.
     pub shared_memory_buffer: Rc<RefCell<Vec<u8>>>,
+    /// Call targets of the current call stack, see [`LocalContextTr::call_stack`].
+    pub call_stack: Vec<Address>,
+    /// Gas used by the transactions of the block, see [`LocalContextTr::cumulative_gas_used`].
+    pub cumulative_gas_used: u64,
 }
 
 impl Default for LocalContext {
     fn default() -> Self {
         Self {
             shared_memory_buffer: Rc::new(RefCell::new(Vec::with_capacity(1024 * 4))),
+            call_stack: Vec::new(),
+            cumulative_gas_used: 0,
         }
     }
 }
@@ -22,6 +29,27 @@ impl LocalContextTr for LocalContext {
     fn clear(&mut self) {
         // Sets len to 0 but it will not shrink to drop the capacity.
         unsafe { self.shared_memory_buffer.borrow_mut().set_len(0) };
+        self.call_stack.clear();
+    }
+
+    fn call_stack(&self) -> &[Address] {
+        &self.call_stack
+    }
+
+    fn push_call(&mut self, address: Address) {
+        self.call_stack.push(address);
+    }
+
+    fn pop_call(&mut self) {
+        self.call_stack.pop();
+    }
+
+    fn cumulative_gas_used(&self) -> u64 {
+        self.cumulative_gas_used
+    }
+
+    fn set_cumulative_gas_used(&mut self, gas_used: u64) {
+        self.cumulative_gas_used = gas_used;
     }
 
     fn shared_memory_buffer(&self) -> &Rc<RefCell<Vec<u8>>> {
@@ -1,6 +1,6 @@
 use crate::Context;
 use auto_impl::auto_impl;
-use context_interface::{Block, Cfg, Database, JournalTr, Transaction};
+use context_interface::{Block, Cfg, Database, HostHooks, JournalTr, Transaction};
 
 /// Setters for the context.
 #[auto_impl(&mut, Box)]
@@ -25,6 +25,7 @@ where
     CFG: Cfg,
     DB: Database,
     JOURNAL: JournalTr<Database = DB>,
+    CHAIN: HostHooks,
 {
     type Tx = TX;
     type Block = BLOCK;
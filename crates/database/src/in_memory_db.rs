@@ -191,11 +191,22 @@ impl<ExtDB: DatabaseRef> CacheDB<ExtDB> {
 }
 
 impl<ExtDB> DatabaseCommit for CacheDB<ExtDB> {
+    /// Commits the touched accounts into the cache.
+    ///
+    /// EIP-161 (Spurious Dragon) is applied: an account that did not exist and was only touched
+    /// while staying empty, for example by a zero value transfer, is not created.
     fn commit(&mut self, changes: HashMap<Address, Account>) {
         for (address, mut account) in changes {
             if !account.is_touched() {
                 continue;
             }
+            if account.is_loaded_as_not_existing()
+                && account.is_empty()
+                && !account.is_created()
+                && !account.is_selfdestructed()
+            {
+                continue;
+            }
             if account.is_selfdestructed() {
                 let db_account = self.cache.accounts.entry(address).or_default();
                 db_account.storage.clear();
@@ -504,9 +515,9 @@ impl Database for BenchmarkDB {
 #[cfg(test)]
 mod tests {
     use super::{CacheDB, EmptyDB};
-    use database_interface::Database;
+    use database_interface::{Database, DatabaseCommit};
     use primitives::{Address, HashMap, StorageKey, StorageValue};
-    use state::AccountInfo;
+    use state::{Account, AccountInfo};
 
     #[test]
     fn test_insert_account_storage() {
@@ -560,6 +571,19 @@ mod tests {
         assert_eq!(new_state.storage(account, key1), Ok(value1));
     }
 
+    #[test]
+    fn test_commit_skips_touched_non_existing_account() {
+        let account = Address::with_last_byte(42);
+        let mut db = CacheDB::new(EmptyDB::default());
+        assert_eq!(db.basic(account).unwrap(), None);
+
+        let mut touched = Account::new_not_existing(0);
+        touched.mark_touch();
+        db.commit(HashMap::from_iter([(account, touched)]));
+
+        assert_eq!(db.basic(account).unwrap(), None);
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn test_serialize_deserialize_cachedb() {
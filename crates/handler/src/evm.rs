@@ -5,7 +5,9 @@ use crate::{
 use auto_impl::auto_impl;
 use context::{ContextTr, Database, Evm, FrameStack};
 use context_interface::context::ContextError;
-use interpreter::{interpreter::EthInterpreter, interpreter_action::FrameInit, InterpreterResult};
+use interpreter::{
+    interpreter::EthInterpreter, interpreter_action::FrameInit, Host, InterpreterResult,
+};
 
 /// Type alias for database error within a context
 pub type ContextDbError<CTX> = ContextError<ContextTrDbError<CTX>>;
@@ -142,9 +144,23 @@ where
         let context = &mut self.ctx;
         let instructions = &mut self.instruction;
 
-        let action = frame
-            .interpreter
-            .run_plain(instructions.instruction_table(), context);
+        let budget = context.opcode_budget();
+        let action = if budget.is_none() && !context.track_stack_high_water() {
+            frame
+                .interpreter
+                .run_plain(instructions.instruction_table(), context)
+        } else {
+            let mut remaining = budget.unwrap_or(u64::MAX);
+            let action = frame.interpreter.run_plain_metered(
+                instructions.instruction_table(),
+                context,
+                &mut remaining,
+            );
+            if let Some(budget) = budget {
+                context.record_opcodes(budget - remaining);
+            }
+            action
+        };
 
         frame.process_next_action(context, action).inspect(|i| {
             if i.is_result() {
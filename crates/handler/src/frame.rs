@@ -5,11 +5,11 @@ use crate::{CallFrame, CreateFrame, FrameData, FrameResult};
 use context::result::FromStringError;
 use context_interface::context::ContextError;
 use context_interface::local::{FrameToken, OutFrame};
-use context_interface::ContextTr;
 use context_interface::{
-    journaled_state::{JournalCheckpoint, JournalTr},
-    Cfg, Database,
+    journaled_state::{CollisionPolicy, JournalCheckpoint, JournalTr, TransferError},
+    Cfg, Database, HostHooks,
 };
+use context_interface::{ContextTr, LocalContextTr};
 use core::cmp::min;
 use derive_where::derive_where;
 use interpreter::interpreter_action::FrameInit;
@@ -18,7 +18,7 @@ use interpreter::{
     interpreter::{EthInterpreter, ExtBytecode},
     interpreter_types::ReturnData,
     CallInput, CallInputs, CallOutcome, CallValue, CreateInputs, CreateOutcome, CreateScheme,
-    FrameInput, Gas, InputsImpl, InstructionResult, Interpreter, InterpreterAction,
+    FrameInput, Gas, Host, InputsImpl, InstructionResult, Interpreter, InterpreterAction,
     InterpreterResult, InterpreterTypes, SharedMemory,
 };
 use primitives::{
@@ -128,6 +128,7 @@ impl EthFrame<EthInterpreter> {
         *depth_ref = depth;
         *is_finished_ref = false;
         interpreter.clear(memory, bytecode, inputs, is_static, spec_id, gas_limit);
+        interpreter.runtime_flag.depth = depth;
         *checkpoint_ref = checkpoint;
     }
 
@@ -162,6 +163,12 @@ impl EthFrame<EthInterpreter> {
             return return_result(InstructionResult::CallTooDeep);
         }
 
+        // Nested calls are pushed by the call instructions, the local context is cleared
+        // at the end of the transaction.
+        if depth == 0 && ctx.chain().track_call_stack() {
+            ctx.local_mut().push_call(inputs.target_address);
+        }
+
         // Make account warm and loaded.
         let _ = ctx
             .journal_mut()
@@ -193,6 +200,10 @@ impl EthFrame<EthInterpreter> {
         let is_static = inputs.is_static;
         let gas_limit = inputs.gas_limit;
 
+        if precompiles.contains(&inputs.bytecode_address) {
+            ctx.on_precompile_input(inputs.bytecode_address, &inputs.input);
+        }
+
         if let Some(result) = precompiles
             .run(
                 ctx,
@@ -203,6 +214,13 @@ impl EthFrame<EthInterpreter> {
             )
             .map_err(ERROR::from_string)?
         {
+            let (output, gas_used) = if result.result.is_ok() {
+                (Ok(&result.output), result.gas.spent())
+            } else {
+                (Err(result.result), gas_limit)
+            };
+            ctx.on_precompile_result(inputs.bytecode_address, &inputs.input, output, gas_used);
+
             if result.result.is_ok() {
                 ctx.journal_mut().checkpoint_commit();
             } else {
@@ -310,7 +328,12 @@ impl EthFrame<EthInterpreter> {
         // Create address
         let mut init_code_hash = B256::ZERO;
         let created_address = match inputs.scheme {
-            CreateScheme::Create => inputs.caller.create(old_nonce),
+            CreateScheme::Create => {
+                let nonce = context.create_nonce(inputs.caller).unwrap_or(old_nonce);
+                context
+                    .create_address_override(inputs.caller, nonce)
+                    .unwrap_or_else(|| inputs.caller.create(nonce))
+            }
             CreateScheme::Create2 { salt } => {
                 init_code_hash = keccak256(&inputs.init_code);
                 inputs.caller.create2(salt.to_be_bytes(), init_code_hash)
@@ -329,6 +352,23 @@ impl EthFrame<EthInterpreter> {
             spec,
         ) {
             Ok(checkpoint) => checkpoint,
+            Err(TransferError::CreateCollision) => {
+                match context.on_create_collision(created_address) {
+                    CollisionPolicy::Reject => {
+                        return return_error(TransferError::CreateCollision.into())
+                    }
+                    policy => match context.journal_mut().create_account_checkpoint_with_policy(
+                        inputs.caller,
+                        created_address,
+                        inputs.value,
+                        spec,
+                        policy,
+                    ) {
+                        Ok(checkpoint) => checkpoint,
+                        Err(e) => return return_error(e.into()),
+                    },
+                }
+            }
             Err(e) => return return_error(e.into()),
         };
 
@@ -537,6 +577,10 @@ impl EthFrame<EthInterpreter> {
             InterpreterAction::Return(result) => result,
         };
 
+        if context.track_stack_high_water() {
+            context.on_stack_high_water(self.depth, self.interpreter.stack.high_water());
+        }
+
         // Handle return from frame
         let result = match &self.data {
             FrameData::Call(frame) => {
@@ -555,7 +599,7 @@ impl EthFrame<EthInterpreter> {
             FrameData::Create(frame) => {
                 let max_code_size = context.cfg().max_code_size();
                 let is_eip3541_disabled = context.cfg().is_eip3541_disabled();
-                return_create(
+                if let Some(code) = return_create(
                     context.journal_mut(),
                     self.checkpoint,
                     &mut interpreter_result,
@@ -563,7 +607,9 @@ impl EthFrame<EthInterpreter> {
                     max_code_size,
                     is_eip3541_disabled,
                     spec,
-                );
+                ) {
+                    context.on_code_deployed(frame.created_address, &code);
+                }
 
                 ItemOrResult::Result(FrameResult::Create(CreateOutcome::new(
                     interpreter_result,
@@ -591,14 +637,21 @@ impl EthFrame<EthInterpreter> {
         // Insert result to the top frame.
         match result {
             FrameResult::Call(outcome) => {
+                // Pairs with `Host::on_call_enter` called by the call instruction.
+                ctx.on_call_exit();
+
                 let out_gas = outcome.gas();
                 let ins_result = *outcome.instruction_result();
-                let returned_len = outcome.result.output.len();
-
-                let interpreter = &mut self.interpreter;
                 let mem_length = outcome.memory_length();
                 let mem_start = outcome.memory_start();
-                interpreter.return_data.set_buffer(outcome.result.output);
+                let mut output = outcome.result.output;
+                if ins_result.is_revert() {
+                    output.truncate(ctx.max_revert_data());
+                }
+                let returned_len = output.len();
+
+                let interpreter = &mut self.interpreter;
+                interpreter.return_data.set_buffer(output);
 
                 let target_len = min(mem_length, returned_len);
 
@@ -632,9 +685,9 @@ impl EthFrame<EthInterpreter> {
 
                 if instruction_result == InstructionResult::Revert {
                     // Save data to return data buffer if the create reverted
-                    interpreter
-                        .return_data
-                        .set_buffer(outcome.output().to_owned());
+                    let mut output = outcome.output().to_owned();
+                    output.truncate(ctx.max_revert_data());
+                    interpreter.return_data.set_buffer(output);
                 } else {
                     // Otherwise clear it. Note that RETURN opcode should abort.
                     interpreter.return_data.clear();
@@ -668,6 +721,8 @@ impl EthFrame<EthInterpreter> {
 }
 
 /// Handles the result of a CREATE operation, including validation and state updates.
+///
+/// Returns the deployed bytecode if the creation succeeded and the code is stored.
 pub fn return_create<JOURNAL: JournalTr>(
     journal: &mut JOURNAL,
     checkpoint: JournalCheckpoint,
@@ -676,11 +731,11 @@ pub fn return_create<JOURNAL: JournalTr>(
     max_code_size: usize,
     is_eip3541_disabled: bool,
     spec_id: SpecId,
-) {
+) -> Option<Bytecode> {
     // If return is not ok revert and return.
     if !interpreter_result.result.is_ok() {
         journal.checkpoint_revert(checkpoint);
-        return;
+        return None;
     }
     // Host error if present on execution
     // If ok, check contract creation limit and calculate gas deduction on output len.
@@ -692,7 +747,7 @@ pub fn return_create<JOURNAL: JournalTr>(
     {
         journal.checkpoint_revert(checkpoint);
         interpreter_result.result = InstructionResult::CreateContractStartingWithEF;
-        return;
+        return None;
     }
 
     // EIP-170: Contract code size limit to 0x6000 (~25kb)
@@ -700,7 +755,7 @@ pub fn return_create<JOURNAL: JournalTr>(
     if spec_id.is_enabled_in(SPURIOUS_DRAGON) && interpreter_result.output.len() > max_code_size {
         journal.checkpoint_revert(checkpoint);
         interpreter_result.result = InstructionResult::CreateContractSizeLimit;
-        return;
+        return None;
     }
     let gas_for_code = interpreter_result.output.len() as u64 * gas::CODEDEPOSIT;
     if !interpreter_result.gas.record_cost(gas_for_code) {
@@ -711,7 +766,7 @@ pub fn return_create<JOURNAL: JournalTr>(
         if spec_id.is_enabled_in(HOMESTEAD) {
             journal.checkpoint_revert(checkpoint);
             interpreter_result.result = InstructionResult::OutOfGas;
-            return;
+            return None;
         } else {
             interpreter_result.output = Bytes::new();
         }
@@ -723,9 +778,10 @@ pub fn return_create<JOURNAL: JournalTr>(
     let bytecode = Bytecode::new_legacy(interpreter_result.output.clone());
 
     // Set code
-    journal.set_code(address, bytecode);
+    journal.set_code(address, bytecode.clone());
 
     interpreter_result.result = InstructionResult::Return;
+    Some(bytecode)
 }
 
 /*
@@ -771,3 +827,414 @@ pub fn return_eofcreate<JOURNAL: JournalTr>(
     journal.set_code(address, Bytecode::Eof(Arc::new(bytecode)));
 }
  */
+
+#[cfg(test)]
+mod tests {
+    use crate::{ExecuteEvm, MainBuilder, MainContext};
+
+    use bytecode::{
+        opcode::{
+            CALL, CALLDATASIZE, GAS, JUMP, JUMPDEST, JUMPI, MLOAD, MSTORE, POP, PUSH0, PUSH1,
+            PUSH20, RETURN, RETURNDATASIZE, REVERT, SSTORE, STOP,
+        },
+        Bytecode,
+    };
+    use context::{
+        result::{ExecutionResult, HaltReason, OutOfGasError},
+        CfgEnv, Context, TxEnv,
+    };
+    use context_interface::{HostHooks, JournalTr};
+
+    use database::{BenchmarkDB, InMemoryDB, BENCH_CALLER, BENCH_TARGET, FFADDRESS};
+    use interpreter::{Gas, InstructionResult, InterpreterResult};
+    use primitives::StorageKey;
+    use primitives::{address, hardfork::SpecId, Address, Bytes, TxKind, B256, U256};
+    use state::AccountInfo;
+
+    #[test]
+    fn max_revert_data_truncates_revert_data() {
+        const CALLER: Address = address!("0x1000000000000000000000000000000000000000");
+        const CALLEE: Address = address!("0x2000000000000000000000000000000000000000");
+
+        // Reverts with 64 bytes, the first word is 0x2a.
+        let callee = [PUSH1, 0x2a, PUSH0, MSTORE, PUSH1, 0x40, PUSH0, REVERT];
+        // Fills the second memory word, calls the callee with 64 bytes of return memory and
+        // stores the return data size and both memory words.
+        let mut caller = vec![PUSH1, 0x77, PUSH1, 0x20, MSTORE];
+        caller.extend([PUSH1, 0x40, PUSH0, PUSH0, PUSH0, PUSH0, PUSH20]);
+        caller.extend_from_slice(CALLEE.as_slice());
+        caller.extend([GAS, CALL, POP]);
+        caller.extend([RETURNDATASIZE, PUSH0, SSTORE]);
+        caller.extend([PUSH0, MLOAD, PUSH1, 0x01, SSTORE]);
+        caller.extend([PUSH1, 0x20, MLOAD, PUSH1, 0x02, SSTORE, STOP]);
+
+        let run = |cfg: CfgEnv| {
+            let mut db = InMemoryDB::default();
+            db.insert_account_info(
+                CALLER,
+                AccountInfo::from_bytecode(Bytecode::new_legacy(caller.clone().into())),
+            );
+            db.insert_account_info(
+                CALLEE,
+                AccountInfo::from_bytecode(Bytecode::new_legacy(callee.to_vec().into())),
+            );
+            let mut evm = Context::mainnet().with_cfg(cfg).with_db(db).build_mainnet();
+            let result = evm
+                .transact(
+                    TxEnv::builder()
+                        .caller(BENCH_CALLER)
+                        .kind(TxKind::Call(CALLER))
+                        .gas_limit(1_000_000)
+                        .build()
+                        .unwrap(),
+                )
+                .unwrap();
+            assert!(result.result.is_success());
+            let storage = &result.state[&CALLER].storage;
+            [0u64, 1, 2].map(|key| storage[&StorageKey::from(key)].present_value)
+        };
+
+        // Off by default, all revert data is copied.
+        assert_eq!(
+            run(CfgEnv::default()),
+            [U256::from(64), U256::from(0x2a), U256::ZERO]
+        );
+
+        // Only the first word is returned, the second memory word is kept.
+        assert_eq!(
+            run(CfgEnv::default().with_max_revert_data(32)),
+            [U256::from(32), U256::from(0x2a), U256::from(0x77)]
+        );
+    }
+
+    /// Chain context that limits reentrancy and counts storage accesses.
+    #[derive(Default)]
+    struct ReentrancyChain {
+        max_reentrancy: Option<usize>,
+        storage_accesses: usize,
+    }
+
+    impl HostHooks for ReentrancyChain {
+        fn on_storage_access(&mut self, _address: Address, _key: StorageKey, _is_write: bool) {
+            self.storage_accesses += 1;
+        }
+
+        fn max_reentrancy(&self) -> usize {
+            self.max_reentrancy.unwrap_or(usize::MAX)
+        }
+    }
+
+    #[test]
+    fn max_reentrancy_of_chain_context() {
+        const OUTER: Address = address!("0x1000000000000000000000000000000000000000");
+        const INNER: Address = address!("0x2000000000000000000000000000000000000000");
+
+        // Without input calls the inner contract and stores the call result, with input stops.
+        let mut outer = vec![CALLDATASIZE, PUSH1, 0x23, JUMPI];
+        outer.extend([PUSH0, PUSH0, PUSH0, PUSH0, PUSH0, PUSH20]);
+        outer.extend_from_slice(INNER.as_slice());
+        outer.extend([GAS, CALL, PUSH0, SSTORE, STOP, JUMPDEST, STOP]);
+        // Reenters the outer contract with one byte of input and stores the call result.
+        let mut inner = vec![PUSH0, PUSH0, PUSH1, 0x01, PUSH0, PUSH0, PUSH20];
+        inner.extend_from_slice(OUTER.as_slice());
+        inner.extend([GAS, CALL, PUSH0, SSTORE, STOP]);
+
+        let run = |chain: ReentrancyChain| {
+            let mut db = InMemoryDB::default();
+            db.insert_account_info(
+                OUTER,
+                AccountInfo::from_bytecode(Bytecode::new_legacy(outer.clone().into())),
+            );
+            db.insert_account_info(
+                INNER,
+                AccountInfo::from_bytecode(Bytecode::new_legacy(inner.clone().into())),
+            );
+            let mut evm = Context::mainnet()
+                .with_db(db)
+                .with_chain(chain)
+                .build_mainnet();
+            let result = evm
+                .transact(
+                    TxEnv::builder()
+                        .caller(BENCH_CALLER)
+                        .kind(TxKind::Call(OUTER))
+                        .gas_limit(1_000_000)
+                        .build()
+                        .unwrap(),
+                )
+                .unwrap();
+            assert!(result.result.is_success());
+            let stored = |address: Address| {
+                result.state[&address]
+                    .storage
+                    .get(&StorageKey::ZERO)
+                    .map(|slot| slot.present_value)
+                    .unwrap_or_default()
+            };
+            (stored(OUTER), stored(INNER), evm.ctx.chain.storage_accesses)
+        };
+
+        // Reentrancy is not limited by default.
+        assert_eq!(
+            run(ReentrancyChain::default()),
+            (U256::from(1), U256::from(1), 2)
+        );
+        assert_eq!(
+            run(ReentrancyChain {
+                max_reentrancy: Some(1),
+                ..Default::default()
+            }),
+            (U256::from(1), U256::from(1), 2)
+        );
+
+        // The outer contract is the transaction target, so the inner contract halts when it
+        // reenters it, before its own storage access, and the outer contract stores the failure.
+        assert_eq!(
+            run(ReentrancyChain {
+                max_reentrancy: Some(0),
+                ..Default::default()
+            }),
+            (U256::ZERO, U256::ZERO, 1)
+        );
+    }
+
+    /// Chain context with an opcode budget.
+    struct OpcodeBudgetChain(u64);
+
+    impl HostHooks for OpcodeBudgetChain {
+        fn opcode_budget(&self) -> Option<u64> {
+            Some(self.0)
+        }
+
+        fn record_opcodes(&mut self, count: u64) {
+            self.0 -= count;
+        }
+    }
+
+    #[test]
+    fn opcode_budget_halts_loop() {
+        let code = Bytecode::new_legacy([JUMPDEST, PUSH0, JUMP].into());
+        let mut evm = Context::mainnet()
+            .with_db(BenchmarkDB::new_bytecode(code))
+            .with_chain(OpcodeBudgetChain(30))
+            .build_mainnet();
+        let result = evm
+            .transact(
+                TxEnv::builder()
+                    .caller(BENCH_CALLER)
+                    .kind(TxKind::Call(BENCH_TARGET))
+                    .gas_limit(1_000_000)
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap();
+
+        assert!(matches!(
+            result.result,
+            ExecutionResult::Halt {
+                reason: HaltReason::OpcodeBudgetExhausted,
+                ..
+            }
+        ));
+        assert_eq!(evm.ctx.chain.0, 0);
+    }
+
+    /// Chain context that records the stack high water of each frame.
+    #[derive(Default)]
+    struct StackHighWaterChain(Vec<(usize, usize)>);
+
+    impl HostHooks for StackHighWaterChain {
+        fn track_stack_high_water(&self) -> bool {
+            true
+        }
+
+        fn on_stack_high_water(&mut self, frame_depth: usize, stack_depth: usize) {
+            self.0.push((frame_depth, stack_depth));
+        }
+    }
+
+    #[test]
+    fn stack_high_water_of_frames() {
+        const CALLEE: Address = address!("0x2000000000000000000000000000000000000000");
+
+        // Pushes the call arguments, seven items, and leaves the call result on the stack.
+        let mut caller = vec![PUSH0, PUSH0, PUSH0, PUSH0, PUSH0, PUSH20];
+        caller.extend_from_slice(CALLEE.as_slice());
+        caller.extend([GAS, CALL, STOP]);
+        let callee = [PUSH0, PUSH0, PUSH0, POP, POP, POP, STOP];
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            BENCH_TARGET,
+            AccountInfo::from_bytecode(Bytecode::new_legacy(caller.into())),
+        );
+        db.insert_account_info(
+            CALLEE,
+            AccountInfo::from_bytecode(Bytecode::new_legacy(callee.to_vec().into())),
+        );
+        let mut evm = Context::mainnet()
+            .with_db(db)
+            .with_chain(StackHighWaterChain::default())
+            .build_mainnet();
+        let result = evm
+            .transact(
+                TxEnv::builder()
+                    .caller(BENCH_CALLER)
+                    .kind(TxKind::Call(BENCH_TARGET))
+                    .gas_limit(100_000)
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap();
+
+        assert!(result.result.is_success());
+        assert_eq!(evm.ctx.chain.0, [(1, 3), (0, 7)]);
+    }
+
+    #[test]
+    fn identity_precompile_output_and_gas() {
+        const IDENTITY: Address = address!("0x0000000000000000000000000000000000000004");
+        let mut evm = Context::mainnet()
+            .modify_cfg_chained(|cfg| cfg.spec = SpecId::CANCUN)
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+            .build_mainnet();
+        let tx = TxEnv::builder()
+            .caller(BENCH_CALLER)
+            .kind(TxKind::Call(IDENTITY))
+            .data(Bytes::from_static(&[1, 2, 3]))
+            .gas_limit(100_000)
+            .build()
+            .unwrap();
+
+        let result = evm.transact(tx).unwrap().result;
+        assert_eq!(result.output(), Some(&Bytes::from_static(&[1, 2, 3])));
+        // Intrinsic gas plus the identity charge of 15 + 3 per word.
+        assert_eq!(result.gas_used(), 21_000 + 3 * 16 + 15 + 3);
+    }
+
+    /// Chain context that records precompile calls.
+    #[derive(Default)]
+    struct PrecompileTraceChain(Vec<(Address, Bytes, Result<Bytes, HaltReason>, u64)>);
+
+    impl HostHooks for PrecompileTraceChain {
+        fn on_precompile_result(
+            &mut self,
+            address: Address,
+            input: &[u8],
+            output: Result<&Bytes, HaltReason>,
+            gas_used: u64,
+        ) {
+            self.0.push((
+                address,
+                Bytes::copy_from_slice(input),
+                output.cloned(),
+                gas_used,
+            ));
+        }
+    }
+
+    #[test]
+    fn precompile_result_of_identity() {
+        const IDENTITY: Address = address!("0x0000000000000000000000000000000000000004");
+
+        // Calls the identity precompile with the memory word 0x2a, then with one gas.
+        let mut code = vec![PUSH1, 0x2a, PUSH0, MSTORE];
+        code.extend([
+            PUSH0, PUSH0, PUSH1, 0x20, PUSH0, PUSH0, PUSH1, 0x04, GAS, CALL,
+        ]);
+        code.extend([
+            PUSH0, PUSH0, PUSH1, 0x20, PUSH0, PUSH0, PUSH1, 0x04, PUSH1, 0x01, CALL,
+        ]);
+        code.push(STOP);
+
+        let run = |kind: TxKind, data: Bytes| {
+            let mut evm = Context::mainnet()
+                .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                    code.clone().into(),
+                )))
+                .with_chain(PrecompileTraceChain::default())
+                .build_mainnet();
+            let tx = TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(kind)
+                .data(data)
+                .gas_limit(100_000)
+                .build()
+                .unwrap();
+            assert!(evm.transact(tx).unwrap().result.is_success());
+            core::mem::take(&mut evm.ctx.chain.0)
+        };
+
+        // Input of the transaction.
+        assert_eq!(
+            run(TxKind::Call(IDENTITY), Bytes::from_static(&[1, 2, 3])),
+            [(
+                IDENTITY,
+                Bytes::from_static(&[1, 2, 3]),
+                Ok(Bytes::from_static(&[1, 2, 3])),
+                15 + 3
+            )]
+        );
+
+        // Input in the memory of the calling contract.
+        let word = Bytes::from(B256::with_last_byte(0x2a).to_vec());
+        assert_eq!(
+            run(TxKind::Call(BENCH_TARGET), Bytes::new()),
+            [
+                (IDENTITY, word.clone(), Ok(word.clone()), 15 + 3),
+                (
+                    IDENTITY,
+                    word,
+                    Err(HaltReason::OutOfGas(OutOfGasError::Precompile)),
+                    1
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn return_create_returns_deployed_code() {
+        use context::Journal;
+
+        let mut journal = Journal::<_>::new(BenchmarkDB::default());
+        journal.load_account(FFADDRESS).unwrap();
+        let runtime = Bytes::from_static(&[PUSH0, PUSH0, RETURN]);
+        let mut create = |max_code_size| {
+            let checkpoint = journal.checkpoint();
+            let mut result = InterpreterResult::new(
+                InstructionResult::Return,
+                runtime.clone(),
+                Gas::new(10_000),
+            );
+            let code = crate::return_create(
+                &mut journal,
+                checkpoint,
+                &mut result,
+                FFADDRESS,
+                max_code_size,
+                false,
+                SpecId::PRAGUE,
+            );
+            (code, result.result)
+        };
+
+        // Code over the EIP-170 limit is not deployed.
+        assert_eq!(
+            create(2),
+            (None, InstructionResult::CreateContractSizeLimit)
+        );
+
+        let (code, result) = create(0x6000);
+        assert_eq!(result, InstructionResult::Return);
+        assert_eq!(code.unwrap().original_bytes(), runtime);
+        assert_eq!(
+            journal.state[&FFADDRESS]
+                .info
+                .code
+                .as_ref()
+                .unwrap()
+                .original_bytes(),
+            runtime
+        );
+    }
+}
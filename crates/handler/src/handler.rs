@@ -3,16 +3,39 @@ use crate::{
     ItemOrResult,
 };
 use context::result::{ExecutionResult, FromStringError};
-use context::LocalContextTr;
+use context::{CfgEnv, Context, Journal, JournalEntryTr, JournalSnapshot, LocalContextTr};
 use context_interface::context::ContextError;
 use context_interface::ContextTr;
 use context_interface::{
     result::{HaltReasonTr, InvalidHeader, InvalidTransaction},
-    Cfg, Database, JournalTr, Transaction,
+    Block, Cfg, Database, JournalTr, Transaction,
 };
 use interpreter::interpreter_action::FrameInit;
-use interpreter::{Gas, InitialAndFloorGas, SharedMemory};
-use primitives::U256;
+use interpreter::{
+    gas, CallInput, CallInputs, CallOutcome, CallScheme, CallValue, FrameInput, Gas, Host,
+    InitialAndFloorGas, InstructionResult, InterpreterResult, SharedMemory,
+};
+use primitives::{hardfork::SpecId, Address, Bytes, Log, TxKind, U256};
+use state::Bytecode;
+use std::{boxed::Box, vec::Vec};
+
+/// Gas limit given to the first frame by [`Handler::run_gas_free`].
+///
+/// Half of [`u64::MAX`] so that adding call stipends and refunds can't overflow.
+pub const GAS_FREE_LIMIT: u64 = u64::MAX / 2;
+
+/// Step of [`Handler::post_execution`], run in the order of [`Handler::post_execution_order`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PostStep {
+    /// [`Handler::refund`], caps the gas refund and adds the EIP-7702 refund.
+    Refund,
+    /// [`Handler::eip7623_check_gas_floor`], spends at least the floor gas.
+    GasFloor,
+    /// [`Handler::reimburse_caller`], returns the unused gas to the caller.
+    ReimburseCaller,
+    /// [`Handler::reward_beneficiary`], pays the transaction fees to the beneficiary.
+    RewardBeneficiary,
+}
 
 /// Trait for errors that can occur during EVM execution.
 ///
@@ -90,16 +113,155 @@ pub trait Handler {
     /// # Returns
     ///
     /// Returns execution result, error, gas spend and logs.
+    ///
+    /// Gas used is added to the cumulative gas used of the block, see
+    /// [`Handler::record_cumulative_gas_used`].
     #[inline]
     fn run(
         &mut self,
         evm: &mut Self::Evm,
     ) -> Result<ExecutionResult<Self::HaltReason>, Self::Error> {
         // Run inner handler and catch all errors to handle cleanup.
-        match self.run_without_catch_error(evm) {
+        let result = match self.run_without_catch_error(evm) {
             Ok(output) => Ok(output),
             Err(e) => self.catch_error(evm, e),
+        };
+        if let Ok(output) = &result {
+            self.record_cumulative_gas_used(evm, output);
         }
+        result
+    }
+
+    /// Adds the gas used by the transaction to the cumulative gas used of the block, kept in
+    /// [`LocalContextTr::cumulative_gas_used`] of the context.
+    ///
+    /// Called by [`Handler::run`] and `InspectorHandler::inspect_run` for transactions that
+    /// returned a result. Transactions that failed validation are not included in the block and
+    /// are not counted, neither are the discarded runs of [`Handler::run_with_retry`].
+    #[inline]
+    fn record_cumulative_gas_used(
+        &self,
+        evm: &mut Self::Evm,
+        result: &ExecutionResult<Self::HaltReason>,
+    ) {
+        let local = evm.ctx().local_mut();
+        let cumulative_gas_used = local
+            .cumulative_gas_used()
+            .saturating_add(result.gas_used());
+        local.set_cumulative_gas_used(cumulative_gas_used);
+    }
+
+    /// Returns the gas used by the transactions run since the last [`Handler::reset_block`],
+    /// which is the cumulative gas used of the receipt of the last transaction.
+    #[inline]
+    fn cumulative_gas_used(&self, evm: &Self::Evm) -> u64 {
+        evm.ctx_ref().local().cumulative_gas_used()
+    }
+
+    /// Resets the cumulative gas used, to be called at the start of a new block.
+    #[inline]
+    fn reset_block(&self, evm: &mut Self::Evm) {
+        evm.ctx().local_mut().set_cumulative_gas_used(0);
+    }
+
+    /// Runs the transaction under the given spec and restores the previous spec afterwards.
+    ///
+    /// Both configuration and journal spec are switched, so gas schedule and EIP gating
+    /// follow the given spec. Previous spec is restored even if execution fails.
+    ///
+    /// This is useful to compare how the same transaction behaves under different hardforks.
+    /// It is available for EVMs with the [`Context`] type, whose configuration can be changed.
+    #[inline]
+    fn run_with_spec<SPEC, BLOCK, TX, DB, JOURNAL, CHAIN, LOCAL>(
+        &mut self,
+        evm: &mut Self::Evm,
+        spec: SPEC,
+    ) -> Result<ExecutionResult<Self::HaltReason>, Self::Error>
+    where
+        SPEC: Into<SpecId> + Copy,
+        DB: Database,
+        JOURNAL: JournalTr<Database = DB>,
+        LOCAL: LocalContextTr,
+        Self::Evm: EvmTr<Context = Context<BLOCK, TX, CfgEnv<SPEC>, DB, JOURNAL, CHAIN, LOCAL>>,
+    {
+        let ctx = evm.ctx();
+        let previous_spec = core::mem::replace(&mut ctx.cfg.spec, spec);
+        ctx.journaled_state.set_spec_id(spec.into());
+
+        let result = self.run(evm);
+
+        let ctx = evm.ctx();
+        ctx.cfg.spec = previous_spec;
+        ctx.journaled_state.set_spec_id(previous_spec.into());
+        result
+    }
+
+    /// Runs the transaction under the given chain id and restores the previous chain id afterwards.
+    ///
+    /// The chain id is read both by the transaction chain id check in [`Handler::validate_env`]
+    /// and by the `CHAINID` instruction. Previous chain id is restored even if execution fails.
+    ///
+    /// This is useful to test replay protection, with [`CfgEnv::tx_chain_id_check`] enabled
+    /// typed transactions signed for another chain are rejected. Like [`Handler::run_with_spec`]
+    /// it is available for EVMs with the [`Context`] type.
+    #[inline]
+    fn run_with_chain_id<SPEC, BLOCK, TX, DB, JOURNAL, CHAIN, LOCAL>(
+        &mut self,
+        evm: &mut Self::Evm,
+        chain_id: u64,
+    ) -> Result<ExecutionResult<Self::HaltReason>, Self::Error>
+    where
+        DB: Database,
+        JOURNAL: JournalTr<Database = DB>,
+        LOCAL: LocalContextTr,
+        Self::Evm: EvmTr<Context = Context<BLOCK, TX, CfgEnv<SPEC>, DB, JOURNAL, CHAIN, LOCAL>>,
+    {
+        let previous_chain_id = core::mem::replace(&mut evm.ctx().cfg.chain_id, chain_id);
+
+        let result = self.run(evm);
+
+        evm.ctx().cfg.chain_id = previous_chain_id;
+        result
+    }
+
+    /// Runs the transaction and runs it again from `snapshot` while `conflict_check` reports a
+    /// conflict, as done by optimistic concurrent execution.
+    ///
+    /// `snapshot` is taken with [`JournalInner::snapshot`](context::JournalInner::snapshot)
+    /// before the transaction. After every run `conflict_check` is called, if it returns `true`
+    /// the journal is restored to the snapshot and the transaction is retried, at most
+    /// `max_retries` times, so the transaction is executed up to `max_retries + 1` times. The
+    /// local context and the cumulative gas used are restored together with the journal. If the
+    /// conflict persists after the last retry
+    /// [`InvalidTransaction::ConflictAfterRetries`] is returned.
+    ///
+    /// Errors of the transaction itself are returned without retrying.
+    #[inline]
+    fn run_with_retry<DB, ENTRY>(
+        &mut self,
+        evm: &mut Self::Evm,
+        snapshot: &JournalSnapshot<ENTRY>,
+        max_retries: usize,
+        conflict_check: impl Fn(&Self::Evm) -> bool,
+    ) -> Result<ExecutionResult<Self::HaltReason>, Self::Error>
+    where
+        ENTRY: JournalEntryTr + Clone,
+        <Self::Evm as EvmTr>::Context: ContextTr<Journal = Journal<DB, ENTRY>>,
+    {
+        // Only the gas of the accepted run is counted in the block.
+        let cumulative_gas_used = self.cumulative_gas_used(evm);
+        for _ in 0..=max_retries {
+            let result = self.run(evm)?;
+            if !conflict_check(evm) {
+                return Ok(result);
+            }
+            let ctx = evm.ctx();
+            ctx.journal_mut().restore(snapshot);
+            let local = ctx.local_mut();
+            local.clear();
+            local.set_cumulative_gas_used(cumulative_gas_used);
+        }
+        Err(InvalidTransaction::ConflictAfterRetries { max_retries }.into())
     }
 
     /// Runs the system call.
@@ -133,6 +295,201 @@ pub trait Handler {
         }
     }
 
+    /// Runs the transaction without gas accounting, for state inspection.
+    ///
+    /// The first frame is given [`GAS_FREE_LIMIT`] gas instead of the transaction gas limit, and
+    /// `validation`, caller deduction, refunds, reimbursement and beneficiary reward are skipped.
+    /// Accounts and EIP-7702 authorizations are still loaded before execution.
+    ///
+    /// All other halts still occur (invalid opcode, stack overflow, call depth, ...). Out of gas
+    /// can still happen when a single cost exceeds the budget, for example memory expansion to an
+    /// unreasonable size, and nested calls are still limited by EIP-150 63/64 forwarding.
+    ///
+    /// Reported gas used excludes intrinsic gas and is not meaningful for fee calculation.
+    ///
+    /// State changes can be obtained by calling [`JournalTr::finalize`] method from the [`EvmTr::Context`].
+    #[inline]
+    fn run_gas_free(
+        &mut self,
+        evm: &mut Self::Evm,
+    ) -> Result<ExecutionResult<Self::HaltReason>, Self::Error> {
+        let result = self
+            .load_accounts(evm)
+            .and_then(|_| self.apply_eip7702_auth_list(evm))
+            .and_then(|_| self.first_frame_input(evm, GAS_FREE_LIMIT))
+            .and_then(|first_frame_input| self.run_exec_loop(evm, first_frame_input))
+            .and_then(|exec_result| self.execution_result(evm, exec_result));
+        match result {
+            out @ Ok(_) => out,
+            Err(e) => self.catch_error(evm, e),
+        }
+    }
+
+    /// Estimates the gas limit needed by the transaction, the core of `eth_estimateGas` without
+    /// the binary search.
+    ///
+    /// The transaction is executed like in [`Handler::run_gas_free`], with [`GAS_FREE_LIMIT`]
+    /// gas and without fee payment, then the journal is discarded with
+    /// [`JournalTr::discard_tx`] so no state persists. Initial gas is validated with
+    /// [`Handler::validate_initial_tx_gas`], so the gas limit of the transaction, usually the cap
+    /// of the search, must cover it.
+    ///
+    /// The estimate is the initial gas plus the gas spent by execution before refunds, and at
+    /// least the EIP-7623 floor gas and its [`Handler::gas_floor_override`]. The
+    /// [`Handler::delegated_execution_surcharge`] is included if it was charged, the
+    /// [`Handler::sponsored_gas`] is not paid from the transaction gas limit and is subtracted
+    /// from the execution gas.
+    ///
+    /// Execution that reverts or halts returns an error, as the transaction fails with any gas
+    /// limit. Because of the 63/64 rule of calls, a transaction can still run out of gas at the
+    /// estimated limit.
+    #[inline]
+    fn estimate_gas(&mut self, evm: &mut Self::Evm) -> Result<u64, Self::Error> {
+        let init_and_floor_gas = self.validate_initial_tx_gas(evm)?;
+        let execution_gas_limit = GAS_FREE_LIMIT.saturating_add(self.sponsored_gas(evm));
+        let mut surcharge = 0;
+        let result = self
+            .load_accounts(evm)
+            .and_then(|_| self.apply_eip7702_auth_list(evm))
+            .and_then(|_| self.first_frame_input(evm, GAS_FREE_LIMIT))
+            .and_then(|first_frame_input| {
+                // Surcharge is subtracted from the gas limit of the first frame.
+                let gas_limit = match &first_frame_input.frame_input {
+                    FrameInput::Call(inputs) => inputs.gas_limit,
+                    FrameInput::Create(inputs) => inputs.gas_limit,
+                    FrameInput::Empty => execution_gas_limit,
+                };
+                surcharge = execution_gas_limit.saturating_sub(gas_limit);
+                self.run_exec_loop(evm, first_frame_input)
+            });
+
+        // Same cleanup as `catch_error`, state changes are discarded.
+        evm.ctx().local_mut().clear();
+        evm.ctx().journal_mut().discard_tx();
+        evm.frame_stack().clear();
+
+        let exec_result = result?;
+        let instruction_result = exec_result.interpreter_result().result;
+        if !instruction_result.is_ok() {
+            return Err(Self::Error::from_string(std::format!(
+                "gas estimation failed, execution ended with {instruction_result:?}"
+            )));
+        }
+        let execution_gas = exec_result
+            .gas()
+            .spent()
+            .saturating_add(surcharge)
+            .saturating_sub(self.sponsored_gas(evm));
+        let gas = self
+            .initial_gas(&init_and_floor_gas)
+            .saturating_add(execution_gas);
+        let floor_gas = self.floor_gas(&init_and_floor_gas);
+        Ok(gas
+            .max(floor_gas)
+            .max(self.gas_floor_override(evm, floor_gas)))
+    }
+
+    /// Executes a message call from `from` to `to` without a transaction, as done by `eth_call`.
+    ///
+    /// Accounts are loaded with [`Handler::load_accounts`] and `from` is warmed, then the call is
+    /// run with [`Handler::run_exec_loop`]. Validation, fee deduction and the post-execution
+    /// phase are skipped, so `from` does not need to exist unless `value` is transferred.
+    /// Instructions that read the transaction (`ORIGIN`, `GASPRICE`, ...) still read the one set
+    /// in the context.
+    ///
+    /// State changes stay in the journal and can be dropped with [`JournalTr::discard_tx`].
+    #[inline]
+    fn call_direct(
+        &mut self,
+        evm: &mut Self::Evm,
+        from: Address,
+        to: Address,
+        input: Bytes,
+        value: U256,
+        gas: u64,
+    ) -> Result<FrameResult, Self::Error> {
+        self.load_accounts(evm)?;
+        evm.ctx().journal_mut().load_account(from)?;
+
+        let memory =
+            SharedMemory::new_with_buffer(evm.ctx().local().shared_memory_buffer().clone());
+        let first_frame_input = FrameInit {
+            depth: 0,
+            memory,
+            frame_input: FrameInput::Call(Box::new(CallInputs {
+                input: CallInput::Bytes(input),
+                gas_limit: gas,
+                target_address: to,
+                bytecode_address: to,
+                caller: from,
+                value: CallValue::Transfer(value),
+                scheme: CallScheme::Call,
+                is_static: false,
+                return_memory_offset: 0..0,
+            })),
+        };
+        self.run_exec_loop(evm, first_frame_input)
+    }
+
+    /// Checks if the transaction would pass validation, without executing it, as done by a
+    /// mempool admission check.
+    ///
+    /// Runs [`Handler::validate`] and the state checks of
+    /// [`Handler::validate_against_state_and_deduct_caller`]: caller code (EIP-3607), nonce and
+    /// balance. Nothing is deducted and the warming of the caller account is reverted, so the
+    /// returned errors are the same as of [`Handler::run`] and the state is unchanged.
+    #[inline]
+    fn check_validity(&self, evm: &mut Self::Evm) -> Result<(), Self::Error> {
+        self.validate(evm)?;
+        let checkpoint = evm.ctx().journal_mut().checkpoint();
+        let result = self.validate_no_sender_code(evm).and_then(|_| {
+            let required_balance = self.required_caller_balance(evm);
+            pre_execution::validate_against_state::<_, Self::Error>(evm.ctx(), required_balance)
+        });
+        evm.ctx().journal_mut().checkpoint_revert(checkpoint);
+        result
+    }
+
+    /// Executes the call and returns its output and the logs it emitted, then discards the state
+    /// changes.
+    ///
+    /// Like [`Handler::call_direct`], accounts are loaded with [`Handler::load_accounts`], the
+    /// caller is warmed and the call is run without validation or fees. Logs of reverted frames
+    /// are removed by the revert, so only logs of the frames that succeeded are returned. If
+    /// the call itself reverts or halts the logs are empty and the output is the revert data.
+    ///
+    /// The journal is discarded with [`JournalTr::discard_tx`] afterwards, together with the logs
+    /// of the current transaction, so this is meant to be called between transactions.
+    #[inline]
+    fn call_capture(
+        &mut self,
+        evm: &mut Self::Evm,
+        call: CallInputs,
+    ) -> Result<(Bytes, Vec<Log>), Self::Error> {
+        // Logs emitted before the call are not part of the result.
+        let _ = evm.ctx().journal_mut().take_logs();
+        let result = self.load_accounts(evm).and_then(|_| {
+            evm.ctx().journal_mut().load_account(call.caller)?;
+            let memory =
+                SharedMemory::new_with_buffer(evm.ctx().local().shared_memory_buffer().clone());
+            let first_frame_input = FrameInit {
+                depth: 0,
+                memory,
+                frame_input: FrameInput::Call(Box::new(call)),
+            };
+            self.run_exec_loop(evm, first_frame_input)
+        });
+        let logs = evm.ctx().journal_mut().take_logs();
+
+        // Same cleanup as `catch_error`, state changes are discarded.
+        evm.ctx().local_mut().clear();
+        evm.ctx().journal_mut().discard_tx();
+        evm.frame_stack().clear();
+
+        let output = result?.interpreter_result().output.clone();
+        Ok((output, logs))
+    }
+
     /// Called by [`Handler::run`] to execute the core handler logic.
     ///
     /// Executes the four phases in sequence: [Handler::validate],
@@ -170,11 +527,17 @@ pub trait Handler {
     ///
     /// Deducts the maximum possible fee from the caller's balance.
     ///
+    /// If [`Handler::sponsored_gas`] is not zero, the sponsor is charged with [`Handler::deduct_sponsor`].
+    ///
     /// For EIP-7702 transactions, applies the authorization list and delegates successful authorizations.
     /// Returns the gas refund amount from EIP-7702. Authorizations are applied before execution begins.
     #[inline]
     fn pre_execution(&self, evm: &mut Self::Evm) -> Result<u64, Self::Error> {
         self.validate_against_state_and_deduct_caller(evm)?;
+        let sponsored_gas = self.sponsored_gas(evm);
+        if sponsored_gas != 0 {
+            self.deduct_sponsor(evm, sponsored_gas)?;
+        }
         self.load_accounts(evm)?;
 
         let gas = self.apply_eip7702_auth_list(evm)?;
@@ -183,6 +546,8 @@ pub trait Handler {
 
     /// Creates and executes the initial frame, then processes the execution loop.
     ///
+    /// Deducts the gas fee from the caller first if [`Handler::defer_deduction`] is set.
+    ///
     /// Always calls [Handler::last_frame_result] to handle returned gas from the call.
     #[inline]
     fn execution(
@@ -190,6 +555,9 @@ pub trait Handler {
         evm: &mut Self::Evm,
         init_and_floor_gas: &InitialAndFloorGas,
     ) -> Result<FrameResult, Self::Error> {
+        if self.defer_deduction() {
+            self.deduct_caller(evm)?;
+        }
         let gas_limit = evm.ctx().tx().gas_limit() - init_and_floor_gas.initial_gas;
         // Create first frame action
         let first_frame_input = self.first_frame_input(evm, gas_limit)?;
@@ -210,6 +578,8 @@ pub trait Handler {
     /// Reimburses unused gas to the caller and rewards the beneficiary with transaction fees.
     /// The effective gas price determines rewards, with the base fee being burned.
     ///
+    /// Steps run in the order of [`Handler::post_execution_order`].
+    ///
     /// Finally, finalizes output by returning the journal state and clearing internal state
     /// for the next execution.
     #[inline]
@@ -220,17 +590,41 @@ pub trait Handler {
         init_and_floor_gas: InitialAndFloorGas,
         eip7702_gas_refund: i64,
     ) -> Result<(), Self::Error> {
-        // Calculate final refund and add EIP-7702 refund to gas.
-        self.refund(evm, exec_result, eip7702_gas_refund);
-        // Ensure gas floor is met and minimum floor gas is spent.
-        self.eip7623_check_gas_floor(evm, exec_result, init_and_floor_gas);
-        // Return unused gas to caller
-        self.reimburse_caller(evm, exec_result)?;
-        // Pay transaction fees to beneficiary
-        self.reward_beneficiary(evm, exec_result)?;
+        for step in self.post_execution_order() {
+            match step {
+                // Calculate final refund and add EIP-7702 refund to gas.
+                PostStep::Refund => self.refund(evm, exec_result, eip7702_gas_refund),
+                // Ensure gas floor is met and minimum floor gas is spent.
+                PostStep::GasFloor => {
+                    self.eip7623_check_gas_floor(evm, exec_result, init_and_floor_gas)
+                }
+                // Return unused gas to caller
+                PostStep::ReimburseCaller => self.reimburse_caller(evm, exec_result)?,
+                // Pay transaction fees to beneficiary
+                PostStep::RewardBeneficiary => self.reward_beneficiary(evm, exec_result)?,
+            }
+        }
         Ok(())
     }
 
+    /// Returns the order of the [`Handler::post_execution`] steps.
+    ///
+    /// [`PostStep::Refund`] and [`PostStep::GasFloor`] change the gas that the other two steps
+    /// pay out, and the floor check resets the refund if the floor is not met, so they are
+    /// expected to run first and in this order. Reimbursing the caller and rewarding the
+    /// beneficiary only read the gas and can run in any order.
+    ///
+    /// Default implementation returns refund, gas floor, reimburse caller, reward beneficiary.
+    #[inline]
+    fn post_execution_order(&self) -> [PostStep; 4] {
+        [
+            PostStep::Refund,
+            PostStep::GasFloor,
+            PostStep::ReimburseCaller,
+            PostStep::RewardBeneficiary,
+        ]
+    }
+
     /* VALIDATION */
 
     /// Validates block, transaction and configuration fields.
@@ -253,6 +647,24 @@ pub trait Handler {
         validation::validate_initial_tx_gas(ctx.tx(), ctx.cfg().spec().into()).map_err(From::from)
     }
 
+    /// Returns the intrinsic gas of the transaction computed by [`Handler::validate_initial_tx_gas`].
+    ///
+    /// It covers the base cost, calldata, access list and authorization list and is charged
+    /// before execution.
+    #[inline]
+    fn initial_gas(&self, gas: &InitialAndFloorGas) -> u64 {
+        gas.initial_gas
+    }
+
+    /// Returns the EIP-7623 floor gas computed by [`Handler::validate_initial_tx_gas`].
+    ///
+    /// It is the minimum gas used by the transaction, enforced by
+    /// [`Handler::eip7623_check_gas_floor`]. It is zero before Prague.
+    #[inline]
+    fn floor_gas(&self, gas: &InitialAndFloorGas) -> u64 {
+        gas.floor_gas
+    }
+
     /* PRE EXECUTION */
 
     /// Loads access list and beneficiary account, marking them as warm in the [`context::Journal`].
@@ -272,24 +684,111 @@ pub trait Handler {
 
     /// Deducts maximum possible fee and transfer value from caller's balance.
     ///
+    /// The caller code is checked with [`Handler::validate_no_sender_code`] and the caller
+    /// balance against [`Handler::required_caller_balance`].
     /// Unused fees are returned to caller after execution completes.
+    ///
+    /// The caller nonce is always bumped here, before the EIP-7702 authorizations are applied.
+    /// If [`Handler::defer_deduction`] is set, [`Handler::deduct_caller`] is called at the start
+    /// of [`Handler::execution`] instead.
     #[inline]
     fn validate_against_state_and_deduct_caller(
         &self,
         evm: &mut Self::Evm,
     ) -> Result<(), Self::Error> {
-        pre_execution::validate_against_state_and_deduct_caller(evm.ctx())
+        self.validate_no_sender_code(evm)?;
+        let required_balance = self.required_caller_balance(evm);
+        pre_execution::validate_against_state::<_, Self::Error>(evm.ctx(), required_balance)?;
+        pre_execution::bump_caller_nonce::<_, Self::Error>(evm.ctx())?;
+        if !self.defer_deduction() {
+            self.deduct_caller(evm)?;
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the caller fee deduction is deferred from pre-execution to the start of
+    /// execution.
+    ///
+    /// This allows validating a batch of transactions before any of them changes a balance. The
+    /// fee is checked against the caller balance again when it is deducted. The caller nonce is
+    /// still bumped in pre-execution so EIP-7702 authorizations signed by the caller see it.
+    ///
+    /// Default implementation returns `false`.
+    #[inline]
+    fn defer_deduction(&self) -> bool {
+        false
+    }
+
+    /// Deducts the gas fee from the caller's balance.
+    #[inline]
+    fn deduct_caller(&self, evm: &mut Self::Evm) -> Result<(), Self::Error> {
+        pre_execution::deduct_caller(evm.ctx())
+    }
+
+    /// Rejects transactions from senders with deployed code (EIP-3607).
+    ///
+    /// Senders with an EIP-7702 delegation are allowed. Simulations can override this to send
+    /// transactions from contract accounts.
+    ///
+    /// Default implementation skips the check if it is disabled in the configuration, see
+    /// [`Cfg::is_eip3607_disabled`].
+    #[inline]
+    fn validate_no_sender_code(&self, evm: &mut Self::Evm) -> Result<(), Self::Error> {
+        pre_execution::validate_no_sender_code(evm.ctx())
+    }
+
+    /// Returns the balance the caller must have for the transaction to be valid.
+    ///
+    /// Sponsored flows can return only the maximum fee if the value is paid by someone else.
+    /// The fee is deducted from the caller in any case, and the value is transferred from the
+    /// caller during execution, so the sponsor is expected to credit it before.
+    ///
+    /// Default implementation returns the maximum fee plus the value,
+    /// see [`Transaction::max_balance_spending`].
+    #[inline]
+    fn required_caller_balance(&self, evm: &Self::Evm) -> U256 {
+        // Overflow is reported by the balance check.
+        evm.ctx_ref()
+            .tx()
+            .max_balance_spending()
+            .unwrap_or(U256::MAX)
     }
 
     /* EXECUTION */
 
+    /// Returns the additional gas that is charged when the transaction calls an account
+    /// with an active EIP-7702 delegation.
+    ///
+    /// The surcharge is subtracted from the gas limit of the first frame in
+    /// [`Handler::first_frame_input`], so it is not reimbursed to the caller.
+    ///
+    /// Default is zero.
+    #[inline]
+    fn delegated_execution_surcharge(&self, _evm: &Self::Evm) -> u64 {
+        0
+    }
+
     /// Creates initial frame input using transaction parameters, gas limit and configuration.
+    ///
+    /// [`Handler::sponsored_gas`] is added to the gas limit. If the transaction calls a delegated
+    /// account, the [`Handler::delegated_execution_surcharge`] is subtracted from it.
     #[inline]
     fn first_frame_input(
         &mut self,
         evm: &mut Self::Evm,
         gas_limit: u64,
     ) -> Result<FrameInit, Self::Error> {
+        let mut gas_limit = gas_limit.saturating_add(self.sponsored_gas(evm));
+        let surcharge = self.delegated_execution_surcharge(evm);
+        if surcharge != 0 {
+            if let TxKind::Call(target) = evm.ctx_ref().tx().kind() {
+                let account = evm.ctx().journal_mut().load_account_code(target)?;
+                if let Some(Bytecode::Eip7702(_)) = account.info.code {
+                    gas_limit = gas_limit.saturating_sub(surcharge);
+                }
+            }
+        }
+
         let memory =
             SharedMemory::new_with_buffer(evm.ctx().local().shared_memory_buffer().clone());
         let ctx = evm.ctx_ref();
@@ -307,13 +806,14 @@ pub trait Handler {
         evm: &mut Self::Evm,
         frame_result: &mut <<Self::Evm as EvmTr>::Frame as FrameTr>::FrameResult,
     ) -> Result<(), Self::Error> {
+        let sponsored_gas = self.sponsored_gas(evm);
         let instruction_result = frame_result.interpreter_result().result;
         let gas = frame_result.gas_mut();
         let remaining = gas.remaining();
         let refunded = gas.refunded();
 
-        // Spend the gas limit. Gas is reimbursed when the tx returns successfully.
-        *gas = Gas::new_spent(evm.ctx().tx().gas_limit());
+        // Spend the gas limit, including sponsored gas. Gas is reimbursed when the tx returns successfully.
+        *gas = Gas::new_spent(evm.ctx().tx().gas_limit().saturating_add(sponsored_gas));
 
         if instruction_result.is_ok_or_revert() {
             gas.erase_cost(remaining);
@@ -327,6 +827,57 @@ pub trait Handler {
 
     /* FRAMES */
 
+    /// Called before a frame is initialized in [`Handler::run_exec_loop`].
+    ///
+    /// `depth` is the call depth of the new frame, starting at 0 for the first frame.
+    /// Every call is matched by exactly one [`Handler::on_frame_result`] call with the same depth,
+    /// and calls nest in the order of the call graph, so both hooks together can be used to
+    /// reconstruct the call tree of the transaction.
+    ///
+    /// Default implementation does nothing.
+    #[inline]
+    fn on_frame_enter(&mut self, _depth: usize, _input: &FrameInput) {}
+
+    /// Called when a frame produces its result in [`Handler::run_exec_loop`], before the result is
+    /// returned to the parent frame.
+    ///
+    /// This is called for frames that return without being pushed to the frame stack as well
+    /// (precompiles, empty code, failed checks).
+    ///
+    /// Default implementation does nothing.
+    #[inline]
+    fn on_frame_result(&mut self, _depth: usize, _result: &FrameResult) {}
+
+    /// Returns `true` if the gas of call frames is checked against
+    /// [`Handler::contract_gas_budget`] and reported with [`Handler::record_contract_gas`].
+    ///
+    /// Default implementation forwards to [`Host::track_contract_gas`].
+    #[inline]
+    fn track_contract_gas(&self, evm: &Self::Evm) -> bool {
+        evm.ctx_ref().track_contract_gas()
+    }
+
+    /// Returns the remaining gas budget of `address`, checked in [`Handler::run_exec_loop`]
+    /// before a call frame of `address` is initialized. The gas limit of the call is clamped
+    /// to the budget.
+    ///
+    /// Only called if [`Handler::track_contract_gas`] is enabled.
+    ///
+    /// Default implementation forwards to [`Host::contract_gas_budget`].
+    #[inline]
+    fn contract_gas_budget(&mut self, evm: &mut Self::Evm, address: Address) -> Option<u64> {
+        evm.ctx().contract_gas_budget(address)
+    }
+
+    /// Called in [`Handler::run_exec_loop`] when a call frame of `address` returns, with the gas
+    /// consumed by the frame itself, excluding its child frames.
+    ///
+    /// Default implementation forwards to [`Host::record_contract_gas`].
+    #[inline]
+    fn record_contract_gas(&mut self, evm: &mut Self::Evm, address: Address, gas_used: u64) {
+        evm.ctx().record_contract_gas(address, gas_used)
+    }
+
     /// Executes the main frame processing loop.
     ///
     /// This loop manages the frame stack, processing each frame until execution completes.
@@ -334,34 +885,60 @@ pub trait Handler {
     /// 1. Calls the current frame
     /// 2. Handles the returned frame input or result
     /// 3. Creates new frames or propagates results as needed
+    ///
+    /// [`Handler::on_frame_enter`] and [`Handler::on_frame_result`] are called for every frame.
+    ///
+    /// If [`Handler::track_contract_gas`] is enabled, calls to an address whose
+    /// [`Handler::contract_gas_budget`] is exhausted are halted with
+    /// [`InstructionResult::ContractGasBudgetExhausted`] instead of being initialized, and gas
+    /// consumed by call frames is reported with [`Handler::record_contract_gas`].
     #[inline]
     fn run_exec_loop(
         &mut self,
         evm: &mut Self::Evm,
-        first_frame_input: <<Self::Evm as EvmTr>::Frame as FrameTr>::FrameInit,
+        mut first_frame_input: <<Self::Evm as EvmTr>::Frame as FrameTr>::FrameInit,
     ) -> Result<FrameResult, Self::Error> {
-        let res = evm.frame_init(first_frame_input)?;
+        let mut contract_gas = ContractGasTracker::new(self, evm);
+        let mut depth = first_frame_input.depth;
+        self.on_frame_enter(depth, &first_frame_input.frame_input);
+        let res = match contract_gas.enter(self, evm, &mut first_frame_input.frame_input) {
+            Some(result) => ItemOrResult::Result(result),
+            None => evm.frame_init(first_frame_input)?,
+        };
 
-        if let ItemOrResult::Result(frame_result) = res {
+        if let ItemOrResult::Result(mut frame_result) = res {
+            contract_gas.exit(self, evm, &mut frame_result);
+            self.on_frame_result(depth, &frame_result);
             return Ok(frame_result);
         }
 
         loop {
             let call_or_result = evm.frame_run()?;
 
-            let result = match call_or_result {
-                ItemOrResult::Item(init) => {
-                    match evm.frame_init(init)? {
-                        ItemOrResult::Item(_) => {
-                            continue;
+            let mut result = match call_or_result {
+                ItemOrResult::Item(mut init) => {
+                    depth = init.depth;
+                    self.on_frame_enter(depth, &init.frame_input);
+                    if let Some(result) = contract_gas.enter(self, evm, &mut init.frame_input) {
+                        result
+                    } else {
+                        match evm.frame_init(init)? {
+                            ItemOrResult::Item(_) => {
+                                continue;
+                            }
+                            // Do not pop the frame since no new frame was created
+                            ItemOrResult::Result(result) => result,
                         }
-                        // Do not pop the frame since no new frame was created
-                        ItemOrResult::Result(result) => result,
                     }
                 }
                 ItemOrResult::Result(result) => result,
             };
 
+            contract_gas.exit(self, evm, &mut result);
+            self.on_frame_result(depth, &result);
+            // Result is returned to the parent frame.
+            depth = depth.saturating_sub(1);
+
             if let Some(result) = evm.frame_return_result(result)? {
                 return Ok(result);
             }
@@ -372,18 +949,52 @@ pub trait Handler {
 
     /// Validates that the minimum gas floor requirements are satisfied.
     ///
-    /// Ensures that at least the floor gas amount has been consumed during execution.
+    /// Ensures that at least the floor gas amount, as returned by
+    /// [`Handler::gas_floor_override`], has been consumed during execution.
     #[inline]
     fn eip7623_check_gas_floor(
         &self,
-        _evm: &mut Self::Evm,
+        evm: &mut Self::Evm,
         exec_result: &mut <<Self::Evm as EvmTr>::Frame as FrameTr>::FrameResult,
         init_and_floor_gas: InitialAndFloorGas,
     ) {
-        post_execution::eip7623_check_gas_floor(exec_result.gas_mut(), init_and_floor_gas)
+        let floor_gas = self.gas_floor_override(evm, init_and_floor_gas.floor_gas);
+        post_execution::eip7623_check_gas_floor(
+            exec_result.gas_mut(),
+            InitialAndFloorGas {
+                floor_gas,
+                ..init_and_floor_gas
+            },
+        )
+    }
+
+    /// Returns the gas floor enforced after execution, given the `computed` EIP-7623 floor.
+    ///
+    /// Chains can use it to set a flat floor, for example for transactions without calldata, where
+    /// the EIP-7623 floor equals the intrinsic gas. If less gas was spent, the floor is spent and
+    /// the refund is cleared. Spent gas is capped by the transaction gas limit, as the overridden
+    /// floor is not checked against it in validation.
+    ///
+    /// Default implementation returns `computed`.
+    #[inline]
+    fn gas_floor_override(&self, _evm: &Self::Evm, computed: u64) -> u64 {
+        computed
+    }
+
+    /// Returns `true` if the transaction receives gas refunds.
+    ///
+    /// Analyses can return `false` to get a pessimistic gas usage, both the accumulated refund
+    /// and the EIP-7702 refund are then dropped. Execution itself is not affected.
+    ///
+    /// Default implementation returns `true`.
+    #[inline]
+    fn refunds_enabled(&self, _evm: &Self::Evm) -> bool {
+        true
     }
 
     /// Calculates the final gas refund amount, including any EIP-7702 refunds.
+    ///
+    /// Refund is zero if [`Handler::refunds_enabled`] returns `false`.
     #[inline]
     fn refund(
         &self,
@@ -391,31 +1002,148 @@ pub trait Handler {
         exec_result: &mut <<Self::Evm as EvmTr>::Frame as FrameTr>::FrameResult,
         eip7702_refund: i64,
     ) {
+        if !self.refunds_enabled(evm) {
+            exec_result.gas_mut().set_refund(0);
+            return;
+        }
         let spec = evm.ctx().cfg().spec().into();
         post_execution::refund(spec, exec_result.gas_mut(), eip7702_refund)
     }
 
+    /// Returns `true` if the transaction changed the state beyond the fee payment.
+    ///
+    /// Balance and nonce changes of the caller and the beneficiary are not counted, their
+    /// storage and code changes are. Changes of reverted frames are not counted either.
+    ///
+    /// Must be called before [`Handler::execution_result`] commits the transaction, for
+    /// example from an overridden [`Handler::reward_beneficiary`].
+    #[inline]
+    fn changed_state(&self, evm: &Self::Evm) -> bool {
+        let ctx = evm.ctx_ref();
+        let excluded = [ctx.tx().caller(), ctx.block().beneficiary()];
+        ctx.journal_ref().changes_state(&excluded)
+    }
+
+    /// Returns the maximum refund allowed for a transaction that used `gas_used` gas.
+    ///
+    /// This is the cap applied by [`Handler::refund`], half of gas used before London and a
+    /// fifth of it after. Tooling can use it to show how much of the claimed refund was capped.
+    #[inline]
+    fn refund_cap(&self, evm: &Self::Evm, gas_used: u64) -> u64 {
+        let spec: SpecId = evm.ctx_ref().cfg().spec().into();
+        gas::refund_cap(gas_used, spec.is_enabled_in(SpecId::LONDON))
+    }
+
+    /// Returns the gas added to the execution gas limit on top of the transaction gas limit.
+    ///
+    /// Sponsored gas is paid by a sponsor (for example a paymaster) and not by the caller. It is
+    /// charged with [`Handler::deduct_sponsor`] before execution and its unused part is returned
+    /// with [`Handler::reimburse_sponsor`]. Sponsored gas is used after the caller gas, so unused
+    /// gas is returned to the sponsor first. The block gas limit is not checked against it.
+    ///
+    /// Sponsorship is all or nothing, a handler that returns sponsored gas must also implement
+    /// [`Handler::deduct_sponsor`] and [`Handler::reimburse_sponsor`]. Their default
+    /// implementations return an error, so the beneficiary is never rewarded for gas that nobody
+    /// paid for.
+    ///
+    /// Default implementation returns zero.
+    #[inline]
+    fn sponsored_gas(&self, _evm: &Self::Evm) -> u64 {
+        0
+    }
+
+    /// Charges the sponsor for the [`Handler::sponsored_gas`].
+    ///
+    /// Called in pre-execution after the caller is charged, only if sponsored gas is not zero.
+    ///
+    /// Default implementation returns an error, as the sponsored gas would not be paid.
+    #[inline]
+    fn deduct_sponsor(&self, _evm: &mut Self::Evm, _sponsored_gas: u64) -> Result<(), Self::Error> {
+        Err(Self::Error::from_string(
+            "sponsored gas requires `Handler::deduct_sponsor` to be implemented".into(),
+        ))
+    }
+
+    /// Returns the unused part of the [`Handler::sponsored_gas`] to the sponsor.
+    ///
+    /// Called before the caller is reimbursed, only if sponsored gas is not zero.
+    ///
+    /// Default implementation returns an error, as the unused sponsored gas would be lost.
+    #[inline]
+    fn reimburse_sponsor(&self, _evm: &mut Self::Evm, _unused_gas: u64) -> Result<(), Self::Error> {
+        Err(Self::Error::from_string(
+            "sponsored gas requires `Handler::reimburse_sponsor` to be implemented".into(),
+        ))
+    }
+
     /// Returns unused gas costs to the transaction sender's account.
+    ///
+    /// Unused sponsored gas is returned to the sponsor and is not reimbursed to the caller.
     #[inline]
     fn reimburse_caller(
         &self,
         evm: &mut Self::Evm,
         exec_result: &mut <<Self::Evm as EvmTr>::Frame as FrameTr>::FrameResult,
     ) -> Result<(), Self::Error> {
+        let sponsored_gas = self.sponsored_gas(evm);
+        if sponsored_gas != 0 {
+            let gas = exec_result.gas_mut();
+            let unused_sponsored_gas = gas.remaining().min(sponsored_gas);
+            // Caller is reimbursed from a copy that does not include gas returned to the sponsor.
+            let mut caller_gas = *gas;
+            caller_gas.set_spent(gas.spent() + unused_sponsored_gas);
+            self.reimburse_sponsor(evm, unused_sponsored_gas)?;
+            return post_execution::reimburse_caller(evm.ctx(), &mut caller_gas, U256::ZERO)
+                .map_err(From::from);
+        }
         post_execution::reimburse_caller(evm.ctx(), exec_result.gas_mut(), U256::ZERO)
             .map_err(From::from)
     }
 
+    /// Returns the effective gas price of the transaction, the price per gas the caller pays.
+    ///
+    /// It depends on the block basefee for EIP-1559 transactions and is the same price used by
+    /// [`Handler::reimburse_caller`] and [`Handler::reward_beneficiary`], so it can be put in the
+    /// receipt without recomputing it.
+    #[inline]
+    fn effective_gas_price(&self, evm: &Self::Evm) -> U256 {
+        U256::from(post_execution::effective_gas_price(evm.ctx_ref()))
+    }
+
+    /// Returns `true` if the block beneficiary should be rewarded with the transaction fees.
+    ///
+    /// Simulations can return `false` so the beneficiary is not even loaded and stays out of
+    /// the state diff. Fees charged to the caller are not affected.
+    ///
+    /// Default implementation returns `true`.
+    #[inline]
+    fn should_reward_beneficiary(&self, _evm: &Self::Evm) -> bool {
+        true
+    }
+
     /// Transfers transaction fees to the block beneficiary's account.
+    ///
+    /// Skipped if [`Handler::should_reward_beneficiary`] returns `false`.
     #[inline]
     fn reward_beneficiary(
         &self,
         evm: &mut Self::Evm,
         exec_result: &mut <<Self::Evm as EvmTr>::Frame as FrameTr>::FrameResult,
     ) -> Result<(), Self::Error> {
+        if !self.should_reward_beneficiary(evm) {
+            return Ok(());
+        }
         post_execution::reward_beneficiary(evm.ctx(), exec_result.gas_mut()).map_err(From::from)
     }
 
+    /// Returns `true` if the frame halted because it ran out of gas.
+    ///
+    /// Final output of such frame is [`ExecutionResult::Halt`] with an out of gas reason.
+    #[inline]
+    fn is_out_of_gas(result: &FrameResult) -> bool {
+        result.interpreter_result().result.is_out_of_gas()
+    }
+
     /// Processes the final execution output.
     ///
     /// This method, retrieves the final state from the journal, converts internal results to the external output format.
@@ -459,3 +1187,1740 @@ pub trait Handler {
         Err(error)
     }
 }
+
+/// Tracks the gas consumed by the call frames of [`Handler::run_exec_loop`] and checks it
+/// against the [`Handler::contract_gas_budget`] of their targets.
+///
+/// Does nothing unless [`Handler::track_contract_gas`] is enabled.
+#[derive(Debug, Default)]
+pub struct ContractGasTracker {
+    enabled: bool,
+    /// Every frame that did not return yet.
+    frames: Vec<ContractGasFrame>,
+}
+
+#[derive(Debug)]
+struct ContractGasFrame {
+    /// Call target the gas of the frame is accounted to.
+    target: Option<Address>,
+    /// Gas consumed by the child frames.
+    children_gas: u64,
+    /// Gas limit of the call before it was clamped to the budget of the target.
+    unclamped_gas_limit: Option<u64>,
+}
+
+impl ContractGasTracker {
+    /// Creates a tracker for the handler, enabled by [`Handler::track_contract_gas`].
+    pub fn new<H: Handler + ?Sized>(handler: &H, evm: &H::Evm) -> Self {
+        Self {
+            enabled: handler.track_contract_gas(evm),
+            frames: Vec::new(),
+        }
+    }
+
+    /// Checks the gas budget of the call target before the frame is initialized.
+    ///
+    /// Returns the halted result if the budget of the target is exhausted, otherwise the gas
+    /// limit of the call is clamped to the remaining budget. Every frame has to be passed to
+    /// [`ContractGasTracker::exit`] once it produces its result.
+    pub fn enter<H: Handler + ?Sized>(
+        &mut self,
+        handler: &mut H,
+        evm: &mut H::Evm,
+        input: &mut FrameInput,
+    ) -> Option<FrameResult> {
+        if !self.enabled {
+            return None;
+        }
+        let FrameInput::Call(inputs) = input else {
+            self.push(None, None);
+            return None;
+        };
+        match handler.contract_gas_budget(evm, inputs.target_address) {
+            Some(0) => {
+                // Halted frame is not accounted to the target.
+                self.push(None, None);
+                Some(FrameResult::Call(CallOutcome::new(
+                    InterpreterResult::new(
+                        InstructionResult::ContractGasBudgetExhausted,
+                        Bytes::new(),
+                        Gas::new_spent(inputs.gas_limit),
+                    ),
+                    inputs.return_memory_offset.clone(),
+                )))
+            }
+            Some(budget) if budget < inputs.gas_limit => {
+                let gas_limit = core::mem::replace(&mut inputs.gas_limit, budget);
+                self.push(Some(inputs.target_address), Some(gas_limit));
+                None
+            }
+            _ => {
+                self.push(Some(inputs.target_address), None);
+                None
+            }
+        }
+    }
+
+    /// Records the gas consumed by the returning frame itself to its call target, and adds the
+    /// gas of the whole frame to the gas consumed by the children of its parent.
+    ///
+    /// The gas limit clamped by [`ContractGasTracker::enter`] is restored, so the gas above the
+    /// budget is returned to the parent.
+    pub fn exit<H: Handler + ?Sized>(
+        &mut self,
+        handler: &mut H,
+        evm: &mut H::Evm,
+        result: &mut FrameResult,
+    ) {
+        let Some(frame) = self.frames.pop() else {
+            return;
+        };
+        // Halted frame consumes its whole gas limit.
+        let spent = if result.interpreter_result().result.is_ok_or_revert() {
+            result.gas().spent()
+        } else {
+            result.gas().limit()
+        };
+        if let Some(gas_limit) = frame.unclamped_gas_limit {
+            let gas = result.gas_mut();
+            let mut unclamped = Gas::new(gas_limit);
+            unclamped.set_spent(spent);
+            unclamped.record_refund(gas.refunded());
+            *gas = unclamped;
+        }
+        if let Some(target) = frame.target {
+            handler.record_contract_gas(evm, target, spent.saturating_sub(frame.children_gas));
+        }
+        if let Some(parent) = self.frames.last_mut() {
+            parent.children_gas += spent;
+        }
+    }
+
+    fn push(&mut self, target: Option<Address>, unclamped_gas_limit: Option<u64>) {
+        self.frames.push(ContractGasFrame {
+            target,
+            children_gas: 0,
+            unclamped_gas_limit,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mainnet_builder::{MainnetContext, MainnetEvm},
+        EthFrame, ExecuteEvm, MainBuilder, MainContext, MainnetHandler,
+    };
+    use alloy_signer::{Either, SignerSync};
+    use alloy_signer_local::PrivateKeySigner;
+    use bytecode::{
+        opcode::{
+            ADD, CALL, CALLDATALOAD, CHAINID, GAS, LOG0, LOG1, MSTORE, POP, PUSH0, PUSH1, PUSH20,
+            RETURN, REVERT, SLOAD, SSTORE, STOP,
+        },
+        Bytecode,
+    };
+    use context::{
+        result::{EVMError, ExecutionResult, HaltReason, InvalidTransaction},
+        BalanceHook, Context, ContextSetters, TxEnv,
+    };
+    use context_interface::{transaction::Authorization, ContextTr, JournalTr};
+    use core::{cell::Cell, convert::Infallible, marker::PhantomData};
+    use database::{
+        BenchmarkDB, InMemoryDB, BENCH_CALLER, BENCH_CALLER_BALANCE, BENCH_TARGET,
+        BENCH_TARGET_BALANCE, EEADDRESS, FFADDRESS,
+    };
+    use interpreter::{CallOutcome, FrameInput, Gas, InstructionResult, InterpreterResult};
+    use primitives::{address, hardfork::SpecId, Address, Bytes, TxKind, B256, U256};
+    use primitives::{StorageKey, StorageValue};
+    use state::AccountInfo;
+    use std::sync::{Arc, Mutex};
+
+    const SURCHARGE: u64 = 1000;
+    const SPONSORED_GAS: u64 = 100_000;
+    /// Pays the sponsored gas of [`TestHandler`].
+    const SPONSOR: Address = BENCH_TARGET;
+
+    /// Handler with configurable overrides, the defaults behave like [`MainnetHandler`].
+    struct TestHandler<DB> {
+        /// Returned by [`Handler::delegated_execution_surcharge`].
+        surcharge: u64,
+        /// Returned by [`Handler::should_reward_beneficiary`].
+        reward_beneficiary: bool,
+        /// Returned by [`Handler::refunds_enabled`].
+        refunds: bool,
+        /// Returned by [`Handler::sponsored_gas`].
+        sponsored_gas: u64,
+        /// Gas charged to the sponsor with [`Handler::deduct_sponsor`].
+        sponsor_deducted: Cell<u64>,
+        /// Gas returned to the sponsor with [`Handler::reimburse_sponsor`].
+        sponsor_reimbursed: Cell<u64>,
+        /// Gas floor of transactions without calldata, see [`Handler::gas_floor_override`].
+        flat_floor: Option<u64>,
+        /// Requires only the fee from the caller, see [`Handler::required_caller_balance`].
+        fee_only_balance: bool,
+        /// Address and remaining gas budget, see [`Handler::contract_gas_budget`].
+        contract_budget: Option<(Address, u64)>,
+        /// Gas reported with [`Handler::record_contract_gas`].
+        contract_gas: Vec<(Address, u64)>,
+        /// Skips [`Handler::validate_no_sender_code`].
+        allow_sender_code: bool,
+        /// Returned by [`Handler::defer_deduction`].
+        defer_deduction: bool,
+        /// Returned by [`Handler::post_execution_order`].
+        post_execution_order: Option<[PostStep; 4]>,
+        /// Builds the call tree of the transaction in [`TestHandler::trace_root`].
+        trace: bool,
+        trace_stack: Vec<TraceNode>,
+        trace_root: Option<TraceNode>,
+        /// [`Handler::changed_state`] before the beneficiary is rewarded.
+        changed_state: Cell<Option<bool>>,
+        _phantom: PhantomData<DB>,
+    }
+
+    impl<DB> Default for TestHandler<DB> {
+        fn default() -> Self {
+            Self {
+                surcharge: 0,
+                reward_beneficiary: true,
+                refunds: true,
+                sponsored_gas: 0,
+                sponsor_deducted: Cell::new(0),
+                sponsor_reimbursed: Cell::new(0),
+                flat_floor: None,
+                fee_only_balance: false,
+                contract_budget: None,
+                contract_gas: Vec::new(),
+                allow_sender_code: false,
+                defer_deduction: false,
+                post_execution_order: None,
+                trace: false,
+                trace_stack: Vec::new(),
+                trace_root: None,
+                changed_state: Cell::new(None),
+                _phantom: PhantomData,
+            }
+        }
+    }
+
+    impl<DB: Database<Error = Infallible>> Handler for TestHandler<DB> {
+        type Evm = MainnetEvm<MainnetContext<DB>>;
+        type Error = EVMError<Infallible>;
+        type HaltReason = HaltReason;
+
+        fn delegated_execution_surcharge(&self, _evm: &Self::Evm) -> u64 {
+            self.surcharge
+        }
+
+        fn should_reward_beneficiary(&self, _evm: &Self::Evm) -> bool {
+            self.reward_beneficiary
+        }
+
+        fn refunds_enabled(&self, _evm: &Self::Evm) -> bool {
+            self.refunds
+        }
+
+        fn sponsored_gas(&self, _evm: &Self::Evm) -> u64 {
+            self.sponsored_gas
+        }
+
+        fn deduct_sponsor(
+            &self,
+            evm: &mut Self::Evm,
+            sponsored_gas: u64,
+        ) -> Result<(), Self::Error> {
+            self.sponsor_deducted.set(sponsored_gas);
+            let fee = self.effective_gas_price(evm) * U256::from(sponsored_gas);
+            let journal = evm.ctx().journal_mut();
+            let sponsor = journal.load_account(SPONSOR)?.data;
+            let old_balance = sponsor.info.balance;
+            sponsor.mark_touch();
+            sponsor.info.balance = old_balance - fee;
+            journal.caller_accounting_journal_entry(SPONSOR, old_balance, false);
+            Ok(())
+        }
+
+        fn reimburse_sponsor(
+            &self,
+            evm: &mut Self::Evm,
+            unused_gas: u64,
+        ) -> Result<(), Self::Error> {
+            self.sponsor_reimbursed.set(unused_gas);
+            let fee = self.effective_gas_price(evm) * U256::from(unused_gas);
+            evm.ctx().journal_mut().balance_incr(SPONSOR, fee)?;
+            Ok(())
+        }
+
+        fn gas_floor_override(&self, evm: &Self::Evm, computed: u64) -> u64 {
+            match self.flat_floor {
+                Some(floor) if evm.ctx.tx.data.is_empty() => floor,
+                _ => computed,
+            }
+        }
+
+        fn required_caller_balance(&self, evm: &Self::Evm) -> U256 {
+            let tx = &evm.ctx.tx;
+            if !self.fee_only_balance {
+                return tx.max_balance_spending().unwrap_or(U256::MAX);
+            }
+            U256::from(tx.gas_limit) * U256::from(tx.gas_price)
+        }
+
+        fn track_contract_gas(&self, _evm: &Self::Evm) -> bool {
+            self.contract_budget.is_some()
+        }
+
+        fn contract_gas_budget(&mut self, _evm: &mut Self::Evm, address: Address) -> Option<u64> {
+            self.contract_budget
+                .and_then(|(target, budget)| (address == target).then_some(budget))
+        }
+
+        fn record_contract_gas(&mut self, _evm: &mut Self::Evm, address: Address, gas_used: u64) {
+            if let Some((target, budget)) = &mut self.contract_budget {
+                if address == *target {
+                    *budget = budget.saturating_sub(gas_used);
+                }
+            }
+            self.contract_gas.push((address, gas_used));
+        }
+
+        fn validate_no_sender_code(&self, evm: &mut Self::Evm) -> Result<(), Self::Error> {
+            if self.allow_sender_code {
+                return Ok(());
+            }
+            crate::pre_execution::validate_no_sender_code(&mut evm.ctx)
+        }
+
+        fn defer_deduction(&self) -> bool {
+            self.defer_deduction
+        }
+
+        fn post_execution_order(&self) -> [PostStep; 4] {
+            self.post_execution_order.unwrap_or([
+                PostStep::Refund,
+                PostStep::GasFloor,
+                PostStep::ReimburseCaller,
+                PostStep::RewardBeneficiary,
+            ])
+        }
+
+        fn on_frame_enter(&mut self, depth: usize, input: &FrameInput) {
+            if !self.trace {
+                return;
+            }
+            assert_eq!(depth, self.trace_stack.len());
+            let FrameInput::Call(inputs) = input else {
+                panic!("unexpected create frame");
+            };
+            self.trace_stack.push(TraceNode {
+                target: inputs.target_address,
+                result: None,
+                children: Vec::new(),
+            });
+        }
+
+        fn on_frame_result(&mut self, depth: usize, result: &FrameResult) {
+            if !self.trace {
+                return;
+            }
+            let mut node = self.trace_stack.pop().expect("result without frame");
+            assert_eq!(depth, self.trace_stack.len());
+            node.result = Some(result.interpreter_result().result);
+            match self.trace_stack.last_mut() {
+                Some(parent) => parent.children.push(node),
+                None => self.trace_root = Some(node),
+            }
+        }
+
+        fn reward_beneficiary(
+            &self,
+            evm: &mut Self::Evm,
+            exec_result: &mut FrameResult,
+        ) -> Result<(), Self::Error> {
+            self.changed_state.set(Some(self.changed_state(evm)));
+            if !self.reward_beneficiary {
+                return Ok(());
+            }
+            crate::post_execution::reward_beneficiary(&mut evm.ctx, exec_result.gas_mut())
+                .map_err(From::from)
+        }
+    }
+
+    #[test]
+    fn delegated_execution_surcharge() {
+        let signer = PrivateKeySigner::random();
+        let auth = Authorization {
+            chain_id: U256::ZERO,
+            nonce: 0,
+            address: FFADDRESS,
+        };
+        let signature = signer.sign_hash_sync(&auth.signature_hash()).unwrap();
+        let auth = auth.into_signed(signature);
+
+        let bytecode = Bytecode::new_legacy([PUSH1, 0x01, PUSH1, 0x01, SSTORE].into());
+        let evm = || {
+            Context::mainnet()
+                .modify_cfg_chained(|cfg| cfg.spec = SpecId::PRAGUE)
+                .with_db(BenchmarkDB::new_bytecode(bytecode.clone()))
+                .build_mainnet()
+        };
+        let gas_used = |tx: TxEnv| {
+            let mainnet_gas = evm().transact(tx.clone()).unwrap().result.gas_used();
+            let mut evm = evm();
+            evm.ctx.set_tx(tx);
+            let surcharge_gas = TestHandler {
+                surcharge: SURCHARGE,
+                ..Default::default()
+            }
+            .run(&mut evm)
+            .unwrap()
+            .gas_used();
+            (mainnet_gas, surcharge_gas)
+        };
+
+        // Call to the delegated account pays the surcharge.
+        let delegated_tx = TxEnv::builder()
+            .gas_limit(100_000)
+            .authorization_list(vec![Either::Left(auth)])
+            .caller(EEADDRESS)
+            .kind(TxKind::Call(signer.address()))
+            .build()
+            .unwrap();
+        let (mainnet_gas, surcharge_gas) = gas_used(delegated_tx);
+        assert_eq!(surcharge_gas, mainnet_gas + SURCHARGE);
+
+        // Call to the account without delegation is not affected.
+        let plain_tx = TxEnv::builder()
+            .gas_limit(100_000)
+            .caller(EEADDRESS)
+            .kind(TxKind::Call(FFADDRESS))
+            .build()
+            .unwrap();
+        let (mainnet_gas, surcharge_gas) = gas_used(plain_tx);
+        assert_eq!(surcharge_gas, mainnet_gas);
+    }
+
+    #[test]
+    fn skip_beneficiary_reward() {
+        let beneficiary = address!("0x0000000000000000000000000000000000c0ffee");
+        let evm = || {
+            Context::mainnet()
+                .modify_block_chained(|block| block.beneficiary = beneficiary)
+                .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+                .build_mainnet()
+        };
+        let tx = TxEnv::builder()
+            .caller(BENCH_CALLER)
+            .kind(TxKind::Call(BENCH_TARGET))
+            .gas_price(10)
+            .build()
+            .unwrap();
+
+        let mainnet = evm().transact(tx.clone()).unwrap();
+        assert!(mainnet.state.contains_key(&beneficiary));
+
+        let mut evm = evm();
+        evm.ctx.set_tx(tx);
+        let result = TestHandler {
+            reward_beneficiary: false,
+            ..Default::default()
+        }
+        .run(&mut evm)
+        .unwrap();
+        let state = evm.finalize();
+
+        // Beneficiary is not loaded but the caller is charged the same.
+        assert!(!state.contains_key(&beneficiary));
+        assert_eq!(result.gas_used(), mainnet.result.gas_used());
+        assert_eq!(
+            state[&BENCH_CALLER].info.balance,
+            mainnet.state[&BENCH_CALLER].info.balance
+        );
+    }
+
+    #[test]
+    fn sponsored_gas_without_sponsor_hooks() {
+        /// Returns sponsored gas but does not charge anyone for it.
+        struct UnpaidSponsorHandler;
+
+        impl Handler for UnpaidSponsorHandler {
+            type Evm = MainnetEvm<MainnetContext<BenchmarkDB>>;
+            type Error = EVMError<Infallible>;
+            type HaltReason = HaltReason;
+
+            fn sponsored_gas(&self, _evm: &Self::Evm) -> u64 {
+                SPONSORED_GAS
+            }
+        }
+
+        let bytecode = Bytecode::new_legacy([PUSH1, 0x01, PUSH1, 0x01, SSTORE].into());
+        let mut evm = Context::mainnet()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .build_mainnet();
+        evm.ctx.set_tx(
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Call(BENCH_TARGET))
+                .gas_limit(30_000)
+                .gas_price(1)
+                .build()
+                .unwrap(),
+        );
+        assert!(matches!(
+            UnpaidSponsorHandler.run(&mut evm),
+            Err(EVMError::Custom(_))
+        ));
+        // Beneficiary is not rewarded.
+        assert!(!evm.finalize().contains_key(&Address::ZERO));
+    }
+
+    #[test]
+    fn disabled_refunds() {
+        // Sets and clears the slot, refunding the storage cost.
+        let bytecode = Bytecode::new_legacy(
+            [PUSH1, 0x01, PUSH1, 0x01, SSTORE, PUSH0, PUSH1, 0x01, SSTORE].into(),
+        );
+        let evm = || {
+            Context::mainnet()
+                .modify_cfg_chained(|cfg| cfg.spec = SpecId::PRAGUE)
+                .with_db(BenchmarkDB::new_bytecode(bytecode.clone()))
+                .build_mainnet()
+        };
+        let gas = |result: &ExecutionResult| match result {
+            ExecutionResult::Success {
+                gas_used,
+                gas_refunded,
+                ..
+            } => (*gas_used, *gas_refunded),
+            result => panic!("expected success, got {result:?}"),
+        };
+        let assert_no_refund = |tx: TxEnv| {
+            let (mainnet_used, mainnet_refunded) = gas(&evm().transact(tx.clone()).unwrap().result);
+            assert!(mainnet_refunded > 0);
+
+            let mut evm = evm();
+            evm.ctx.set_tx(tx);
+            let (used, refunded) = gas(&TestHandler {
+                refunds: false,
+                ..Default::default()
+            }
+            .run(&mut evm)
+            .unwrap());
+            assert_eq!(refunded, 0);
+            assert_eq!(used, mainnet_used + mainnet_refunded);
+        };
+
+        // SSTORE refund.
+        assert_no_refund(
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Call(BENCH_TARGET))
+                .gas_limit(100_000)
+                .build()
+                .unwrap(),
+        );
+
+        // EIP-7702 refund, second authorization is applied to the existing account.
+        let signer = PrivateKeySigner::random();
+        let auth_list = (0..2)
+            .map(|nonce| {
+                let auth = Authorization {
+                    chain_id: U256::ZERO,
+                    nonce,
+                    address: FFADDRESS,
+                };
+                let signature = signer.sign_hash_sync(&auth.signature_hash()).unwrap();
+                Either::Left(auth.into_signed(signature))
+            })
+            .collect();
+        assert_no_refund(
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Call(FFADDRESS))
+                .gas_limit(100_000)
+                .authorization_list(auth_list)
+                .build()
+                .unwrap(),
+        );
+    }
+
+    #[test]
+    fn sponsored_gas() {
+        let bytecode = Bytecode::new_legacy([PUSH1, 0x01, PUSH1, 0x01, SSTORE].into());
+        let evm = || {
+            Context::mainnet()
+                .with_db(BenchmarkDB::new_bytecode(bytecode.clone()))
+                .build_mainnet()
+        };
+        // Caller gas does not cover the SSTORE.
+        let tx = TxEnv::builder()
+            .caller(BENCH_CALLER)
+            .kind(TxKind::Call(BENCH_TARGET))
+            .gas_limit(30_000)
+            .gas_price(1)
+            .build()
+            .unwrap();
+
+        let mainnet = evm().transact(tx.clone()).unwrap();
+        assert!(mainnet.result.is_out_of_gas());
+
+        let mut evm = evm();
+        evm.ctx.set_tx(tx);
+        let mut handler = TestHandler {
+            sponsored_gas: SPONSORED_GAS,
+            ..Default::default()
+        };
+        let result = handler.run(&mut evm).unwrap();
+        let state = evm.finalize();
+        assert!(result.is_success());
+        assert!(result.gas_used() > 30_000);
+
+        // Sponsor gets back what was not used, caller pays its whole gas limit.
+        assert_eq!(handler.sponsor_deducted.get(), SPONSORED_GAS);
+        assert_eq!(
+            handler.sponsor_reimbursed.get(),
+            30_000 + SPONSORED_GAS - result.gas_used()
+        );
+        assert_eq!(
+            state[&BENCH_CALLER].info.balance,
+            mainnet.state[&BENCH_CALLER].info.balance
+        );
+
+        // Beneficiary gets exactly what the caller and the sponsor paid.
+        let paid = |address: Address| {
+            let balance = if address == BENCH_CALLER {
+                BENCH_CALLER_BALANCE
+            } else {
+                BENCH_TARGET_BALANCE
+            };
+            balance - state[&address].info.balance
+        };
+        assert_eq!(
+            state[&Address::ZERO].info.balance,
+            paid(BENCH_CALLER) + paid(SPONSOR)
+        );
+        assert_eq!(paid(SPONSOR), U256::from(result.gas_used() - 30_000));
+    }
+
+    #[test]
+    fn out_of_gas_classification() {
+        let run = |gas_limit| {
+            let bytecode = Bytecode::new_legacy([PUSH1, 0x01, PUSH1, 0x01, SSTORE].into());
+            Context::mainnet()
+                .with_db(BenchmarkDB::new_bytecode(bytecode))
+                .build_mainnet()
+                .transact(
+                    TxEnv::builder()
+                        .caller(BENCH_CALLER)
+                        .kind(TxKind::Call(BENCH_TARGET))
+                        .gas_limit(gas_limit)
+                        .build()
+                        .unwrap(),
+                )
+                .unwrap()
+                .result
+        };
+
+        // Gas limit covers the intrinsic cost but not the SSTORE.
+        let result = run(21_100);
+        assert!(result.is_halt());
+        assert!(result.is_out_of_gas());
+        assert!(!run(100_000).is_out_of_gas());
+
+        let frame_result = |result| {
+            FrameResult::Call(CallOutcome::new(
+                InterpreterResult::new(result, Bytes::new(), Gas::new(0)),
+                0..0,
+            ))
+        };
+        type Mainnet =
+            MainnetHandler<MainnetEvm<MainnetContext<BenchmarkDB>>, EVMError<Infallible>, EthFrame>;
+        assert!(Mainnet::is_out_of_gas(&frame_result(
+            InstructionResult::OutOfGas
+        )));
+        assert!(Mainnet::is_out_of_gas(&frame_result(
+            InstructionResult::MemoryOOG
+        )));
+        assert!(!Mainnet::is_out_of_gas(&frame_result(
+            InstructionResult::StackUnderflow
+        )));
+        assert!(!Mainnet::is_out_of_gas(&frame_result(
+            InstructionResult::Revert
+        )));
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct TraceNode {
+        target: Address,
+        result: Option<InstructionResult>,
+        children: Vec<TraceNode>,
+    }
+
+    impl TraceNode {
+        fn new(target: Address, children: Vec<TraceNode>) -> Self {
+            Self {
+                target,
+                result: Some(InstructionResult::Stop),
+                children,
+            }
+        }
+    }
+
+    #[test]
+    fn frame_trace_tree() {
+        const A: Address = address!("0x1000000000000000000000000000000000000000");
+        const B: Address = address!("0x2000000000000000000000000000000000000000");
+        const C: Address = address!("0x3000000000000000000000000000000000000000");
+        const IDENTITY: Address = address!("0x0000000000000000000000000000000000000004");
+
+        // Calls each target with no value and empty input, then stops.
+        let calls = |targets: &[Address]| {
+            let mut code = Vec::new();
+            for target in targets {
+                code.extend([PUSH0, PUSH0, PUSH0, PUSH0, PUSH0, PUSH20]);
+                code.extend_from_slice(target.as_slice());
+                code.extend([GAS, CALL, POP]);
+            }
+            code.push(STOP);
+            AccountInfo::from_bytecode(Bytecode::new_legacy(code.into()))
+        };
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(A, calls(&[B, C]));
+        db.insert_account_info(B, calls(&[C]));
+        db.insert_account_info(C, calls(&[IDENTITY]));
+
+        let mut evm = Context::mainnet().with_db(db).build_mainnet();
+        evm.ctx.set_tx(
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Call(A))
+                .gas_limit(1_000_000)
+                .build()
+                .unwrap(),
+        );
+        let mut handler = TestHandler {
+            trace: true,
+            ..Default::default()
+        };
+        assert!(handler.run(&mut evm).unwrap().is_success());
+
+        // Precompile frames return without being pushed to the frame stack.
+        let precompile = || TraceNode {
+            target: IDENTITY,
+            result: Some(InstructionResult::Return),
+            children: Vec::new(),
+        };
+        let expected = TraceNode::new(
+            A,
+            vec![
+                TraceNode::new(B, vec![TraceNode::new(C, vec![precompile()])]),
+                TraceNode::new(C, vec![precompile()]),
+            ],
+        );
+        assert!(handler.trace_stack.is_empty());
+        assert_eq!(handler.trace_root, Some(expected));
+    }
+
+    #[test]
+    fn effective_gas_price_of_eip1559_tx() {
+        let run = |max_fee: u128, priority_fee: u128| {
+            let bytecode = Bytecode::new_legacy([PUSH1, 0x01, PUSH1, 0x01, SSTORE].into());
+            let mut evm = Context::mainnet()
+                .modify_block_chained(|block| block.basefee = 10)
+                .with_db(BenchmarkDB::new_bytecode(bytecode))
+                .build_mainnet();
+            evm.ctx.set_tx(
+                TxEnv::builder()
+                    .caller(BENCH_CALLER)
+                    .kind(TxKind::Call(BENCH_TARGET))
+                    .gas_limit(100_000)
+                    .gas_price(max_fee)
+                    .gas_priority_fee(Some(priority_fee))
+                    .build()
+                    .unwrap(),
+            );
+            let mut handler = MainnetHandler::<_, EVMError<Infallible>, EthFrame>::default();
+            let price = handler.effective_gas_price(&evm);
+            let result = handler.run(&mut evm).unwrap();
+            let state = evm.finalize();
+            let paid = BENCH_CALLER_BALANCE - state[&BENCH_CALLER].info.balance;
+            (price, paid, result.gas_used())
+        };
+
+        // Priority fee fits under the max fee, basefee plus priority fee is paid.
+        let (price, paid, gas_used) = run(30, 5);
+        assert_eq!(price, U256::from(15));
+        assert_eq!(paid, price * U256::from(gas_used));
+
+        // Capped by the max fee.
+        let (price, paid, gas_used) = run(12, 5);
+        assert_eq!(price, U256::from(12));
+        assert_eq!(paid, price * U256::from(gas_used));
+    }
+
+    #[test]
+    fn gas_floor_override_of_value_transfer() {
+        let run = |input: Bytes| {
+            let mut evm = Context::mainnet()
+                .modify_cfg_chained(|cfg| cfg.spec = SpecId::PRAGUE)
+                .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+                .build_mainnet();
+            evm.ctx.set_tx(
+                TxEnv::builder()
+                    .caller(BENCH_CALLER)
+                    .kind(TxKind::Call(BENCH_TARGET))
+                    .value(U256::from(1))
+                    .data(input)
+                    .gas_price(1)
+                    .gas_limit(100_000)
+                    .build()
+                    .unwrap(),
+            );
+            let result = TestHandler {
+                flat_floor: Some(30_000),
+                ..Default::default()
+            }
+            .run(&mut evm)
+            .unwrap();
+            let state = evm.finalize();
+            let paid = BENCH_CALLER_BALANCE - state[&BENCH_CALLER].info.balance - U256::from(1);
+            (result.gas_used(), paid)
+        };
+
+        // Flat floor is spent and charged to the caller.
+        let (gas_used, paid) = run(Bytes::new());
+        assert_eq!(gas_used, 30_000);
+        assert_eq!(paid, U256::from(30_000));
+
+        // Calldata transaction keeps the computed floor of 21000 + 10 * 4.
+        let (gas_used, paid) = run(Bytes::from_static(&[1]));
+        assert_eq!(gas_used, 21_040);
+        assert_eq!(paid, U256::from(21_040));
+    }
+
+    #[test]
+    fn required_caller_balance_of_sponsored_value() {
+        const CALLER: Address = address!("0x1000000000000000000000000000000000000000");
+        let value = U256::from(1_000);
+        let evm = || {
+            let mut db = InMemoryDB::default();
+            db.insert_account_info(CALLER, AccountInfo::from_balance(U256::from(100_000)));
+            Context::mainnet().with_db(db).build_mainnet()
+        };
+        let tx = TxEnv::builder()
+            .caller(CALLER)
+            .kind(TxKind::Call(BENCH_TARGET))
+            .value(value)
+            .gas_price(1)
+            .gas_limit(100_000)
+            .build()
+            .unwrap();
+
+        // Caller balance covers only the fee.
+        assert!(matches!(
+            evm().transact(tx.clone()),
+            Err(EVMError::Transaction(
+                InvalidTransaction::LackOfFundForMaxFee { .. }
+            ))
+        ));
+
+        let mut evm = evm();
+        evm.ctx.set_tx(tx);
+        let mut handler = TestHandler {
+            fee_only_balance: true,
+            ..Default::default()
+        };
+        let init_and_floor_gas = handler.validate(&mut evm).unwrap();
+        let eip7702_refund = handler.pre_execution(&mut evm).unwrap() as i64;
+        // Sponsor credits the value before it is transferred by the call.
+        evm.ctx.journal_mut().balance_incr(CALLER, value).unwrap();
+        let mut exec_result = handler.execution(&mut evm, &init_and_floor_gas).unwrap();
+        handler
+            .post_execution(
+                &mut evm,
+                &mut exec_result,
+                init_and_floor_gas,
+                eip7702_refund,
+            )
+            .unwrap();
+        let result = handler.execution_result(&mut evm, exec_result).unwrap();
+        let state = evm.finalize();
+
+        // Caller paid only the used gas, unused fee was reimbursed.
+        assert!(result.is_success());
+        assert_eq!(result.gas_used(), 21_000);
+        assert_eq!(state[&CALLER].info.balance, U256::from(100_000 - 21_000));
+        assert_eq!(state[&BENCH_TARGET].info.balance, value);
+    }
+
+    #[test]
+    fn run_gas_free_ignores_gas_limit() {
+        let evm = |bytecode: Bytecode| {
+            Context::mainnet()
+                .with_db(BenchmarkDB::new_bytecode(bytecode))
+                .build_mainnet()
+        };
+        // Gas limit covers only the intrinsic gas.
+        let tx = TxEnv::builder()
+            .caller(BENCH_CALLER)
+            .kind(TxKind::Call(BENCH_TARGET))
+            .gas_limit(21_000)
+            .build()
+            .unwrap();
+        let sstore = Bytecode::new_legacy([PUSH1, 0x01, PUSH1, 0x01, SSTORE].into());
+
+        let mainnet = evm(sstore.clone()).transact(tx.clone()).unwrap();
+        assert!(mainnet.result.is_out_of_gas());
+
+        let mut handler = MainnetHandler::<_, EVMError<Infallible>, EthFrame>::default();
+        let mut evm_sstore = evm(sstore);
+        evm_sstore.ctx.set_tx(tx.clone());
+        let result = handler.run_gas_free(&mut evm_sstore).unwrap();
+        let state = evm_sstore.finalize();
+        assert!(result.is_success());
+        assert_eq!(
+            state[&BENCH_TARGET].storage[&StorageKey::from(1)].present_value,
+            StorageValue::from(1)
+        );
+        // Caller is neither charged nor has its nonce bumped.
+        assert!(!state.contains_key(&BENCH_CALLER));
+
+        // Other halts still occur.
+        let mut evm_invalid = evm(Bytecode::new_legacy([0xFE].into()));
+        evm_invalid.ctx.set_tx(tx);
+        let result = handler.run_gas_free(&mut evm_invalid).unwrap();
+        assert!(matches!(
+            result,
+            ExecutionResult::Halt {
+                reason: HaltReason::InvalidFEOpcode,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn contract_gas_budget_halts_exhausted_contract() {
+        const CALLER: Address = address!("0x1000000000000000000000000000000000000000");
+        const TARGET: Address = address!("0x2000000000000000000000000000000000000000");
+
+        // Calls the target twice, storing the success flags in slots 0 and 1.
+        let mut code = Vec::new();
+        for slot in [0, 1] {
+            code.extend([PUSH0, PUSH0, PUSH0, PUSH0, PUSH0, PUSH20]);
+            code.extend_from_slice(TARGET.as_slice());
+            code.extend([PUSH1, 0xFF, CALL, PUSH1, slot, SSTORE]);
+        }
+        code.push(STOP);
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            CALLER,
+            AccountInfo::from_bytecode(Bytecode::new_legacy(code.into())),
+        );
+        db.insert_account_info(
+            TARGET,
+            AccountInfo::from_bytecode(Bytecode::new_legacy([PUSH0, POP, STOP].into())),
+        );
+        let run = |contract_budget| {
+            let mut evm = Context::mainnet().with_db(db.clone()).build_mainnet();
+            evm.ctx.set_tx(
+                TxEnv::builder()
+                    .caller(BENCH_CALLER)
+                    .kind(TxKind::Call(CALLER))
+                    .gas_limit(1_000_000)
+                    .build()
+                    .unwrap(),
+            );
+            let mut handler = TestHandler {
+                contract_budget,
+                ..Default::default()
+            };
+            let result = handler.run(&mut evm).unwrap();
+            assert!(result.is_success());
+            let storage = evm.finalize().remove(&CALLER).unwrap().storage;
+            let flags = [0, 1].map(|slot| storage[&StorageKey::from(slot)].present_value);
+            (flags, handler, result.gas_used())
+        };
+
+        // First call spends the whole budget, second one is halted without being recorded.
+        let (flags, handler, _) = run(Some((TARGET, 4)));
+        assert_eq!(flags, [StorageValue::from(1), StorageValue::ZERO]);
+        assert_eq!(handler.contract_budget, Some((TARGET, 0)));
+        assert_eq!(handler.contract_gas.len(), 2);
+        assert_eq!(handler.contract_gas[0], (TARGET, 4));
+        assert_eq!(handler.contract_gas[1].0, CALLER);
+
+        // First call is clamped to the budget and runs out of gas.
+        let (flags, handler, _) = run(Some((TARGET, 3)));
+        assert_eq!(flags, [StorageValue::ZERO, StorageValue::ZERO]);
+        assert_eq!(handler.contract_budget, Some((TARGET, 0)));
+        assert_eq!(handler.contract_gas[0], (TARGET, 3));
+
+        // Gas above the clamped limit is returned to the caller.
+        let (flags, _, gas_used) = run(Some((TARGET, 8)));
+        assert_eq!(flags, [StorageValue::from(1), StorageValue::from(1)]);
+        assert_eq!(gas_used, run(Some((TARGET, 1_000))).2);
+
+        // Nothing is tracked without a budget.
+        let (flags, handler, _) = run(None);
+        assert_eq!(flags, [StorageValue::from(1), StorageValue::from(1)]);
+        assert!(handler.contract_gas.is_empty());
+    }
+
+    #[test]
+    fn validate_no_sender_code_of_contract_sender() {
+        const SENDER: Address = address!("0x1000000000000000000000000000000000000000");
+        let evm = |code: Bytecode| {
+            let mut db = InMemoryDB::default();
+            db.insert_account_info(
+                SENDER,
+                AccountInfo::from_balance(U256::from(1_000_000)).with_code(code),
+            );
+            let mut evm = Context::mainnet().with_db(db).build_mainnet();
+            evm.ctx.set_tx(
+                TxEnv::builder()
+                    .caller(SENDER)
+                    .kind(TxKind::Call(BENCH_TARGET))
+                    .gas_limit(21_000)
+                    .build()
+                    .unwrap(),
+            );
+            evm
+        };
+        let contract = Bytecode::new_legacy([PUSH0, POP, STOP].into());
+        let mut mainnet = MainnetHandler::<_, EVMError<Infallible>, EthFrame>::default();
+
+        assert!(matches!(
+            mainnet.run(&mut evm(contract.clone())),
+            Err(EVMError::Transaction(
+                InvalidTransaction::RejectCallerWithCode
+            ))
+        ));
+        assert!(TestHandler {
+            allow_sender_code: true,
+            ..Default::default()
+        }
+        .run(&mut evm(contract))
+        .unwrap()
+        .is_success());
+
+        // EIP-7702 delegated senders are allowed by default.
+        let delegated = Bytecode::new_eip7702(BENCH_TARGET);
+        assert!(mainnet.run(&mut evm(delegated)).unwrap().is_success());
+
+        // The combined free function still checks the sender code.
+        let mut evm = evm(Bytecode::new_legacy([STOP].into()));
+        assert!(matches!(
+            crate::pre_execution::validate_against_state_and_deduct_caller::<_, EVMError<Infallible>>(
+                &mut evm.ctx
+            ),
+            Err(EVMError::Transaction(
+                InvalidTransaction::RejectCallerWithCode
+            ))
+        ));
+    }
+
+    #[test]
+    fn defer_deduction_of_validated_tx() {
+        let bytecode = Bytecode::new_legacy([PUSH1, 0x01, PUSH1, 0x01, SSTORE].into());
+        let mut evm = Context::mainnet()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .build_mainnet();
+        evm.ctx.set_tx(
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Call(BENCH_TARGET))
+                .gas_limit(100_000)
+                .gas_price(1)
+                .build()
+                .unwrap(),
+        );
+        let mut handler = TestHandler {
+            defer_deduction: true,
+            ..Default::default()
+        };
+        let init_and_floor_gas = handler.validate(&mut evm).unwrap();
+        let eip7702_refund = handler.pre_execution(&mut evm).unwrap() as i64;
+
+        // Caller nonce is bumped but the fee is not deducted yet.
+        let caller = evm.ctx.journal_mut().load_account(BENCH_CALLER).unwrap();
+        assert_eq!(caller.info.balance, BENCH_CALLER_BALANCE);
+        assert_eq!(caller.info.nonce, 1);
+
+        let mut exec_result = handler.execution(&mut evm, &init_and_floor_gas).unwrap();
+        handler
+            .post_execution(
+                &mut evm,
+                &mut exec_result,
+                init_and_floor_gas,
+                eip7702_refund,
+            )
+            .unwrap();
+        let result = handler.execution_result(&mut evm, exec_result).unwrap();
+        let state = evm.finalize();
+
+        // Unused gas is reimbursed as with the immediate deduction.
+        assert!(result.is_success());
+        let caller = &state[&BENCH_CALLER].info;
+        assert_eq!(
+            caller.balance,
+            BENCH_CALLER_BALANCE - U256::from(result.gas_used())
+        );
+        assert_eq!(caller.nonce, 1);
+    }
+
+    #[test]
+    fn defer_deduction_of_self_sponsored_eip7702_tx() {
+        let signer = PrivateKeySigner::random();
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            signer.address(),
+            AccountInfo::from_balance(U256::from(1_000_000_000u64)),
+        );
+        let mut evm = Context::mainnet()
+            .modify_cfg_chained(|cfg| cfg.spec = SpecId::PRAGUE)
+            .with_db(db)
+            .build_mainnet();
+
+        // The authorization is signed for the nonce after the transaction nonce is bumped.
+        let auth = Authorization {
+            chain_id: U256::ZERO,
+            nonce: 1,
+            address: FFADDRESS,
+        };
+        let signature = signer.sign_hash_sync(&auth.signature_hash()).unwrap();
+        evm.ctx.set_tx(
+            TxEnv::builder()
+                .caller(signer.address())
+                .kind(TxKind::Call(signer.address()))
+                .authorization_list(vec![Either::Left(auth.into_signed(signature))])
+                .gas_limit(100_000)
+                .gas_price(1)
+                .build()
+                .unwrap(),
+        );
+
+        let result = TestHandler {
+            defer_deduction: true,
+            ..Default::default()
+        }
+        .run(&mut evm)
+        .unwrap();
+        let state = evm.finalize();
+
+        assert!(result.is_success());
+        let caller = &state[&signer.address()].info;
+        assert_eq!(caller.code, Some(Bytecode::new_eip7702(FFADDRESS)));
+        assert_eq!(caller.nonce, 2);
+    }
+
+    #[test]
+    fn call_direct_of_view_function() {
+        const SENDER: Address = address!("0x1000000000000000000000000000000000000000");
+        const TARGET: Address = address!("0x2000000000000000000000000000000000000000");
+
+        // Returns the value of slot 0.
+        let code = [PUSH0, SLOAD, PUSH0, MSTORE, PUSH1, 32, PUSH0, RETURN];
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            TARGET,
+            AccountInfo::from_bytecode(Bytecode::new_legacy(code.into())),
+        );
+        db.insert_account_storage(TARGET, StorageKey::ZERO, StorageValue::from(42))
+            .unwrap();
+        let mut evm = Context::mainnet().with_db(db).build_mainnet();
+
+        let mut handler = MainnetHandler::<_, EVMError<Infallible>, EthFrame>::default();
+        let result = handler
+            .call_direct(&mut evm, SENDER, TARGET, Bytes::new(), U256::ZERO, 100_000)
+            .unwrap();
+        assert!(result.instruction_result().is_ok());
+        assert_eq!(
+            result.output().into_data(),
+            Bytes::from(U256::from(42).to_be_bytes_vec())
+        );
+
+        // Sender does not exist but is loaded warm, changes are left in the journal.
+        let sender = evm.ctx.journal_mut().load_account(SENDER).unwrap();
+        assert!(!sender.is_cold);
+        evm.ctx.journal_mut().discard_tx();
+    }
+
+    #[test]
+    fn post_execution_order_rewards_first() {
+        const BENEFICIARY: Address = address!("0x3000000000000000000000000000000000000000");
+        let mut evm = Context::mainnet()
+            .modify_block_chained(|block| block.beneficiary = BENEFICIARY)
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+            .build_mainnet();
+        evm.ctx.set_tx(
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Call(BENCH_TARGET))
+                .gas_limit(100_000)
+                .gas_price(1)
+                .build()
+                .unwrap(),
+        );
+        let credited = Arc::new(Mutex::new(Vec::new()));
+        let record = credited.clone();
+        evm.ctx.journal_mut().on_balance_change = BalanceHook::new(move |address, old, new| {
+            if new > old {
+                record.lock().unwrap().push(address);
+            }
+        });
+
+        let result = TestHandler {
+            post_execution_order: Some([
+                PostStep::Refund,
+                PostStep::GasFloor,
+                PostStep::RewardBeneficiary,
+                PostStep::ReimburseCaller,
+            ]),
+            ..Default::default()
+        }
+        .run(&mut evm)
+        .unwrap();
+        let state = evm.finalize();
+
+        // Same balances as with the default order.
+        assert!(result.is_success());
+        assert_eq!(*credited.lock().unwrap(), [BENEFICIARY, BENCH_CALLER]);
+        assert_eq!(
+            state[&BENCH_CALLER].info.balance,
+            BENCH_CALLER_BALANCE - U256::from(21_000)
+        );
+        assert_eq!(state[&BENEFICIARY].info.balance, U256::from(21_000));
+    }
+
+    #[test]
+    fn refund_cap_of_capped_refund() {
+        // Sets and clears the slot, refunding more than a fifth of the gas used.
+        let bytecode = Bytecode::new_legacy(
+            [PUSH1, 0x01, PUSH1, 0x01, SSTORE, PUSH0, PUSH1, 0x01, SSTORE].into(),
+        );
+        let mut evm = Context::mainnet()
+            .modify_cfg_chained(|cfg| cfg.spec = SpecId::PRAGUE)
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .build_mainnet();
+        evm.ctx.set_tx(
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Call(BENCH_TARGET))
+                .gas_limit(100_000)
+                .build()
+                .unwrap(),
+        );
+        let mut handler = MainnetHandler::<_, EVMError<Infallible>, EthFrame>::default();
+        let ExecutionResult::Success {
+            gas_used,
+            gas_refunded,
+            ..
+        } = handler.run(&mut evm).unwrap()
+        else {
+            panic!("expected success");
+        };
+
+        // Claimed refund is 20_000 - 100 of the cleared slot.
+        let cap = handler.refund_cap(&evm, gas_used + gas_refunded);
+        assert!(cap < 19_900);
+        assert_eq!(gas_refunded, cap);
+    }
+
+    #[test]
+    fn changed_state_of_read_and_write() {
+        let changed_state = |code: &[u8]| {
+            let mut evm = Context::mainnet()
+                .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                    code.to_vec().into(),
+                )))
+                .build_mainnet();
+            evm.ctx.set_tx(
+                TxEnv::builder()
+                    .caller(BENCH_CALLER)
+                    .kind(TxKind::Call(BENCH_TARGET))
+                    .gas_limit(100_000)
+                    .build()
+                    .unwrap(),
+            );
+            let mut handler = TestHandler::default();
+            handler.run(&mut evm).unwrap();
+            handler.changed_state.get().unwrap()
+        };
+
+        // Only the fee is paid.
+        assert!(!changed_state(&[PUSH0, SLOAD, POP, STOP]));
+        // Write is reverted.
+        assert!(!changed_state(&[
+            PUSH1, 0x01, PUSH0, SSTORE, PUSH0, PUSH0, REVERT
+        ]));
+        assert!(changed_state(&[PUSH1, 0x01, PUSH0, SSTORE, STOP]));
+    }
+
+    #[test]
+    fn min_priority_fee_of_low_tip_tx() {
+        let run = |max_fee: u128, priority_fee: u128| {
+            let bytecode = Bytecode::new_legacy([PUSH1, 0x01, PUSH1, 0x01, SSTORE].into());
+            let mut evm = Context::mainnet()
+                .modify_block_chained(|block| block.basefee = 10)
+                .modify_cfg_chained(|cfg| cfg.min_priority_fee = 5)
+                .with_db(BenchmarkDB::new_bytecode(bytecode))
+                .build_mainnet();
+            evm.ctx.set_tx(
+                TxEnv::builder()
+                    .caller(BENCH_CALLER)
+                    .kind(TxKind::Call(BENCH_TARGET))
+                    .gas_limit(100_000)
+                    .gas_price(max_fee)
+                    .gas_priority_fee(Some(priority_fee))
+                    .build()
+                    .unwrap(),
+            );
+            let mut handler = MainnetHandler::<_, EVMError<Infallible>, EthFrame>::default();
+            let price = handler.effective_gas_price(&evm);
+            let result = handler.run(&mut evm).unwrap();
+            let state = evm.finalize();
+            let paid = BENCH_CALLER_BALANCE - state[&BENCH_CALLER].info.balance;
+            let reward = state[&Address::ZERO].info.balance;
+            (price, paid, reward, U256::from(result.gas_used()))
+        };
+
+        // Tip of one is raised to the floor of five.
+        let (price, paid, reward, gas_used) = run(30, 1);
+        assert_eq!(price, U256::from(15));
+        assert_eq!(paid, price * gas_used);
+        assert_eq!(reward, U256::from(5) * gas_used);
+
+        // Tip above the floor is kept.
+        let (price, _, reward, gas_used) = run(30, 7);
+        assert_eq!(price, U256::from(17));
+        assert_eq!(reward, U256::from(7) * gas_used);
+
+        // Floor is capped by the max fee.
+        let (price, paid, reward, gas_used) = run(12, 1);
+        assert_eq!(price, U256::from(12));
+        assert_eq!(paid, price * gas_used);
+        assert_eq!(reward, U256::from(2) * gas_used);
+    }
+
+    #[test]
+    fn estimate_gas_of_transfer() {
+        let recipient = address!("0x1000000000000000000000000000000000000004");
+        let sstore = Bytecode::new_legacy([PUSH1, 0x01, PUSH1, 0x01, SSTORE].into());
+        let mut evm = Context::mainnet()
+            .with_db(BenchmarkDB::new_bytecode(sstore))
+            .build_mainnet();
+        let mut handler = MainnetHandler::<_, EVMError<Infallible>, EthFrame>::default();
+        // Gas limit of the transaction is the cap of the search.
+        let tx = |to: Address, value: u64| {
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Call(to))
+                .value(U256::from(value))
+                .gas_limit(1_000_000)
+                .build()
+                .unwrap()
+        };
+
+        evm.ctx.set_tx(tx(recipient, 1));
+        assert_eq!(handler.estimate_gas(&mut evm), Ok(21_000));
+        evm.ctx.set_tx(tx(BENCH_TARGET, 0));
+        assert_eq!(handler.estimate_gas(&mut evm), Ok(21_000 + 3 + 3 + 22_100));
+
+        // Nothing is changed.
+        let state = evm.finalize();
+        assert_eq!(state[&recipient].info.balance, U256::ZERO);
+        assert!(state[&BENCH_TARGET]
+            .changed_storage_slots()
+            .next()
+            .is_none());
+        assert_eq!(state[&BENCH_CALLER].info.balance, BENCH_CALLER_BALANCE);
+
+        let mut evm = Context::mainnet()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                [PUSH0, PUSH0, REVERT].into(),
+            )))
+            .build_mainnet();
+        evm.ctx.set_tx(tx(BENCH_TARGET, 0));
+        assert!(matches!(
+            handler.estimate_gas(&mut evm),
+            Err(EVMError::Custom(_))
+        ));
+
+        // Cap that doesn't cover the initial gas is invalid.
+        evm.ctx.modify_tx(|tx| tx.gas_limit = 20_000);
+        assert!(matches!(
+            handler.estimate_gas(&mut evm),
+            Err(EVMError::Transaction(
+                InvalidTransaction::CallGasCostMoreThanGasLimit { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn estimate_gas_of_handler_overrides() {
+        let sstore = Bytecode::new_legacy([PUSH1, 0x01, PUSH1, 0x01, SSTORE].into());
+        let evm = |tx: TxEnv| {
+            let mut evm = Context::mainnet()
+                .modify_cfg_chained(|cfg| cfg.spec = SpecId::PRAGUE)
+                .with_db(BenchmarkDB::new_bytecode(sstore.clone()))
+                .build_mainnet();
+            evm.ctx.set_tx(tx);
+            evm
+        };
+        let mainnet = |tx: TxEnv| {
+            MainnetHandler::<_, EVMError<Infallible>, EthFrame>::default()
+                .estimate_gas(&mut evm(tx))
+                .unwrap()
+        };
+        let call = |to: Address| {
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Call(to))
+                .gas_limit(1_000_000)
+        };
+
+        // Flat floor of a transaction without calldata.
+        let mut empty = Context::mainnet()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+            .build_mainnet();
+        empty.ctx.set_tx(call(BENCH_TARGET).build().unwrap());
+        assert_eq!(
+            TestHandler {
+                flat_floor: Some(30_000),
+                ..Default::default()
+            }
+            .estimate_gas(&mut empty),
+            Ok(30_000)
+        );
+
+        // Execution is paid by the sponsor.
+        let tx = call(BENCH_TARGET).build().unwrap();
+        assert_eq!(mainnet(tx.clone()), 21_000 + 3 + 3 + 22_100);
+        assert_eq!(
+            TestHandler {
+                sponsored_gas: SPONSORED_GAS,
+                ..Default::default()
+            }
+            .estimate_gas(&mut evm(tx)),
+            Ok(21_000)
+        );
+
+        // Surcharge of the call to a delegated account.
+        let signer = PrivateKeySigner::random();
+        let auth = Authorization {
+            chain_id: U256::ZERO,
+            nonce: 0,
+            address: FFADDRESS,
+        };
+        let signature = signer.sign_hash_sync(&auth.signature_hash()).unwrap();
+        let tx = call(signer.address())
+            .authorization_list(vec![Either::Left(auth.into_signed(signature))])
+            .build()
+            .unwrap();
+        assert_eq!(
+            TestHandler {
+                surcharge: SURCHARGE,
+                ..Default::default()
+            }
+            .estimate_gas(&mut evm(tx.clone())),
+            Ok(mainnet(tx) + SURCHARGE)
+        );
+    }
+
+    #[test]
+    fn initial_and_floor_gas_of_access_list_tx() {
+        use context::transaction::{AccessList, AccessListItem};
+
+        let mut evm = Context::mainnet()
+            .modify_cfg_chained(|cfg| cfg.spec = SpecId::PRAGUE)
+            .with_db(BenchmarkDB::default())
+            .build_mainnet();
+        evm.ctx.set_tx(
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Call(BENCH_TARGET))
+                .data(Bytes::from_static(&[0x00, 0x01]))
+                .access_list(AccessList(vec![AccessListItem {
+                    address: BENCH_TARGET,
+                    storage_keys: vec![Default::default()],
+                }]))
+                .gas_limit(100_000)
+                .build()
+                .unwrap(),
+        );
+        let handler = MainnetHandler::<_, EVMError<Infallible>, EthFrame>::default();
+        let gas = handler.validate_initial_tx_gas(&evm).unwrap();
+
+        // Base, access list address and key, zero and nonzero calldata byte.
+        assert_eq!(handler.initial_gas(&gas), 21_000 + 2_400 + 1_900 + 4 + 16);
+        // Base and ten per token, a zero byte is one token and a nonzero byte four.
+        assert_eq!(handler.floor_gas(&gas), 21_000 + 10 * 5);
+    }
+
+    #[test]
+    fn run_with_chain_id_of_mismatched_tx() {
+        let bytecode = Bytecode::new_legacy([CHAINID, PUSH0, SSTORE].into());
+        let mut evm = Context::mainnet()
+            .modify_cfg_chained(|cfg| cfg.tx_chain_id_check = true)
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .build_mainnet();
+        evm.ctx.set_tx(
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Call(BENCH_TARGET))
+                .gas_priority_fee(Some(0))
+                .chain_id(Some(5))
+                .gas_limit(100_000)
+                .build()
+                .unwrap(),
+        );
+        let mut handler = MainnetHandler::<_, EVMError<Infallible>, EthFrame>::default();
+
+        // Transaction signed for chain 5 is rejected on chain 10.
+        assert!(matches!(
+            handler.run_with_chain_id(&mut evm, 10),
+            Err(EVMError::Transaction(InvalidTransaction::InvalidChainId))
+        ));
+        assert_eq!(evm.ctx.cfg.chain_id, 1);
+
+        // On chain 5 it executes and `CHAINID` reads the overridden chain id.
+        let result = handler.run_with_chain_id(&mut evm, 5).unwrap();
+        assert!(result.is_success());
+        assert_eq!(evm.ctx.cfg.chain_id, 1);
+        let state = evm.finalize();
+        assert_eq!(
+            state[&BENCH_TARGET].storage[&StorageKey::ZERO].present_value,
+            StorageValue::from(5)
+        );
+    }
+
+    #[test]
+    fn run_with_retry_of_conflicting_tx() {
+        let bytecode = Bytecode::new_legacy([PUSH1, 0x01, PUSH0, SLOAD, ADD, PUSH0, SSTORE].into());
+        let mut evm = Context::mainnet()
+            .with_db(BenchmarkDB::new_bytecode(bytecode))
+            .build_mainnet();
+        evm.ctx.set_tx(
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Call(BENCH_TARGET))
+                .gas_limit(100_000)
+                .build()
+                .unwrap(),
+        );
+        let mut handler = MainnetHandler::<_, EVMError<Infallible>, EthFrame>::default();
+        let snapshot = evm.ctx.journaled_state.snapshot();
+
+        // Persistent conflict runs the transaction `max_retries + 1` times and counts no gas.
+        let attempts = Cell::new(0);
+        assert_eq!(
+            handler.run_with_retry(&mut evm, &snapshot, 2, |_| {
+                attempts.set(attempts.get() + 1);
+                true
+            }),
+            Err(EVMError::Transaction(
+                InvalidTransaction::ConflictAfterRetries { max_retries: 2 }
+            ))
+        );
+        assert_eq!(attempts.get(), 3);
+        assert_eq!(handler.cumulative_gas_used(&evm), 0);
+
+        // First attempt conflicts, the retry succeeds.
+        let attempts = Cell::new(0);
+        let result = handler
+            .run_with_retry(&mut evm, &snapshot, 3, |_| {
+                attempts.set(attempts.get() + 1);
+                attempts.get() == 1
+            })
+            .unwrap();
+        assert!(result.is_success());
+        assert_eq!(attempts.get(), 2);
+        assert_eq!(handler.cumulative_gas_used(&evm), result.gas_used());
+
+        // Counter and nonce are incremented once.
+        let state = evm.finalize();
+        assert_eq!(state[&BENCH_CALLER].info.nonce, 1);
+        assert_eq!(
+            state[&BENCH_TARGET].storage[&StorageKey::ZERO].present_value,
+            StorageValue::from(1)
+        );
+    }
+
+    #[test]
+    fn check_validity_of_valid_and_unfunded_tx() {
+        let mut evm = Context::mainnet()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new()))
+            .build_mainnet();
+        let tx = |value| {
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Call(BENCH_TARGET))
+                .value(value)
+                .gas_limit(100_000)
+                .build()
+                .unwrap()
+        };
+        let handler = MainnetHandler::<_, EVMError<Infallible>, EthFrame>::default();
+
+        evm.ctx.set_tx(tx(BENCH_CALLER_BALANCE + U256::from(1)));
+        assert!(matches!(
+            handler.check_validity(&mut evm),
+            Err(EVMError::Transaction(
+                InvalidTransaction::LackOfFundForMaxFee { .. }
+            ))
+        ));
+
+        evm.ctx.set_tx(tx(U256::from(1)));
+        assert_eq!(handler.check_validity(&mut evm), Ok(()));
+
+        // Nothing is deducted or journaled.
+        assert!(evm.ctx.journaled_state.journal.is_empty());
+        let state = evm.finalize();
+        assert_eq!(state[&BENCH_CALLER].info.balance, BENCH_CALLER_BALANCE);
+        assert_eq!(state[&BENCH_CALLER].info.nonce, 0);
+    }
+
+    #[test]
+    fn call_capture_of_logging_call() {
+        use interpreter::{CallInput, CallInputs, CallScheme, CallValue};
+
+        const CALLER: Address = address!("0x1000000000000000000000000000000000000000");
+        const TARGET: Address = address!("0x2000000000000000000000000000000000000000");
+        const CHILD: Address = address!("0x3000000000000000000000000000000000000000");
+
+        // Calls the child, then logs and returns 42.
+        let mut code = vec![PUSH0, PUSH0, PUSH0, PUSH0, PUSH0, PUSH20];
+        code.extend_from_slice(CHILD.as_slice());
+        code.extend_from_slice(&[GAS, CALL, POP]);
+        code.extend_from_slice(&[PUSH1, 42, PUSH0, MSTORE, PUSH1, 7, PUSH1, 32, PUSH0, LOG1]);
+        code.extend_from_slice(&[PUSH1, 32, PUSH0, RETURN]);
+        // Logs and reverts.
+        let child_code = [PUSH0, PUSH0, LOG0, PUSH0, PUSH0, REVERT];
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            TARGET,
+            AccountInfo::from_bytecode(Bytecode::new_legacy(code.into())),
+        );
+        db.insert_account_info(
+            CHILD,
+            AccountInfo::from_bytecode(Bytecode::new_legacy(child_code.into())),
+        );
+        let mut evm = Context::mainnet().with_db(db).build_mainnet();
+
+        let mut handler = MainnetHandler::<_, EVMError<Infallible>, EthFrame>::default();
+        let (output, logs) = handler
+            .call_capture(
+                &mut evm,
+                CallInputs {
+                    input: CallInput::Bytes(Bytes::new()),
+                    gas_limit: 100_000,
+                    target_address: TARGET,
+                    bytecode_address: TARGET,
+                    caller: CALLER,
+                    value: CallValue::Transfer(U256::ZERO),
+                    scheme: CallScheme::Call,
+                    is_static: false,
+                    return_memory_offset: 0..0,
+                },
+            )
+            .unwrap();
+
+        let data = Bytes::from(U256::from(42).to_be_bytes_vec());
+        assert_eq!(output, data);
+        // Log of the reverted child is excluded.
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].address, TARGET);
+        assert_eq!(logs[0].topics(), [B256::from(U256::from(7))]);
+        assert_eq!(logs[0].data.data, data);
+
+        // State is discarded.
+        assert!(evm.ctx.journaled_state.journal.is_empty());
+    }
+
+    #[test]
+    fn cumulative_gas_used_of_three_txs() {
+        let mut evm = Context::mainnet()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                [PUSH1, 0x01, PUSH0, SSTORE, STOP].into(),
+            )))
+            .build_mainnet();
+        let tx = |nonce| {
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(TxKind::Call(BENCH_TARGET))
+                .nonce(nonce)
+                .gas_limit(100_000)
+                .build()
+                .unwrap()
+        };
+
+        // Each transaction is run by a new handler, the total is kept in the context.
+        let mut expected = 0;
+        for nonce in 0..3 {
+            expected += evm.transact_one(tx(nonce)).unwrap().gas_used();
+            assert_eq!(evm.ctx.local.cumulative_gas_used, expected);
+        }
+        // First transaction pays for the new storage slot.
+        assert!(expected > 3 * 21_000 + 20_000);
+
+        // Invalid transaction is not counted.
+        assert!(evm.transact_one(tx(0)).is_err());
+        let handler = MainnetHandler::<_, EVMError<Infallible>, EthFrame>::default();
+        assert_eq!(handler.cumulative_gas_used(&evm), expected);
+
+        handler.reset_block(&mut evm);
+        assert_eq!(handler.cumulative_gas_used(&evm), 0);
+    }
+
+    #[test]
+    fn staged_logs_are_returned_with_their_tx() {
+        const CALLER: Address = address!("0x1000000000000000000000000000000000000000");
+        const LOGGER: Address = address!("0x2000000000000000000000000000000000000000");
+        const REVERTER: Address = address!("0x3000000000000000000000000000000000000000");
+
+        // Logs the first calldata word as topic, then stops or reverts.
+        let log = [PUSH0, CALLDATALOAD, PUSH0, PUSH0, LOG1];
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(CALLER, AccountInfo::from_balance(U256::from(10u64.pow(18))));
+        for (address, last) in [
+            (LOGGER, [STOP, STOP, STOP]),
+            (REVERTER, [PUSH0, PUSH0, REVERT]),
+        ] {
+            let code = [&log[..], &last[..]].concat();
+            db.insert_account_info(
+                address,
+                AccountInfo::from_bytecode(Bytecode::new_legacy(code.into())),
+            );
+        }
+        let mut evm = Context::mainnet().with_db(db).build_mainnet();
+        evm.ctx.journaled_state.inner.staged_logs_enabled = true;
+        let mut handler = MainnetHandler::<_, EVMError<Infallible>, EthFrame>::default();
+
+        let mut run = |nonce: u64, target: Address| {
+            evm.ctx.set_tx(
+                TxEnv::builder()
+                    .caller(CALLER)
+                    .kind(TxKind::Call(target))
+                    .data(U256::from(nonce).to_be_bytes_vec().into())
+                    .nonce(nonce)
+                    .gas_limit(100_000)
+                    .build()
+                    .unwrap(),
+            );
+            let result = handler.run(&mut evm).unwrap();
+            result
+                .logs()
+                .iter()
+                .map(|log| (log.address, log.topics()[0]))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(run(0, LOGGER), [(LOGGER, B256::from(U256::from(0)))]);
+        // Staged logs of a reverted tx are dropped and don't leak into the next tx.
+        assert!(run(1, REVERTER).is_empty());
+        assert_eq!(run(2, LOGGER), [(LOGGER, B256::from(U256::from(2)))]);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{frame::EthFrame, instructions::EthInstructions, EthPrecompiles};
 use context::{BlockEnv, Cfg, CfgEnv, Context, Evm, FrameStack, Journal, TxEnv};
-use context_interface::{Block, Database, JournalTr, Transaction};
+use context_interface::{Block, Database, HostHooks, JournalTr, Transaction};
 use database_interface::EmptyDB;
 use interpreter::interpreter::EthInterpreter;
 use primitives::hardfork::SpecId;
@@ -32,6 +32,7 @@ where
     CFG: Cfg,
     DB: Database,
     JOURNAL: JournalTr<Database = DB>,
+    CHAIN: HostHooks,
 {
     type Context = Self;
 
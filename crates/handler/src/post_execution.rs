@@ -4,7 +4,7 @@ use context_interface::{
     result::{ExecutionResult, HaltReasonTr},
     Block, Cfg, ContextTr, Database, Transaction,
 };
-use interpreter::{Gas, InitialAndFloorGas, SuccessOrHalt};
+use interpreter::{Gas, Host, InitialAndFloorGas, SuccessOrHalt};
 use primitives::{hardfork::SpecId, U256};
 
 /// Ensures minimum gas floor is spent according to EIP-7623.
@@ -27,6 +27,20 @@ pub fn refund(spec: SpecId, gas: &mut Gas, eip7702_refund: i64) {
     gas.set_final_refund(spec.is_enabled_in(SpecId::LONDON));
 }
 
+/// Returns the price per gas paid by the caller, used by [`reimburse_caller`] and
+/// [`reward_beneficiary`].
+///
+/// For EIP-1559 transactions it is `min(max_fee, basefee + max_priority_fee)`, raised by
+/// [`Host::min_priority_fee`].
+#[inline]
+pub fn effective_gas_price<CTX: ContextTr>(context: &CTX) -> u128 {
+    let basefee = context.block().basefee() as u128;
+    let min_priority_fee = context.min_priority_fee().saturating_to();
+    context
+        .tx()
+        .effective_gas_price_with_min_priority_fee(basefee, min_priority_fee)
+}
+
 /// Reimburses the caller for unused gas.
 #[inline]
 pub fn reimburse_caller<CTX: ContextTr>(
@@ -34,9 +48,8 @@ pub fn reimburse_caller<CTX: ContextTr>(
     gas: &mut Gas,
     additional_refund: U256,
 ) -> Result<(), <CTX::Db as Database>::Error> {
-    let basefee = context.block().basefee() as u128;
     let caller = context.tx().caller();
-    let effective_gas_price = context.tx().effective_gas_price(basefee);
+    let effective_gas_price = effective_gas_price(context);
 
     // Return balance of not spend gas.
     context.journal_mut().balance_incr(
@@ -57,7 +70,7 @@ pub fn reward_beneficiary<CTX: ContextTr>(
 ) -> Result<(), <CTX::Db as Database>::Error> {
     let beneficiary = context.block().beneficiary();
     let basefee = context.block().basefee() as u128;
-    let effective_gas_price = context.tx().effective_gas_price(basefee);
+    let effective_gas_price = effective_gas_price(context);
 
     // Transfer fee to coinbase/beneficiary.
     // EIP-1559 discard basefee for coinbase transfer. Basefee amount of gas is discarded.
@@ -10,13 +10,14 @@ use context_interface::{
     journaled_state::JournalTr,
     result::InvalidTransaction,
     transaction::{Transaction, TransactionType},
-    Block, Cfg, Database,
+    Block, Cfg, Database, HostHooks,
 };
 use core::cmp::Ordering;
+use interpreter::{AuthResult, Host};
 use primitives::StorageKey;
-use primitives::{eip7702, hardfork::SpecId, KECCAK_EMPTY, U256};
+use primitives::{eip7702, hardfork::SpecId, Address, HashSet, KECCAK_EMPTY, U256};
 use state::AccountInfo;
-use std::boxed::Box;
+use std::{boxed::Box, vec::Vec};
 
 /// Loads and warms accounts for execution, including precompiles and access list.
 pub fn load_accounts<
@@ -34,7 +35,20 @@ pub fn load_accounts<
     let precompiles_changed = precompiles.set_spec(gen_spec);
     let empty_warmed_precompiles = context.journal_mut().precompile_addresses().is_empty();
 
-    if precompiles_changed || empty_warmed_precompiles {
+    if let Some(extra_precompiles) = context.chain().extra_precompiles() {
+        // Chain specific precompiles can change between transactions, so the set is rebuilt
+        // and the journal is only reset when it differs.
+        let addresses: HashSet<Address> = precompiles
+            .warm_addresses()
+            .chain(extra_precompiles.iter().copied())
+            .collect();
+        if precompiles_changed
+            || empty_warmed_precompiles
+            || addresses != *context.journal_ref().precompile_addresses()
+        {
+            context.journal_mut().warm_precompiles(addresses);
+        }
+    } else if precompiles_changed || empty_warmed_precompiles {
         // load new precompile addresses into journal.
         // When precompiles addresses are changed we reset the warmed hashmap to those new addresses.
         context
@@ -60,6 +74,8 @@ pub fn load_accounts<
                 if storage.peek().is_none() {
                     journal.warm_account(*address);
                 } else {
+                    // Accounts with storage keys get a warming entry for the account and for each slot.
+                    journal.reserve_entries(storage.size_hint().0 + 1);
                     journal.warm_account_and_storage(
                         *address,
                         storage.map(|i| StorageKey::from_be_bytes(i.0)),
@@ -72,6 +88,43 @@ pub fn load_accounts<
     Ok(())
 }
 
+/// Validates that the caller account has no code according to EIP-3607.
+///
+/// EIP-7702 delegated accounts are allowed to send transactions.
+#[inline]
+pub fn validate_account_code(caller_info: &AccountInfo) -> Result<(), InvalidTransaction> {
+    // EIP-3607: Reject transactions from senders with deployed code
+    // This EIP is introduced after london but there was no collision in past
+    // so we can leave it enabled always
+    let bytecode = match caller_info.code.as_ref() {
+        Some(code) => code,
+        None => &Bytecode::default(),
+    };
+    // Allow EOAs whose code is a valid delegation designation,
+    // i.e. 0xef0100 || address, to continue to originate transactions.
+    if !bytecode.is_empty() && !bytecode.is_eip7702() {
+        return Err(InvalidTransaction::RejectCallerWithCode);
+    }
+    Ok(())
+}
+
+/// Loads the caller account and validates that it has no code according to EIP-3607,
+/// unless the check is disabled in the configuration.
+#[inline]
+pub fn validate_no_sender_code<
+    CTX: ContextTr,
+    ERROR: From<InvalidTransaction> + From<<CTX::Db as Database>::Error>,
+>(
+    context: &mut CTX,
+) -> Result<(), ERROR> {
+    if context.cfg().is_eip3607_disabled() {
+        return Ok(());
+    }
+    let (tx, journal) = context.tx_journal_mut();
+    let caller_account = journal.load_account_code(tx.caller())?.data;
+    validate_account_code(&caller_account.info).map_err(From::from)
+}
+
 /// Validates caller account nonce and code according to EIP-3607.
 #[inline]
 pub fn validate_account_nonce_and_code(
@@ -80,19 +133,8 @@ pub fn validate_account_nonce_and_code(
     is_eip3607_disabled: bool,
     is_nonce_check_disabled: bool,
 ) -> Result<(), InvalidTransaction> {
-    // EIP-3607: Reject transactions from senders with deployed code
-    // This EIP is introduced after london but there was no collision in past
-    // so we can leave it enabled always
     if !is_eip3607_disabled {
-        let bytecode = match caller_info.code.as_ref() {
-            Some(code) => code,
-            None => &Bytecode::default(),
-        };
-        // Allow EOAs whose code is a valid delegation designation,
-        // i.e. 0xef0100 || address, to continue to originate transactions.
-        if !bytecode.is_empty() && !bytecode.is_eip7702() {
-            return Err(InvalidTransaction::RejectCallerWithCode);
-        }
+        validate_account_code(caller_info)?;
     }
 
     // Check that the transaction's nonce is correct
@@ -108,22 +150,56 @@ pub fn validate_account_nonce_and_code(
             }
             _ => {}
         }
+
+        // EIP-2681: Limit account nonce to 2^64-1. The nonce bump of the transaction, or of its
+        // CREATE frame, would overflow.
+        if state == u64::MAX {
+            return Err(InvalidTransaction::NonceOverflowInTransaction);
+        }
     }
     Ok(())
 }
 
 /// Validates caller state and deducts transaction costs from the caller's balance.
+///
+/// Runs [`validate_no_sender_code`] and [`validate_against_state`] with the
+/// [`Transaction::max_balance_spending`] as the required balance, followed by
+/// [`deduct_caller`], see them for details.
 #[inline]
 pub fn validate_against_state_and_deduct_caller<
     CTX: ContextTr,
     ERROR: From<InvalidTransaction> + From<<CTX::Db as Database>::Error>,
 >(
     context: &mut CTX,
+) -> Result<(), ERROR> {
+    validate_no_sender_code::<CTX, ERROR>(context)?;
+    let required_balance = context.tx().max_balance_spending()?;
+    validate_against_state::<CTX, ERROR>(context, required_balance)?;
+    bump_caller_nonce::<CTX, ERROR>(context)?;
+    deduct_caller(context)
+}
+
+/// Validates caller nonce and balance without changing the caller account.
+///
+/// The caller must have at least `required_balance`, usually the
+/// [`Transaction::max_balance_spending`]. It can exclude the value when it is paid by someone
+/// else, but the gas fee that is deducted must always be covered by the caller balance.
+///
+/// The caller code is not checked here, see [`validate_no_sender_code`].
+#[inline]
+pub fn validate_against_state<
+    CTX: ContextTr,
+    ERROR: From<InvalidTransaction> + From<<CTX::Db as Database>::Error>,
+>(
+    context: &mut CTX,
+    required_balance: U256,
 ) -> Result<(), ERROR> {
     let basefee = context.block().basefee() as u128;
-    let blob_price = context.block().blob_gasprice().unwrap_or_default();
+    let blob_price = context
+        .blob_base_fee_override()
+        .unwrap_or_else(|| context.block().blob_gasprice().unwrap_or_default());
+    let min_priority_fee = context.min_priority_fee().saturating_to();
     let is_balance_check_disabled = context.cfg().is_balance_check_disabled();
-    let is_eip3607_disabled = context.cfg().is_eip3607_disabled();
     let is_nonce_check_disabled = context.cfg().is_nonce_check_disabled();
 
     let (tx, journal) = context.tx_journal_mut();
@@ -131,55 +207,135 @@ pub fn validate_against_state_and_deduct_caller<
     // Load caller's account.
     let caller_account = journal.load_account_code(tx.caller())?.data;
 
+    // Code is validated separately by `validate_no_sender_code`.
     validate_account_nonce_and_code(
         &mut caller_account.info,
         tx.nonce(),
-        is_eip3607_disabled,
+        true,
         is_nonce_check_disabled,
     )?;
 
-    // Bump the nonce for calls. Nonce for CREATE will be bumped in `make_create_frame`.
-    if tx.kind().is_call() {
-        // Nonce is already checked
-        caller_account.info.nonce = caller_account.info.nonce.saturating_add(1);
-    }
-
-    let max_balance_spending = tx.max_balance_spending()?;
-
-    let mut new_balance = caller_account.info.balance;
+    // Fails if the max balance spending overflows, the required balance can be lower.
+    tx.max_balance_spending()?;
 
     // Check if account has enough balance for `gas_limit * max_fee`` and value transfer.
-    // Transfer will be done inside `*_inner` functions.
     if is_balance_check_disabled {
-        // Make sure the caller's balance is at least the value of the transaction.
-        new_balance = caller_account.info.balance.max(tx.value());
-    } else if max_balance_spending > caller_account.info.balance {
+        return Ok(());
+    }
+    if required_balance > caller_account.info.balance {
         return Err(InvalidTransaction::LackOfFundForMaxFee {
-            fee: Box::new(max_balance_spending),
+            fee: Box::new(required_balance),
             balance: Box::new(caller_account.info.balance),
         }
         .into());
-    } else {
-        let effective_balance_spending = tx
-            .effective_balance_spending(basefee, blob_price)
-            .expect("effective balance is always smaller than max balance so it can't overflow");
-
-        // subtracting max balance spending with value that is going to be deducted later in the call.
-        let gas_balance_spending = effective_balance_spending - tx.value();
+    }
+    let gas_balance_spending = gas_balance_spending(tx, basefee, blob_price, min_priority_fee);
+    // Required balance can leave out the value but not the fee, it is reimbursed later.
+    if gas_balance_spending > caller_account.info.balance {
+        return Err(InvalidTransaction::LackOfFundForMaxFee {
+            fee: Box::new(gas_balance_spending),
+            balance: Box::new(caller_account.info.balance),
+        }
+        .into());
+    }
+    Ok(())
+}
 
-        new_balance = new_balance.saturating_sub(gas_balance_spending);
+/// Bumps the caller nonce for calls. Nonce for CREATE will be bumped in `make_create_frame`.
+///
+/// The nonce is expected to be validated with [`validate_against_state`].
+#[inline]
+pub fn bump_caller_nonce<CTX: ContextTr, ERROR: From<<CTX::Db as Database>::Error>>(
+    context: &mut CTX,
+) -> Result<(), ERROR> {
+    let (tx, journal) = context.tx_journal_mut();
+    if !tx.kind().is_call() {
+        return Ok(());
     }
 
+    let caller_account = journal.load_account_code(tx.caller())?.data;
+    // Nonce is already checked
+    caller_account.info.nonce = caller_account.info.nonce.saturating_add(1);
+
+    journal.nonce_bump_journal_entry(tx.caller());
+    Ok(())
+}
+
+/// Deducts the gas fee from the caller balance.
+///
+/// The transaction is expected to be validated with [`validate_against_state`]. The fee is
+/// checked against the caller balance again, as it could have changed in between if the
+/// deduction was deferred.
+#[inline]
+pub fn deduct_caller<
+    CTX: ContextTr,
+    ERROR: From<InvalidTransaction> + From<<CTX::Db as Database>::Error>,
+>(
+    context: &mut CTX,
+) -> Result<(), ERROR> {
+    let basefee = context.block().basefee() as u128;
+    let blob_price = context
+        .blob_base_fee_override()
+        .unwrap_or_else(|| context.block().blob_gasprice().unwrap_or_default());
+    let min_priority_fee = context.min_priority_fee().saturating_to();
+    let is_balance_check_disabled = context.cfg().is_balance_check_disabled();
+
+    let (tx, journal) = context.tx_journal_mut();
+
+    // Load caller's account.
+    let caller_account = journal.load_account_code(tx.caller())?.data;
+
+    // Transfer will be done inside `*_inner` functions.
+    let new_balance = if is_balance_check_disabled {
+        // Make sure the caller's balance is at least the value of the transaction.
+        caller_account.info.balance.max(tx.value())
+    } else {
+        let gas_balance_spending = gas_balance_spending(tx, basefee, blob_price, min_priority_fee);
+        caller_account
+            .info
+            .balance
+            .checked_sub(gas_balance_spending)
+            .ok_or_else(|| InvalidTransaction::LackOfFundForMaxFee {
+                fee: Box::new(gas_balance_spending),
+                balance: Box::new(caller_account.info.balance),
+            })?
+    };
+
     let old_balance = caller_account.info.balance;
     // Touch account so we know it is changed.
     caller_account.mark_touch();
     caller_account.info.balance = new_balance;
 
-    journal.caller_accounting_journal_entry(tx.caller(), old_balance, tx.kind().is_call());
+    journal.caller_accounting_journal_entry(tx.caller(), old_balance, false);
     Ok(())
 }
 
+/// Returns the gas fee deducted from the caller, the effective balance spending without the
+/// value that is transferred later in the call.
+///
+/// The priority fee raised to `min_priority_fee` is charged on top of it, matching the price
+/// used by [`post_execution::reimburse_caller`](crate::post_execution::reimburse_caller) and
+/// [`post_execution::reward_beneficiary`](crate::post_execution::reward_beneficiary).
+#[inline]
+pub fn gas_balance_spending(
+    tx: &impl Transaction,
+    basefee: u128,
+    blob_price: u128,
+    min_priority_fee: u128,
+) -> U256 {
+    let effective_balance_spending = tx
+        .effective_balance_spending(basefee, blob_price)
+        .expect("effective balance is always smaller than max balance so it can't overflow");
+    let min_priority_fee_spending = (tx.gas_limit() as u128).saturating_mul(
+        tx.effective_gas_price_with_min_priority_fee(basefee, min_priority_fee)
+            - tx.effective_gas_price(basefee),
+    );
+    effective_balance_spending - tx.value() + U256::from(min_priority_fee_spending)
+}
+
 /// Apply EIP-7702 auth list and return number gas refund on already created accounts.
+///
+/// The result of each authorization is reported with [`Host::on_authorization`].
 #[inline]
 pub fn apply_eip7702_auth_list<
     CTX: ContextTr,
@@ -194,70 +350,275 @@ pub fn apply_eip7702_auth_list<
     }
 
     let chain_id = context.cfg().chain_id();
-    let (tx, journal) = context.tx_journal_mut();
 
     let mut refunded_accounts = 0;
+    // Results are reported after the list is applied, as the authorizations borrow the
+    // transaction from the context.
+    let (tx, journal) = context.tx_journal_mut();
+    let mut results = Vec::with_capacity(tx.authorization_list_len());
     for authorization in tx.authorization_list() {
-        // 1. Verify the chain id is either 0 or the chain's current ID.
-        let auth_chain_id = authorization.chain_id();
-        if !auth_chain_id.is_zero() && auth_chain_id != U256::from(chain_id) {
-            continue;
-        }
+        let (authority, result) =
+            apply_eip7702_authorization(&authorization, chain_id, journal, &mut refunded_accounts)?;
+        results.push((authority, authorization.address(), result));
+    }
+    for (authority, delegate, result) in results {
+        context.on_authorization(authority, delegate, result);
+    }
 
-        // 2. Verify the `nonce` is less than `2**64 - 1`.
-        if authorization.nonce() == u64::MAX {
-            continue;
-        }
+    let refunded_gas =
+        refunded_accounts * (eip7702::PER_EMPTY_ACCOUNT_COST - eip7702::PER_AUTH_BASE_COST);
 
-        // recover authority and authorized addresses.
-        // 3. `authority = ecrecover(keccak(MAGIC || rlp([chain_id, address, nonce])), y_parity, r, s]`
-        let Some(authority) = authorization.authority() else {
-            continue;
-        };
+    Ok(refunded_gas)
+}
 
-        // warm authority account and check nonce.
-        // 4. Add `authority` to `accessed_addresses` (as defined in [EIP-2929](./eip-2929.md).)
-        let mut authority_acc = journal.load_account_code(authority)?;
+/// Applies a single EIP-7702 authorization, returns its authority if it was recovered and
+/// the result.
+///
+/// The authority is not recovered if the chain id or nonce of the authorization is invalid.
+/// `refunded_accounts` is incremented if the authority account already exists.
+#[inline]
+fn apply_eip7702_authorization<JOURNAL: JournalTr>(
+    authorization: &impl AuthorizationTr,
+    chain_id: u64,
+    journal: &mut JOURNAL,
+    refunded_accounts: &mut u64,
+) -> Result<(Option<Address>, AuthResult), <JOURNAL::Database as Database>::Error> {
+    let address = authorization.address();
+
+    // 1. Verify the chain id is either 0 or the chain's current ID.
+    let auth_chain_id = authorization.chain_id();
+    if !auth_chain_id.is_zero() && auth_chain_id != U256::from(chain_id) {
+        return Ok((None, AuthResult::InvalidChainId));
+    }
 
-        // 5. Verify the code of `authority` is either empty or already delegated.
-        if let Some(bytecode) = &authority_acc.info.code {
-            // if it is not empty and it is not eip7702
-            if !bytecode.is_empty() && !bytecode.is_eip7702() {
-                continue;
-            }
+    // 2. Verify the `nonce` is less than `2**64 - 1`.
+    if authorization.nonce() == u64::MAX {
+        return Ok((None, AuthResult::InvalidNonce));
+    }
+
+    // recover authority and authorized addresses.
+    // 3. `authority = ecrecover(keccak(MAGIC || rlp([chain_id, address, nonce])), y_parity, r, s]`
+    let Some(authority) = authorization.authority() else {
+        return Ok((None, AuthResult::InvalidSignature));
+    };
+
+    // warm authority account and check nonce.
+    // 4. Add `authority` to `accessed_addresses` (as defined in [EIP-2929](./eip-2929.md).)
+    let mut authority_acc = journal.load_account_code(authority)?;
+
+    // 5. Verify the code of `authority` is either empty or already delegated.
+    if let Some(bytecode) = &authority_acc.info.code {
+        // if it is not empty and it is not eip7702
+        if !bytecode.is_empty() && !bytecode.is_eip7702() {
+            return Ok((Some(authority), AuthResult::AuthorityHasCode));
         }
+    }
+
+    // 6. Verify the nonce of `authority` is equal to `nonce`. In case `authority` does not exist in the trie, verify that `nonce` is equal to `0`.
+    if authorization.nonce() != authority_acc.info.nonce {
+        return Ok((Some(authority), AuthResult::InvalidNonce));
+    }
+
+    // 7. Add `PER_EMPTY_ACCOUNT_COST - PER_AUTH_BASE_COST` gas to the global refund counter if `authority` exists in the trie.
+    if !(authority_acc.is_empty() && authority_acc.is_loaded_as_not_existing_not_touched()) {
+        *refunded_accounts += 1;
+    }
+
+    // 8. Set the code of `authority` to be `0xef0100 || address`. This is a delegation designation.
+    //  * As a special case, if `address` is `0x0000000000000000000000000000000000000000` do not write the designation.
+    //    Clear the accounts code and reset the account's code hash to the empty hash `0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470`.
+    let (bytecode, hash) = if address.is_zero() {
+        (Bytecode::default(), KECCAK_EMPTY)
+    } else {
+        let bytecode = Bytecode::new_eip7702(address);
+        let hash = bytecode.hash_slow();
+        (bytecode, hash)
+    };
+    authority_acc.info.code_hash = hash;
+    authority_acc.info.code = Some(bytecode);
+
+    // 9. Increase the nonce of `authority` by one.
+    authority_acc.info.nonce = authority_acc.info.nonce.saturating_add(1);
+    authority_acc.mark_touch();
+    Ok((Some(authority), AuthResult::Success))
+}
 
-        // 6. Verify the nonce of `authority` is equal to `nonce`. In case `authority` does not exist in the trie, verify that `nonce` is equal to `0`.
-        if authorization.nonce() != authority_acc.info.nonce {
-            continue;
+#[cfg(test)]
+mod tests {
+    use crate::{ExecuteEvm, MainBuilder, MainContext};
+    use alloy_signer::{Either, SignerSync};
+    use alloy_signer_local::PrivateKeySigner;
+    use bytecode::{
+        opcode::{BALANCE, PUSH20, STOP},
+        Bytecode,
+    };
+    use context::{
+        result::{EVMError, InvalidTransaction},
+        Context, TxEnv,
+    };
+    use context_interface::{
+        host_hooks::AuthResult, transaction::Authorization, ContextTr, HostHooks, JournalTr,
+    };
+
+    use database::{
+        BenchmarkDB, InMemoryDB, BENCH_CALLER, BENCH_CALLER_BALANCE, BENCH_TARGET, EEADDRESS,
+        FFADDRESS,
+    };
+    use interpreter::gas;
+    use primitives::{address, hardfork::SpecId, Address, HashSet, TxKind, U256};
+
+    use state::AccountInfo;
+
+    /// Chain context with a chain specific precompile.
+    struct ExtraPrecompileChain(HashSet<Address>);
+
+    impl HostHooks for ExtraPrecompileChain {
+        fn extra_precompiles(&self) -> Option<&HashSet<Address>> {
+            Some(&self.0)
         }
+    }
+
+    #[test]
+    fn extra_precompile_is_warm() {
+        const EXTRA: Address = address!("0x0000000000000000000000000000000000000fff");
+
+        let mut code = vec![PUSH20];
+        code.extend_from_slice(EXTRA.as_slice());
+        code.extend([BALANCE, STOP]);
+
+        let run = |extra_precompiles: HashSet<Address>| {
+            let db = BenchmarkDB::new_bytecode(Bytecode::new_legacy(code.clone().into()));
+            let mut evm = Context::mainnet()
+                .with_db(db)
+                .with_chain(ExtraPrecompileChain(extra_precompiles))
+                .build_mainnet();
+            let result = evm
+                .transact(
+                    TxEnv::builder()
+                        .caller(BENCH_CALLER)
+                        .kind(TxKind::Call(BENCH_TARGET))
+                        .gas_limit(100_000)
+                        .build()
+                        .unwrap(),
+                )
+                .unwrap();
+            assert!(result.result.is_success());
+            assert_eq!(
+                evm.ctx
+                    .journal_ref()
+                    .precompile_addresses()
+                    .contains(&EXTRA),
+                !evm.ctx.chain.0.is_empty()
+            );
+            result.result.gas_used()
+        };
 
-        // 7. Add `PER_EMPTY_ACCOUNT_COST - PER_AUTH_BASE_COST` gas to the global refund counter if `authority` exists in the trie.
-        if !(authority_acc.is_empty() && authority_acc.is_loaded_as_not_existing_not_touched()) {
-            refunded_accounts += 1;
+        // `BALANCE` of the extra precompile is charged the warm access cost.
+        assert_eq!(
+            run(HashSet::default()) - run(HashSet::from_iter([EXTRA])),
+            gas::COLD_ACCOUNT_ACCESS_COST - gas::WARM_STORAGE_READ_COST
+        );
+    }
+
+    /// Chain context that records applied authorizations.
+    #[derive(Default)]
+    struct AuthorizationTraceChain(Vec<(Option<Address>, Address, AuthResult)>);
+
+    impl HostHooks for AuthorizationTraceChain {
+        fn on_authorization(
+            &mut self,
+            authority: Option<Address>,
+            delegate: Address,
+            result: AuthResult,
+        ) {
+            self.0.push((authority, delegate, result));
         }
+    }
 
-        // 8. Set the code of `authority` to be `0xef0100 || address`. This is a delegation designation.
-        //  * As a special case, if `address` is `0x0000000000000000000000000000000000000000` do not write the designation.
-        //    Clear the accounts code and reset the account's code hash to the empty hash `0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470`.
-        let address = authorization.address();
-        let (bytecode, hash) = if address.is_zero() {
-            (Bytecode::default(), KECCAK_EMPTY)
-        } else {
-            let bytecode = Bytecode::new_eip7702(address);
-            let hash = bytecode.hash_slow();
-            (bytecode, hash)
+    #[test]
+    fn authorization_results_are_reported() {
+        let signer = PrivateKeySigner::random();
+        let sign = |chain_id: u64, nonce: u64| {
+            let auth = Authorization {
+                chain_id: U256::from(chain_id),
+                nonce,
+                address: FFADDRESS,
+            };
+            let signature = signer.sign_hash_sync(&auth.signature_hash()).unwrap();
+            Either::Left(auth.into_signed(signature))
         };
-        authority_acc.info.code_hash = hash;
-        authority_acc.info.code = Some(bytecode);
 
-        // 9. Increase the nonce of `authority` by one.
-        authority_acc.info.nonce = authority_acc.info.nonce.saturating_add(1);
-        authority_acc.mark_touch();
+        let mut evm = Context::mainnet()
+            .modify_cfg_chained(|cfg| cfg.spec = SpecId::PRAGUE)
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                [STOP].into(),
+            )))
+            .with_chain(AuthorizationTraceChain::default())
+            .build_mainnet();
+        let tx = TxEnv::builder()
+            .gas_limit(200_000)
+            .authorization_list(vec![
+                sign(1234, 0),
+                sign(0, u64::MAX),
+                sign(0, 0),
+                sign(0, 0),
+            ])
+            .caller(EEADDRESS)
+            .kind(TxKind::Call(signer.address()))
+            .build()
+            .unwrap();
+        assert!(evm.transact(tx).unwrap().result.is_success());
+
+        // Authority is not recovered for the invalid chain id and nonce.
+        assert_eq!(
+            evm.ctx.chain.0,
+            [
+                (None, FFADDRESS, AuthResult::InvalidChainId),
+                (None, FFADDRESS, AuthResult::InvalidNonce),
+                (Some(signer.address()), FFADDRESS, AuthResult::Success),
+                (Some(signer.address()), FFADDRESS, AuthResult::InvalidNonce),
+            ]
+        );
     }
 
-    let refunded_gas =
-        refunded_accounts * (eip7702::PER_EMPTY_ACCOUNT_COST - eip7702::PER_AUTH_BASE_COST);
+    #[test]
+    fn caller_with_max_nonce() {
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            BENCH_CALLER,
+            AccountInfo {
+                balance: BENCH_CALLER_BALANCE,
+                nonce: u64::MAX,
+                ..Default::default()
+            },
+        );
+        let mut evm = Context::mainnet().with_db(db).build_mainnet();
+        let tx = |kind| {
+            TxEnv::builder()
+                .caller(BENCH_CALLER)
+                .kind(kind)
+                .nonce(u64::MAX)
+                .gas_limit(100_000)
+                .build()
+                .unwrap()
+        };
 
-    Ok(refunded_gas)
+        for kind in [TxKind::Create, TxKind::Call(BENCH_TARGET)] {
+            assert_eq!(
+                evm.transact(tx(kind)).unwrap_err(),
+                EVMError::Transaction(InvalidTransaction::NonceOverflowInTransaction)
+            );
+        }
+
+        // Without the nonce check the call goes through and the nonce stays at the limit,
+        // while the CREATE frame can't bump it and returns without an address.
+        evm.ctx.modify_cfg(|cfg| cfg.disable_nonce_check = true);
+        let result = evm.transact(tx(TxKind::Call(BENCH_TARGET))).unwrap();
+        assert!(result.result.is_success());
+        assert_eq!(result.state[&BENCH_CALLER].info.nonce, u64::MAX);
+
+        let result = evm.transact(tx(TxKind::Create)).unwrap().result;
+        assert!(result.is_success());
+        assert_eq!(result.created_address(), None);
+    }
 }
@@ -3,9 +3,13 @@ use context_interface::{
     transaction::{Transaction, TransactionType},
     Block, Cfg, ContextTr,
 };
-use core::cmp;
-use interpreter::gas::{self, InitialAndFloorGas};
-use primitives::{eip4844, hardfork::SpecId, B256};
+use core::{cmp, fmt};
+use interpreter::{
+    gas::{self, InitialAndFloorGas},
+    Host,
+};
+use primitives::{eip4844, hardfork::SpecId, Address, HashMap, HashSet, StorageKey, B256};
+use std::vec::Vec;
 
 /// Validates the execution environment including block and transaction parameters.
 pub fn validate_env<CTX: ContextTr, ERROR: From<InvalidHeader> + From<InvalidTransaction>>(
@@ -172,7 +176,9 @@ pub fn validate_tx_env<CTX: ContextTr, Error>(
             validate_eip4844_tx(
                 tx.blob_versioned_hashes(),
                 tx.max_fee_per_blob_gas(),
-                context.block().blob_gasprice().unwrap_or_default(),
+                context
+                    .blob_base_fee_override()
+                    .unwrap_or_else(|| context.block().blob_gasprice().unwrap_or_default()),
                 context.cfg().max_blobs_per_tx(),
             )?;
         }
@@ -308,16 +314,209 @@ pub fn validate_initial_tx_gas(
     Ok(gas)
 }
 
+/// Errors returned by [`validate_access_list`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AccessListError {
+    /// EIP-2930 access lists are not supported before Berlin.
+    NotSupported,
+    /// Address is listed more than once.
+    DuplicateAddress {
+        /// Duplicated address.
+        address: Address,
+    },
+    /// Storage key is listed more than once for the same address.
+    DuplicateStorageKey {
+        /// Address the key is listed under.
+        address: Address,
+        /// Duplicated storage key.
+        key: StorageKey,
+    },
+}
+
+impl core::error::Error for AccessListError {}
+
+impl fmt::Display for AccessListError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotSupported => write!(f, "access list is not supported before Berlin"),
+            Self::DuplicateAddress { address } => {
+                write!(f, "duplicate access list address {address}")
+            }
+            Self::DuplicateStorageKey { address, key } => {
+                write!(
+                    f,
+                    "duplicate access list storage key {key} for address {address}"
+                )
+            }
+        }
+    }
+}
+
+/// Validates an EIP-2930 access list without executing the transaction.
+///
+/// Rejects lists with duplicated addresses or duplicated storage keys under the same address.
+/// Duplicates are permitted by consensus but only add gas cost, so they are a sign of a malformed list.
+///
+/// # Returns
+///
+/// Intrinsic gas the access list adds to the transaction, the same amount
+/// [`validate_initial_tx_gas`] charges for it.
+pub fn validate_access_list(
+    list: &[(Address, Vec<StorageKey>)],
+    spec: SpecId,
+) -> Result<u64, AccessListError> {
+    if !spec.is_enabled_in(SpecId::BERLIN) {
+        return Err(AccessListError::NotSupported);
+    }
+
+    let mut addresses = HashSet::<Address>::default();
+    let mut storages = 0u64;
+    for (address, keys) in list {
+        if !addresses.insert(*address) {
+            return Err(AccessListError::DuplicateAddress { address: *address });
+        }
+
+        let mut seen = HashSet::<StorageKey>::default();
+        for key in keys {
+            if !seen.insert(*key) {
+                return Err(AccessListError::DuplicateStorageKey {
+                    address: *address,
+                    key: *key,
+                });
+            }
+        }
+        storages += keys.len() as u64;
+    }
+
+    Ok(list.len() as u64 * gas::ACCESS_LIST_ADDRESS + storages * gas::ACCESS_LIST_STORAGE_KEY)
+}
+
+/// Errors returned by [`validate_nonce_sequence`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NonceError {
+    /// Nonce is below the current nonce of the sender.
+    TooLow {
+        /// Sender of the transaction.
+        sender: Address,
+        /// Nonce expected for the transaction.
+        expected: u64,
+        /// Nonce of the transaction.
+        actual: u64,
+    },
+    /// Nonce is already used by a previous transaction of the sender in the batch.
+    Duplicate {
+        /// Sender of the transaction.
+        sender: Address,
+        /// Nonce expected for the transaction.
+        expected: u64,
+        /// Nonce of the transaction.
+        actual: u64,
+    },
+    /// Nonce skips over the expected nonce.
+    Gap {
+        /// Sender of the transaction.
+        sender: Address,
+        /// Nonce expected for the transaction.
+        expected: u64,
+        /// Nonce of the transaction.
+        actual: u64,
+    },
+}
+
+impl core::error::Error for NonceError {}
+
+impl fmt::Display for NonceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (reason, sender, expected, actual) = match self {
+            Self::TooLow {
+                sender,
+                expected,
+                actual,
+            } => ("too low", sender, expected, actual),
+            Self::Duplicate {
+                sender,
+                expected,
+                actual,
+            } => ("duplicate", sender, expected, actual),
+            Self::Gap {
+                sender,
+                expected,
+                actual,
+            } => ("gap", sender, expected, actual),
+        };
+        write!(
+            f,
+            "nonce {actual} of {sender} is {reason}, expected {expected}"
+        )
+    }
+}
+
+/// Validates that the nonces of a batch of transactions follow the nonces of their senders.
+///
+/// Transactions are checked in order. The first transaction of a sender must have the
+/// sender's nonce from `current_nonces` (zero if the sender is missing) and every following
+/// transaction of the same sender the next nonce.
+///
+/// This is admission logic that runs before execution, nonces are not bumped in any state.
+pub fn validate_nonce_sequence(
+    txs: &[&impl Transaction],
+    current_nonces: &HashMap<Address, u64>,
+) -> Result<(), NonceError> {
+    let mut next_nonces = HashMap::<Address, u64>::default();
+    for tx in txs {
+        let sender = tx.caller();
+        let current = current_nonces.get(&sender).copied().unwrap_or_default();
+        let expected = next_nonces.entry(sender).or_insert(current);
+        let actual = tx.nonce();
+
+        if actual < current {
+            return Err(NonceError::TooLow {
+                sender,
+                expected: *expected,
+                actual,
+            });
+        }
+        if actual < *expected {
+            return Err(NonceError::Duplicate {
+                sender,
+                expected: *expected,
+                actual,
+            });
+        }
+        if actual > *expected {
+            return Err(NonceError::Gap {
+                sender,
+                expected: *expected,
+                actual,
+            });
+        }
+        *expected = expected.saturating_add(1);
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{ExecuteCommitEvm, MainBuilder, MainContext};
-    use bytecode::opcode;
+    use super::{
+        validate_access_list, validate_initial_tx_gas, validate_nonce_sequence, AccessListError,
+        NonceError,
+    };
+    use crate::{ExecuteCommitEvm, ExecuteEvm, MainBuilder, MainContext};
+    use bytecode::{opcode, Bytecode};
     use context::{
         result::{EVMError, ExecutionResult, HaltReason, InvalidTransaction, Output},
         Context, TxEnv,
     };
+    use context_interface::transaction::{AccessList, AccessListItem};
     use database::{CacheDB, EmptyDB};
-    use primitives::{address, eip3860, eip7907, hardfork::SpecId, Bytes, TxKind};
+    use interpreter::gas;
+    use primitives::{
+        address, eip3860, eip4844, eip7907, hardfork::SpecId, Address, Bytes, HashMap, StorageKey,
+        TxKind, B256, U256,
+    };
+    use state::AccountInfo;
 
     fn deploy_contract(
         bytecode: Bytes,
@@ -604,4 +803,171 @@ mod tests {
             _ => panic!("execution result is not Success"),
         }
     }
+
+    #[test]
+    fn validate_access_list_cost() {
+        let (a, b) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let list = vec![
+            (a, vec![StorageKey::from(1), StorageKey::from(2)]),
+            (b, vec![]),
+        ];
+        assert_eq!(validate_access_list(&[], SpecId::PRAGUE), Ok(0));
+        assert_eq!(
+            validate_access_list(&list, SpecId::PRAGUE),
+            Ok(2 * gas::ACCESS_LIST_ADDRESS + 2 * gas::ACCESS_LIST_STORAGE_KEY)
+        );
+
+        // Cost matches the access list part of the initial tx gas.
+        let initial_gas = |list: &[(Address, Vec<StorageKey>)]| {
+            let access_list = AccessList(
+                list.iter()
+                    .map(|(address, keys)| AccessListItem {
+                        address: *address,
+                        storage_keys: keys.iter().map(|key| B256::from(*key)).collect(),
+                    })
+                    .collect(),
+            );
+            let tx = TxEnv::builder()
+                .gas_limit(100_000)
+                .access_list(access_list)
+                .build()
+                .unwrap();
+            validate_initial_tx_gas(tx, SpecId::PRAGUE)
+                .unwrap()
+                .initial_gas
+        };
+        assert_eq!(
+            validate_access_list(&list, SpecId::PRAGUE),
+            Ok(initial_gas(&list) - initial_gas(&[]))
+        );
+    }
+
+    #[test]
+    fn validate_access_list_errors() {
+        let a = Address::with_last_byte(1);
+        assert_eq!(
+            validate_access_list(&[(a, vec![])], SpecId::ISTANBUL),
+            Err(AccessListError::NotSupported)
+        );
+        assert_eq!(
+            validate_access_list(&[(a, vec![]), (a, vec![])], SpecId::BERLIN),
+            Err(AccessListError::DuplicateAddress { address: a })
+        );
+        let key = StorageKey::from(7);
+        assert_eq!(
+            validate_access_list(&[(a, vec![key, key])], SpecId::BERLIN),
+            Err(AccessListError::DuplicateStorageKey { address: a, key })
+        );
+        // Same key under different addresses is allowed.
+        let b = Address::with_last_byte(2);
+        assert!(validate_access_list(&[(a, vec![key]), (b, vec![key])], SpecId::BERLIN).is_ok());
+    }
+
+    #[test]
+    fn blob_base_fee_override() {
+        const CALLER: Address = address!("0x1000000000000000000000000000000000000000");
+        const TARGET: Address = address!("0x2000000000000000000000000000000000000000");
+        let balance = U256::from(10 * eip4844::GAS_PER_BLOB);
+
+        let run = |blob_base_fee: Option<u128>| {
+            let mut db = CacheDB::<EmptyDB>::default();
+            db.insert_account_info(
+                CALLER,
+                AccountInfo {
+                    balance,
+                    ..Default::default()
+                },
+            );
+            // Stores the blob base fee seen by the `BLOBBASEFEE` instruction.
+            db.insert_account_info(
+                TARGET,
+                AccountInfo::from_bytecode(Bytecode::new_legacy(
+                    [opcode::BLOBBASEFEE, opcode::PUSH0, opcode::SSTORE].into(),
+                )),
+            );
+            let mut blob_hash = B256::ZERO;
+            blob_hash[0] = eip4844::VERSIONED_HASH_VERSION_KZG;
+
+            Context::mainnet()
+                .modify_cfg_chained(|cfg| cfg.blob_base_fee_override = blob_base_fee)
+                .with_db(db)
+                .build_mainnet()
+                .transact(
+                    TxEnv::builder()
+                        .caller(CALLER)
+                        .kind(TxKind::Call(TARGET))
+                        .blob_hashes(vec![blob_hash])
+                        .max_fee_per_blob_gas(10)
+                        .build()
+                        .unwrap(),
+                )
+        };
+
+        // Default block blob base fee is one.
+        let state = run(None).unwrap().state;
+        assert_eq!(
+            state[&CALLER].info.balance,
+            balance - U256::from(eip4844::GAS_PER_BLOB)
+        );
+        assert_eq!(state[&TARGET].storage[&U256::ZERO].present_value, U256::ONE);
+
+        let state = run(Some(7)).unwrap().state;
+        assert_eq!(
+            state[&CALLER].info.balance,
+            balance - U256::from(7 * eip4844::GAS_PER_BLOB)
+        );
+        assert_eq!(
+            state[&TARGET].storage[&U256::ZERO].present_value,
+            U256::from(7)
+        );
+
+        // Overridden fee above the max fee per blob gas invalidates the transaction.
+        assert!(matches!(
+            run(Some(11)),
+            Err(EVMError::Transaction(
+                InvalidTransaction::BlobGasPriceGreaterThanMax
+            ))
+        ));
+    }
+
+    #[test]
+    fn validate_nonce_sequence_cases() {
+        let (alice, bob) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let tx = |caller, nonce| TxEnv::builder().caller(caller).nonce(nonce).build_fill();
+        let current_nonces = HashMap::from_iter([(alice, 5)]);
+        let check = |txs: &[TxEnv]| {
+            validate_nonce_sequence(&txs.iter().collect::<Vec<_>>(), &current_nonces)
+        };
+
+        // In order, interleaved senders. Missing sender starts at zero.
+        assert_eq!(
+            check(&[tx(alice, 5), tx(bob, 0), tx(alice, 6), tx(bob, 1)]),
+            Ok(())
+        );
+
+        assert_eq!(
+            check(&[tx(alice, 5), tx(alice, 7)]),
+            Err(NonceError::Gap {
+                sender: alice,
+                expected: 6,
+                actual: 7
+            })
+        );
+        assert_eq!(
+            check(&[tx(alice, 5), tx(alice, 6), tx(alice, 6)]),
+            Err(NonceError::Duplicate {
+                sender: alice,
+                expected: 7,
+                actual: 6
+            })
+        );
+        assert_eq!(
+            check(&[tx(bob, 0), tx(alice, 4)]),
+            Err(NonceError::TooLow {
+                sender: alice,
+                expected: 5,
+                actual: 4
+            })
+        );
+    }
 }
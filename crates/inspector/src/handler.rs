@@ -1,9 +1,11 @@
 use crate::{Inspector, InspectorEvmTr, JournalExt};
 use context::{result::ExecutionResult, ContextTr, JournalEntry, Transaction};
-use handler::{evm::FrameTr, EvmTr, FrameResult, Handler, ItemOrResult};
+use handler::{
+    evm::FrameTr, handler::ContractGasTracker, EvmTr, FrameResult, Handler, ItemOrResult,
+};
 use interpreter::{
     instructions::InstructionTable,
-    interpreter_types::{Jumps, LoopControl},
+    interpreter_types::{Jumps, LoopControl, StackTr},
     FrameInput, Host, InitialAndFloorGas, InstructionContext, InstructionResult, Interpreter,
     InterpreterAction, InterpreterTypes,
 };
@@ -38,10 +40,14 @@ where
         &mut self,
         evm: &mut Self::Evm,
     ) -> Result<ExecutionResult<Self::HaltReason>, Self::Error> {
-        match self.inspect_run_without_catch_error(evm) {
+        let result = match self.inspect_run_without_catch_error(evm) {
             Ok(output) => Ok(output),
             Err(e) => self.catch_error(evm, e),
+        };
+        if let Ok(output) = &result {
+            self.record_cumulative_gas_used(evm, output);
         }
+        result
     }
 
     /// Run inspection without catching error.
@@ -66,6 +72,9 @@ where
         evm: &mut Self::Evm,
         init_and_floor_gas: &InitialAndFloorGas,
     ) -> Result<FrameResult, Self::Error> {
+        if self.defer_deduction() {
+            self.deduct_caller(evm)?;
+        }
         let gas_limit = evm.ctx().tx().gas_limit() - init_and_floor_gas.initial_gas;
         // Create first frame action
         let first_frame_input = self.first_frame_input(evm, gas_limit)?;
@@ -91,30 +100,48 @@ where
     fn inspect_run_exec_loop(
         &mut self,
         evm: &mut Self::Evm,
-        first_frame_input: <<Self::Evm as EvmTr>::Frame as FrameTr>::FrameInit,
+        mut first_frame_input: <<Self::Evm as EvmTr>::Frame as FrameTr>::FrameInit,
     ) -> Result<FrameResult, Self::Error> {
-        let res = evm.inspect_frame_init(first_frame_input)?;
+        let mut contract_gas = ContractGasTracker::new(self, evm);
+        let mut depth = first_frame_input.depth;
+        self.on_frame_enter(depth, &first_frame_input.frame_input);
+        let res = match contract_gas.enter(self, evm, &mut first_frame_input.frame_input) {
+            Some(result) => ItemOrResult::Result(result),
+            None => evm.inspect_frame_init(first_frame_input)?,
+        };
 
-        if let ItemOrResult::Result(frame_result) = res {
+        if let ItemOrResult::Result(mut frame_result) = res {
+            contract_gas.exit(self, evm, &mut frame_result);
+            self.on_frame_result(depth, &frame_result);
             return Ok(frame_result);
         }
 
         loop {
             let call_or_result = evm.inspect_frame_run()?;
 
-            let result = match call_or_result {
-                ItemOrResult::Item(init) => {
-                    match evm.inspect_frame_init(init)? {
-                        ItemOrResult::Item(_) => {
-                            continue;
+            let mut result = match call_or_result {
+                ItemOrResult::Item(mut init) => {
+                    depth = init.depth;
+                    self.on_frame_enter(depth, &init.frame_input);
+                    if let Some(result) = contract_gas.enter(self, evm, &mut init.frame_input) {
+                        result
+                    } else {
+                        match evm.inspect_frame_init(init)? {
+                            ItemOrResult::Item(_) => {
+                                continue;
+                            }
+                            // Do not pop the frame since no new frame was created
+                            ItemOrResult::Result(result) => result,
                         }
-                        // Do not pop the frame since no new frame was created
-                        ItemOrResult::Result(result) => result,
                     }
                 }
                 ItemOrResult::Result(result) => result,
             };
 
+            contract_gas.exit(self, evm, &mut result);
+            self.on_frame_result(depth, &result);
+            depth = depth.saturating_sub(1);
+
             if let Some(result) = evm.frame_return_result(result)? {
                 return Ok(result);
             }
@@ -183,6 +210,9 @@ where
     IT: InterpreterTypes,
 {
     let mut log_num = context.journal_mut().logs().len();
+    let opcode_budget = context.opcode_budget();
+    let mut remaining_opcodes = opcode_budget;
+    let track_stack_high_water = context.track_stack_high_water();
     // Main loop
     while interpreter.bytecode.is_not_end() {
         // Get current opcode.
@@ -194,6 +224,14 @@ where
             break;
         }
 
+        if let Some(remaining) = &mut remaining_opcodes {
+            if *remaining == 0 {
+                interpreter.halt(InstructionResult::OpcodeBudgetExhausted);
+                break;
+            }
+            *remaining -= 1;
+        }
+
         // SAFETY: In analysis we are doing padding of bytecode so that we are sure that last
         // byte instruction is STOP so we are safe to just increment program_counter bcs on last instruction
         // it will do noop and just stop execution of this contract
@@ -205,6 +243,9 @@ where
             host: context,
         };
         instructions[opcode as usize](instruction_context);
+        if track_stack_high_water {
+            interpreter.stack.record_high_water();
+        }
 
         // check if new log is added
         let new_log = context.journal_mut().logs().len();
@@ -224,6 +265,10 @@ where
         inspector.step_end(interpreter, context);
     }
 
+    if let (Some(budget), Some(remaining)) = (opcode_budget, remaining_opcodes) {
+        context.record_opcodes(budget - remaining);
+    }
+
     interpreter.bytecode.revert_to_previous_pointer();
     // call step_end again to handle the last instruction
     inspector.step_end(interpreter, context);
@@ -1,11 +1,11 @@
 #[cfg(test)]
 mod tests {
-    use crate::{InspectEvm, Inspector};
-    use context::{Context, TxEnv};
-    use database::{BenchmarkDB, BENCH_CALLER, BENCH_TARGET};
+    use crate::{InspectEvm, Inspector, NoOpInspector};
+    use context::{Context, ContextTr, HostHooks, LocalContextTr, TxEnv};
+    use database::{BenchmarkDB, InMemoryDB, BENCH_CALLER, BENCH_TARGET};
     use handler::{MainBuilder, MainContext};
     use interpreter::{
-        interpreter_types::{Jumps, MemoryTr, StackTr},
+        interpreter_types::{Jumps, MemoryTr, RuntimeFlag, StackTr},
         CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter, InterpreterTypes,
     };
     use primitives::{address, Address, Bytes, Log, TxKind, U256};
@@ -731,4 +731,172 @@ mod tests {
             "Should have jumped to JUMPDEST"
         );
     }
+
+    /// Records the call depth and top level flag for every executed opcode.
+    #[derive(Default)]
+    struct DepthInspector {
+        steps: Vec<(u8, usize, bool)>,
+    }
+
+    impl<CTX, INTR> Inspector<CTX, INTR> for DepthInspector
+    where
+        INTR: InterpreterTypes,
+        INTR::Bytecode: Jumps,
+    {
+        fn step(&mut self, interp: &mut Interpreter<INTR>, _context: &mut CTX) {
+            self.steps.push((
+                interp.bytecode.opcode(),
+                interp.runtime_flag.depth(),
+                interp.runtime_flag.is_top_level(),
+            ));
+        }
+    }
+
+    #[test]
+    fn test_top_level_frame_depth() {
+        // Contract calls itself once, the inner call stops as caller is the contract itself.
+        let code = vec![
+            opcode::CALLER,
+            opcode::ADDRESS,
+            opcode::EQ,
+            opcode::PUSH1,
+            0x14,
+            opcode::JUMPI,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::PUSH1,
+            0x00,
+            opcode::ADDRESS,
+            opcode::GAS,
+            opcode::CALL,
+            opcode::POP,
+            opcode::JUMPDEST,
+            opcode::STOP,
+        ];
+
+        let bytecode = Bytecode::new_raw(Bytes::from(code));
+        let ctx = Context::mainnet().with_db(BenchmarkDB::new_bytecode(bytecode));
+        let mut evm = ctx.build_mainnet_with_inspector(DepthInspector::default());
+
+        let result = evm
+            .inspect_one_tx(
+                TxEnv::builder()
+                    .caller(BENCH_CALLER)
+                    .kind(TxKind::Call(BENCH_TARGET))
+                    .gas_limit(100_000)
+                    .build()
+                    .unwrap(),
+            )
+            .unwrap();
+        assert!(result.is_success());
+
+        let steps = &evm.inspector.steps;
+        // Initial frame is at depth zero.
+        assert_eq!(steps.first(), Some(&(opcode::CALLER, 0, true)));
+        // Nested call runs at depth one and jumps to the end.
+        let call = steps
+            .iter()
+            .position(|(op, ..)| *op == opcode::CALL)
+            .unwrap();
+        assert_eq!(steps[call + 1], (opcode::CALLER, 1, false));
+        assert!(steps[call + 1..]
+            .iter()
+            .take_while(|(op, ..)| *op != opcode::POP)
+            .all(|(_, depth, is_top_level)| *depth == 1 && !is_top_level));
+        // Execution continues at the initial frame after the call.
+        assert_eq!(steps.last(), Some(&(opcode::STOP, 0, true)));
+    }
+
+    /// Records the length of the call stack of the local context for every call.
+    #[derive(Default)]
+    struct CallStackInspector {
+        call_stack_lens: Vec<usize>,
+    }
+
+    impl<CTX: ContextTr, INTR: InterpreterTypes> Inspector<CTX, INTR> for CallStackInspector {
+        fn call(&mut self, context: &mut CTX, _inputs: &mut CallInputs) -> Option<CallOutcome> {
+            self.call_stack_lens
+                .push(context.local().call_stack().len());
+            None
+        }
+    }
+
+    /// Chain context that tracks the call stack without limiting reentrancy.
+    struct CallStackChain;
+
+    impl HostHooks for CallStackChain {
+        fn track_call_stack(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn test_call_stack_is_tracked_on_opt_in() {
+        // Contract calls the target, which stops.
+        let mut code = vec![opcode::PUSH0; 5];
+        code.push(opcode::PUSH20);
+        code.extend_from_slice(BENCH_TARGET.as_slice());
+        code.extend([opcode::GAS, opcode::CALL, opcode::STOP]);
+        let caller = address!("0x1000000000000000000000000000000000000000");
+        let tx = TxEnv::builder()
+            .caller(BENCH_CALLER)
+            .kind(TxKind::Call(caller))
+            .gas_limit(100_000)
+            .build()
+            .unwrap();
+        let db = || {
+            let mut db = InMemoryDB::default();
+            db.insert_account_info(
+                caller,
+                AccountInfo::from_bytecode(Bytecode::new_legacy(code.clone().into())),
+            );
+            db
+        };
+
+        // Without a reentrancy limit the call stack is not kept.
+        let mut evm = Context::mainnet()
+            .with_db(db())
+            .build_mainnet_with_inspector(CallStackInspector::default());
+        assert!(evm.inspect_one_tx(tx.clone()).unwrap().is_success());
+        assert_eq!(evm.inspector.call_stack_lens, [0, 0]);
+
+        let mut evm = Context::mainnet()
+            .with_db(db())
+            .with_chain(CallStackChain)
+            .build_mainnet_with_inspector(CallStackInspector::default());
+        assert!(evm.inspect_one_tx(tx).unwrap().is_success());
+        // The nested call sees the transaction target and its own target.
+        assert_eq!(evm.inspector.call_stack_lens, [0, 2]);
+    }
+
+    #[test]
+    fn test_inspect_cumulative_gas_used() {
+        let bytecode =
+            Bytecode::new_legacy([opcode::PUSH1, 0x01, opcode::PUSH0, opcode::SSTORE].into());
+        let ctx = Context::mainnet().with_db(BenchmarkDB::new_bytecode(bytecode));
+        let mut evm = ctx.build_mainnet_with_inspector(NoOpInspector);
+
+        let mut expected = 0;
+        for nonce in 0..2 {
+            expected += evm
+                .inspect_one_tx(
+                    TxEnv::builder()
+                        .caller(BENCH_CALLER)
+                        .kind(TxKind::Call(BENCH_TARGET))
+                        .nonce(nonce)
+                        .gas_limit(100_000)
+                        .build()
+                        .unwrap(),
+                )
+                .unwrap()
+                .gas_used();
+            assert_eq!(evm.ctx.local.cumulative_gas_used, expected);
+        }
+    }
 }
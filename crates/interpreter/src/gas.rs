@@ -124,8 +124,7 @@ impl Gas {
     /// Related to EIP-3529: Reduction in refunds
     #[inline]
     pub fn set_final_refund(&mut self, is_london: bool) {
-        let max_refund_quotient = if is_london { 5 } else { 2 };
-        self.refunded = (self.refunded() as u64).min(self.spent() / max_refund_quotient) as i64;
+        self.refunded = (self.refunded() as u64).min(refund_cap(self.spent(), is_london)) as i64;
     }
 
     /// Set a refund value. This overrides the current refund value.
@@ -153,6 +152,27 @@ impl Gas {
         false
     }
 
+    /// Record memory expansion with a custom cost.
+    ///
+    /// `cost` is called with the current and the new number of words if memory is expanded.
+    #[inline]
+    #[must_use = "internally uses record_cost that flags out of gas error"]
+    pub fn record_memory_expansion_with(
+        &mut self,
+        new_len: usize,
+        cost: impl FnOnce(u64, u64) -> u64,
+    ) -> MemoryExtensionResult {
+        let Some(additional_cost) = self.memory.record_new_len_with(new_len, cost) else {
+            return MemoryExtensionResult::Same;
+        };
+
+        if !self.record_cost(additional_cost) {
+            return MemoryExtensionResult::OutOfGas;
+        }
+
+        MemoryExtensionResult::Extended
+    }
+
     /// Record memory expansion
     #[inline]
     #[must_use = "internally uses record_cost that flags out of gas error"]
@@ -216,4 +236,21 @@ impl MemoryGas {
         // Notice the swap above.
         Some(self.expansion_cost - cost)
     }
+
+    /// Same as [`Self::record_new_len`] but the additional cost is returned by `cost`, that is
+    /// called with the current and the new number of words.
+    #[inline]
+    pub fn record_new_len_with(
+        &mut self,
+        new_num: usize,
+        cost: impl FnOnce(u64, u64) -> u64,
+    ) -> Option<u64> {
+        if new_num <= self.words_num {
+            return None;
+        }
+        let additional_cost = cost(self.words_num as u64, new_num as u64);
+        self.words_num = new_num;
+        self.expansion_cost = self.expansion_cost.saturating_add(additional_cost);
+        Some(additional_cost)
+    }
 }
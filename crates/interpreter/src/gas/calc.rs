@@ -3,7 +3,7 @@ use crate::{num_words, tri, SStoreResult, SelfDestructResult, StateLoad};
 use context_interface::{
     journaled_state::AccountLoad, transaction::AccessListItemTr as _, Transaction, TransactionType,
 };
-use primitives::{eip7702, hardfork::SpecId, U256};
+use primitives::{eip7702, hardfork::SpecId, StorageValue, U256};
 
 /// `SSTORE` opcode refund calculation.
 #[allow(clippy::collapsible_else_if)]
@@ -57,10 +57,53 @@ pub fn sstore_refund(spec_id: SpecId, vals: &SStoreResult) -> i64 {
     }
 }
 
+/// Maximum refund of a transaction that used `gas_used` gas.
+///
+/// EIP-3529: Reduction in refunds lowered the cap from half to a fifth of gas used in London.
+#[inline]
+pub const fn refund_cap(gas_used: u64, is_london: bool) -> u64 {
+    let max_refund_quotient = if is_london { 5 } else { 2 };
+    gas_used / max_refund_quotient
+}
+
 /// `CREATE2` opcode cost calculation.
+///
+/// It is sum of the [`CREATE`] base cost and the [`create2_hashing_cost`].
 #[inline]
 pub const fn create2_cost(len: usize) -> Option<u64> {
-    CREATE.checked_add(tri!(cost_per_word(len, KECCAK256WORD)))
+    CREATE.checked_add(tri!(create2_hashing_cost(len)))
+}
+
+/// `CREATE2` init code hashing cost calculation.
+///
+/// This is the part of the `CREATE2` cost that is not paid by `CREATE`.
+#[inline]
+pub const fn create2_hashing_cost(len: usize) -> Option<u64> {
+    cost_per_word(len, KECCAK256WORD)
+}
+
+/// Gas charged by the `CREATE` or `CREATE2` instruction for `init_code_len` bytes of init code,
+/// excluding memory expansion and the gas used by the init code itself.
+///
+/// It is the [`CREATE`] base cost, the [`create2_hashing_cost`] for `CREATE2`, and since Shanghai
+/// the EIP-3860 [`initcode_cost`]. Saturates at [`u64::MAX`] instead of overflowing.
+#[inline]
+pub const fn create_gas_cost(init_code_len: usize, is_create2: bool, spec: SpecId) -> u64 {
+    let mut gas = CREATE;
+    if is_create2 {
+        let Some(hashing_cost) = create2_hashing_cost(init_code_len) else {
+            return u64::MAX;
+        };
+        gas = gas.saturating_add(hashing_cost);
+    }
+    // EIP-3860: Limit and meter initcode
+    if spec.is_enabled_in(SpecId::SHANGHAI) {
+        let Some(initcode_cost) = cost_per_word(init_code_len, INITCODE_WORD_COST) else {
+            return u64::MAX;
+        };
+        gas = gas.saturating_add(initcode_cost);
+    }
+    gas
 }
 
 #[inline]
@@ -201,6 +244,30 @@ pub fn sstore_cost(spec_id: SpecId, vals: &SStoreResult, is_cold: bool) -> u64 {
     }
 }
 
+/// Returns the gas cost and refund of an `SSTORE` that writes `new` to a slot with the
+/// `original` value (at the start of the transaction) and the `current` value.
+///
+/// Combines [`sstore_cost`] and [`sstore_refund`] so storage writes can be priced without
+/// execution. The EIP-2200 stipend check of the instruction is not included.
+#[inline]
+pub fn sstore_gas_cost(
+    original: StorageValue,
+    current: StorageValue,
+    new: StorageValue,
+    is_cold: bool,
+    spec: SpecId,
+) -> (u64, i64) {
+    let vals = SStoreResult {
+        original_value: original,
+        present_value: current,
+        new_value: new,
+    };
+    (
+        sstore_cost(spec, &vals, is_cold),
+        sstore_refund(spec, &vals),
+    )
+}
+
 /// EIP-2200: Structured Definitions for Net Gas Metering
 #[inline]
 fn istanbul_sstore_cost<const SLOAD_GAS: u64, const SSTORE_RESET_GAS: u64>(
@@ -278,6 +345,20 @@ pub const fn call_cost(
     spec_id: SpecId,
     transfers_value: bool,
     account_load: StateLoad<AccountLoad>,
+) -> u64 {
+    call_cost_with_new_account_cost(spec_id, transfers_value, account_load, NEWACCOUNT)
+}
+
+/// Calculate call gas cost for the call instruction, charging `new_account_cost` instead of
+/// [`NEWACCOUNT`] when the account is created.
+///
+/// See [`call_cost`] for details.
+#[inline]
+pub const fn call_cost_with_new_account_cost(
+    spec_id: SpecId,
+    transfers_value: bool,
+    account_load: StateLoad<AccountLoad>,
+    new_account_cost: u64,
 ) -> u64 {
     let is_empty = account_load.data.is_empty;
     // Account access.
@@ -301,10 +382,10 @@ pub const fn call_cost(
         if spec_id.is_enabled_in(SpecId::SPURIOUS_DRAGON) {
             // Account only if there is value transferred.
             if transfers_value {
-                gas += NEWACCOUNT;
+                gas += new_account_cost;
             }
         } else {
-            gas += NEWACCOUNT;
+            gas += new_account_cost;
         }
     }
 
@@ -487,3 +568,88 @@ pub fn get_tokens_in_calldata(input: &[u8], is_istanbul: bool) -> u64 {
 pub fn calc_tx_floor_cost(tokens_in_calldata: u64) -> u64 {
     tokens_in_calldata * TOTAL_COST_FLOOR_PER_TOKEN + 21_000
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sstore_gas_cost_transitions() {
+        let zero = U256::ZERO;
+        let one = U256::from(1);
+        let two = U256::from(2);
+        let cost =
+            |original, current, new, spec| sstore_gas_cost(original, current, new, false, spec);
+
+        // Set, reset, clear, no-op and restore of a clean slot in (spec, gas, refund) order.
+        for (spec, set, reset, clear, noop) in [
+            (
+                SpecId::FRONTIER,
+                (20000, 0),
+                (5000, 0),
+                (5000, 15000),
+                (5000, 0),
+            ),
+            (
+                SpecId::ISTANBUL,
+                (20000, 0),
+                (5000, 0),
+                (5000, 15000),
+                (800, 0),
+            ),
+            (
+                SpecId::BERLIN,
+                (20000, 0),
+                (2900, 0),
+                (2900, 15000),
+                (100, 0),
+            ),
+            (
+                SpecId::LONDON,
+                (20000, 0),
+                (2900, 0),
+                (2900, 4800),
+                (100, 0),
+            ),
+            (
+                SpecId::PRAGUE,
+                (20000, 0),
+                (2900, 0),
+                (2900, 4800),
+                (100, 0),
+            ),
+        ] {
+            assert_eq!(cost(zero, zero, one, spec), set, "set {spec:?}");
+            assert_eq!(cost(one, one, two, spec), reset, "reset {spec:?}");
+            assert_eq!(cost(one, one, zero, spec), clear, "clear {spec:?}");
+            assert_eq!(cost(one, one, one, spec), noop, "no-op {spec:?}");
+        }
+
+        // Dirty slot restored to the original value, refund returns the set cost.
+        assert_eq!(cost(zero, one, zero, SpecId::ISTANBUL), (800, 19200));
+        assert_eq!(cost(zero, one, zero, SpecId::LONDON), (100, 19900));
+
+        // Cold access is charged on top after Berlin.
+        assert_eq!(
+            sstore_gas_cost(zero, zero, one, true, SpecId::LONDON),
+            (22100, 0)
+        );
+        assert_eq!(
+            sstore_gas_cost(zero, zero, one, true, SpecId::ISTANBUL),
+            (20000, 0)
+        );
+    }
+
+    #[test]
+    fn create_gas_cost_across_specs() {
+        // 33 bytes are two words.
+        assert_eq!(create_gas_cost(33, false, SpecId::LONDON), 32000);
+        assert_eq!(create_gas_cost(33, true, SpecId::LONDON), 32000 + 2 * 6);
+        assert_eq!(create_gas_cost(33, false, SpecId::SHANGHAI), 32000 + 2 * 2);
+        assert_eq!(
+            create_gas_cost(33, true, SpecId::SHANGHAI),
+            32000 + 2 * 6 + 2 * 2
+        );
+        assert_eq!(create_gas_cost(0, true, SpecId::SHANGHAI), 32000);
+    }
+}
@@ -1,16 +1,36 @@
+use bytecode::Bytecode;
 use context_interface::{
     context::{ContextTr, SStoreResult, SelfDestructResult, StateLoad},
-    journaled_state::AccountLoad,
-    Block, Cfg, Database, JournalTr, Transaction, TransactionType,
+    journaled_state::{AccountLoad, CollisionPolicy},
+    result::HaltReason,
+    Block, Cfg, CreateScheme, Database, HostHooks, JournalTr, LocalContextTr, Transaction,
+    TransactionType,
 };
-use primitives::{Address, Bytes, Log, StorageKey, StorageValue, B256, U256};
+use primitives::{hardfork::SpecId, Address, Bytes, Log, StorageKey, StorageValue, B256, U256};
 
-use crate::instructions::utility::IntoU256;
+use crate::{gas, instructions::utility::IntoU256, CallInput, InstructionResult, SuccessOrHalt};
+
+pub use context_interface::host_hooks::{AuthResult, StaticSensitiveOp};
 
 /// Host trait with all methods that are needed by the Interpreter.
 ///
 /// This trait is implemented for all types that have `ContextTr` trait.
 ///
+/// Hooks and knobs with a default implementation keep the mainnet behavior. Context types forward
+/// them to the [`HostHooks`] of their chain context, [`ContextTr::chain`], and keep the call stack
+/// in their local context, [`LocalContextTr::call_stack`].
+///
+/// Hooks shared with [`HostHooks`] are documented there. They differ only where the host resolves
+/// the chain override:
+/// * Overrides that return `Option<T>` in [`HostHooks`] return `T` here, `None` is replaced by
+///   the mainnet value. These are [`Host::selfdestruct_refund`], [`Host::new_account_cost`],
+///   [`Host::log_cost`] and [`Host::memory_expansion_cost`], whose defaults return the mainnet
+///   value.
+/// * Precompile hooks take the [`CallInput`] of the call instead of a slice. A
+///   [`CallInput::SharedBuffer`] range points to the memory of the caller and can be read with
+///   [`LocalContextTr::shared_memory_buffer_slice`] only during the hook. Failures are reported
+///   as [`InstructionResult`] instead of [`HaltReason`].
+///
 /// There are few groups of functions which are Block, Transaction, Config, Database and Journal functions.
 pub trait Host {
     /* Block */
@@ -19,6 +39,16 @@ pub trait Host {
     fn basefee(&self) -> U256;
     /// Block blob gasprice, calls `ContextTr::block().blob_gasprice()`
     fn blob_gasprice(&self) -> U256;
+    /// Blob base fee used instead of [`Host::blob_gasprice`] by the `BLOBBASEFEE` instruction
+    /// and, for context types, by the blob transaction validation and caller balance check.
+    ///
+    /// Context types return [`Cfg::blob_base_fee_override`].
+    ///
+    /// Default implementation returns `None` and the block blob gasprice is used.
+    #[inline]
+    fn blob_base_fee_override(&self) -> Option<u128> {
+        None
+    }
     /// Block gas limit, calls ContextTr::block().gas_limit()
     fn gas_limit(&self) -> U256;
     /// Block difficulty, calls ContextTr::block().difficulty()
@@ -37,7 +67,21 @@ pub trait Host {
     /* Transaction */
 
     /// Transaction effective gas price, calls `ContextTr::tx().effective_gas_price(basefee as u128)`
+    /// raised by [`Host::min_priority_fee`].
     fn effective_gas_price(&self) -> U256;
+    /// Minimum priority fee per gas paid to the beneficiary.
+    ///
+    /// The effective gas price is raised to `basefee + min_priority_fee`, but never above the max
+    /// fee of the transaction. For context types the caller is charged, reimbursed and the
+    /// beneficiary rewarded with the raised price.
+    ///
+    /// Context types return [`Cfg::min_priority_fee`].
+    ///
+    /// Default implementation returns zero and the effective gas price is not changed.
+    #[inline]
+    fn min_priority_fee(&self) -> U256 {
+        U256::ZERO
+    }
     /// Transaction caller, calls `ContextTr::tx().caller()`
     fn caller(&self) -> Address;
     /// Transaction blob hash, calls `ContextTr::tx().blob_hash(number)`
@@ -62,7 +106,7 @@ pub trait Host {
         target: Address,
     ) -> Option<StateLoad<SelfDestructResult>>;
 
-    /// Log, calls `ContextTr::journal_mut().log(log)`
+    /// Log, calls `ContextTr::journal_mut().log_staged(log)`
     fn log(&mut self, log: Log);
     /// Sstore, calls `ContextTr::journal_mut().sstore(address, key, value)`
     fn sstore(
@@ -86,6 +130,255 @@ pub trait Host {
     fn load_account_code(&mut self, address: Address) -> Option<StateLoad<Bytes>>;
     /// Load account code hash, calls `ContextTr::journal_mut().code_hash(address)`
     fn load_account_code_hash(&mut self, address: Address) -> Option<StateLoad<B256>>;
+
+    /// See [`HostHooks::on_storage_access`].
+    #[inline]
+    fn on_storage_access(&mut self, _address: Address, _key: StorageKey, _is_write: bool) {}
+
+    /// See [`HostHooks::is_account_empty_override`].
+    #[inline]
+    fn is_account_empty_override(&self, _address: Address, default: bool) -> bool {
+        default
+    }
+
+    /// See [`HostHooks::selfdestruct_refund`].
+    #[inline]
+    fn selfdestruct_refund(&self, spec: SpecId, newly_destroyed: bool) -> i64 {
+        mainnet_selfdestruct_refund(spec, newly_destroyed)
+    }
+
+    /* Static call */
+
+    /// See [`HostHooks::is_static_violation`].
+    #[inline]
+    fn is_static_violation(&self, _op: StaticSensitiveOp) -> bool {
+        true
+    }
+
+    /* Create */
+
+    /// See [`HostHooks::create_address_override`].
+    #[inline]
+    fn create_address_override(&self, _caller: Address, _nonce: u64) -> Option<Address> {
+        None
+    }
+
+    /// See [`HostHooks::create_nonce`].
+    #[inline]
+    fn create_nonce(&self, _caller: Address) -> Option<u64> {
+        None
+    }
+
+    /// See [`HostHooks::on_create_collision`].
+    #[inline]
+    fn on_create_collision(&mut self, _target: Address) -> CollisionPolicy {
+        CollisionPolicy::Reject
+    }
+
+    /// See [`HostHooks::can_create`].
+    #[inline]
+    fn can_create(&self, _caller: Address, _scheme: &CreateScheme) -> bool {
+        true
+    }
+
+    /// See [`HostHooks::max_call_input_size`].
+    #[inline]
+    fn max_call_input_size(&self) -> usize {
+        usize::MAX
+    }
+
+    /// See [`HostHooks::max_creates_per_tx`].
+    #[inline]
+    fn max_creates_per_tx(&self) -> usize {
+        usize::MAX
+    }
+
+    /// See [`HostHooks::record_create`].
+    #[inline]
+    fn record_create(&mut self) -> usize {
+        0
+    }
+
+    /// See [`HostHooks::track_contract_gas`].
+    #[inline]
+    fn track_contract_gas(&self) -> bool {
+        false
+    }
+
+    /// See [`HostHooks::contract_gas_budget`].
+    #[inline]
+    fn contract_gas_budget(&mut self, _address: Address) -> Option<u64> {
+        None
+    }
+
+    /// See [`HostHooks::record_contract_gas`].
+    #[inline]
+    fn record_contract_gas(&mut self, _address: Address, _gas_used: u64) {}
+
+    /* Authorization list */
+
+    /// See [`HostHooks::on_authorization`].
+    #[inline]
+    fn on_authorization(
+        &mut self,
+        _authority: Option<Address>,
+        _delegate: Address,
+        _result: AuthResult,
+    ) {
+    }
+
+    /* Gas */
+
+    /// See [`HostHooks::gas_cost_multiplier`].
+    #[inline]
+    fn gas_cost_multiplier(&self) -> u64 {
+        1
+    }
+
+    /// See [`HostHooks::new_account_cost`].
+    #[inline]
+    fn new_account_cost(&self) -> u64 {
+        gas::NEWACCOUNT
+    }
+
+    /// See [`HostHooks::min_forwarded_gas`].
+    #[inline]
+    fn min_forwarded_gas(&self) -> u64 {
+        0
+    }
+
+    /// See [`HostHooks::forced_call_gas`].
+    #[inline]
+    fn forced_call_gas(&self, _target: Address) -> Option<u64> {
+        None
+    }
+
+    /// See [`HostHooks::strict_gas_forwarding`].
+    #[inline]
+    fn strict_gas_forwarding(&self) -> bool {
+        false
+    }
+
+    /// See [`HostHooks::on_create_base_gas`].
+    #[inline]
+    fn on_create_base_gas(&mut self, _is_create2: bool, _base: u64, _hashing: u64) {}
+
+    /// See [`HostHooks::on_code_deployed`].
+    #[inline]
+    fn on_code_deployed(&mut self, _address: Address, _code: &Bytecode) {}
+
+    /// See [`HostHooks::on_cold_sload`].
+    #[inline]
+    fn on_cold_sload(&mut self, _address: Address, _key: StorageKey, _gas: u64) {}
+
+    /// See [`HostHooks::on_stipend`].
+    #[inline]
+    fn on_stipend(&mut self, _amount: u64) {}
+
+    /// See [`HostHooks::log_cost`].
+    #[inline]
+    fn log_cost(&self, topics: usize, data_len: usize) -> u64 {
+        mainnet_log_cost(topics, data_len)
+    }
+
+    /// See [`HostHooks::memory_expansion_cost`].
+    #[inline]
+    fn memory_expansion_cost(&self, current_words: u64, new_words: u64) -> u64 {
+        mainnet_memory_expansion_cost(current_words, new_words)
+    }
+
+    /* Interpreter */
+
+    /// See [`HostHooks::opcode_budget`].
+    #[inline]
+    fn opcode_budget(&self) -> Option<u64> {
+        None
+    }
+
+    /// See [`HostHooks::record_opcodes`].
+    #[inline]
+    fn record_opcodes(&mut self, _count: u64) {}
+
+    /* Call stack */
+
+    /// Called by the call instructions just before a new call frame is requested.
+    ///
+    /// `address` is the target address of the call, the account whose storage is going to be used.
+    /// Context types push it to their call stack only if [`HostHooks::track_call_stack`] is
+    /// enabled.
+    ///
+    /// Default implementation does nothing.
+    #[inline]
+    fn on_call_enter(&mut self, _address: Address) {}
+
+    /// Called when a call frame requested by a call instruction returns to its caller.
+    ///
+    /// It is paired with [`Host::on_call_enter`] so the host can maintain its own call stack.
+    ///
+    /// Default implementation does nothing.
+    #[inline]
+    fn on_call_exit(&mut self) {}
+
+    /// See [`HostHooks::track_stack_high_water`].
+    #[inline]
+    fn track_stack_high_water(&self) -> bool {
+        false
+    }
+
+    /// See [`HostHooks::on_stack_high_water`].
+    #[inline]
+    fn on_stack_high_water(&mut self, _frame_depth: usize, _stack_depth: usize) {}
+
+    /// Returns how many times `address` appears as a call target in the current call stack.
+    ///
+    /// Default implementation does not track the call stack and returns zero.
+    #[inline]
+    fn call_stack_occurrences(&self, _address: Address) -> usize {
+        0
+    }
+
+    /// See [`HostHooks::max_reentrancy`].
+    #[inline]
+    fn max_reentrancy(&self) -> usize {
+        usize::MAX
+    }
+
+    /// Returns `true` if `address` is a call target in the current call stack.
+    #[inline]
+    fn call_stack_contains(&self, address: Address) -> bool {
+        self.call_stack_occurrences(address) != 0
+    }
+
+    /// Returns the maximum length of the revert data returned to the calling frame.
+    ///
+    /// Longer revert data of a reverted call or create is truncated before it is copied to the
+    /// return data buffer and the memory of the caller. Truncation is lossy, the caller only sees
+    /// the first bytes of the revert reason. It bounds the memory used by malicious reverts.
+    ///
+    /// Context types return [`Cfg::max_revert_data`].
+    ///
+    /// Default implementation returns `usize::MAX` and revert data is not truncated.
+    #[inline]
+    fn max_revert_data(&self) -> usize {
+        usize::MAX
+    }
+
+    /* Precompiles */
+
+    /// See [`HostHooks::on_precompile_input`].
+    #[inline]
+    fn on_precompile_input(&mut self, _address: Address, _input: &CallInput) {}
+
+    /// See [`HostHooks::on_precompile_result`].
+    #[inline]
+    fn on_precompile_result(
+        &mut self,
+        _address: Address,
+        _input: &CallInput,
+        _output: Result<&Bytes, InstructionResult>,
+        _gas_used: u64,
+    ) {
+    }
 }
 
 impl<CTX: ContextTr> Host for CTX {
@@ -131,7 +424,14 @@ impl<CTX: ContextTr> Host for CTX {
 
     fn effective_gas_price(&self) -> U256 {
         let basefee = self.block().basefee();
-        U256::from(self.tx().effective_gas_price(basefee as u128))
+        U256::from(self.tx().effective_gas_price_with_min_priority_fee(
+            basefee as u128,
+            self.cfg().min_priority_fee(),
+        ))
+    }
+
+    fn min_priority_fee(&self) -> U256 {
+        U256::from(self.cfg().min_priority_fee())
     }
 
     fn caller(&self) -> Address {
@@ -154,6 +454,216 @@ impl<CTX: ContextTr> Host for CTX {
         self.cfg().max_initcode_size()
     }
 
+    fn blob_base_fee_override(&self) -> Option<u128> {
+        self.cfg().blob_base_fee_override()
+    }
+
+    fn max_revert_data(&self) -> usize {
+        self.cfg().max_revert_data()
+    }
+
+    /* Chain hooks */
+
+    fn on_storage_access(&mut self, address: Address, key: StorageKey, is_write: bool) {
+        self.chain_mut().on_storage_access(address, key, is_write)
+    }
+
+    fn on_cold_sload(&mut self, address: Address, key: StorageKey, gas: u64) {
+        self.chain_mut().on_cold_sload(address, key, gas)
+    }
+
+    fn is_account_empty_override(&self, address: Address, default: bool) -> bool {
+        self.chain().is_account_empty_override(address, default)
+    }
+
+    fn selfdestruct_refund(&self, spec: SpecId, newly_destroyed: bool) -> i64 {
+        self.chain()
+            .selfdestruct_refund(spec, newly_destroyed)
+            .unwrap_or_else(|| mainnet_selfdestruct_refund(spec, newly_destroyed))
+    }
+
+    fn is_static_violation(&self, op: StaticSensitiveOp) -> bool {
+        self.chain().is_static_violation(op)
+    }
+
+    fn create_address_override(&self, caller: Address, nonce: u64) -> Option<Address> {
+        self.chain().create_address_override(caller, nonce)
+    }
+
+    fn create_nonce(&self, caller: Address) -> Option<u64> {
+        self.chain().create_nonce(caller)
+    }
+
+    fn on_create_collision(&mut self, target: Address) -> CollisionPolicy {
+        self.chain_mut().on_create_collision(target)
+    }
+
+    fn can_create(&self, caller: Address, scheme: &CreateScheme) -> bool {
+        self.chain().can_create(caller, scheme)
+    }
+
+    fn max_call_input_size(&self) -> usize {
+        self.chain().max_call_input_size()
+    }
+
+    fn max_creates_per_tx(&self) -> usize {
+        self.chain().max_creates_per_tx()
+    }
+
+    fn record_create(&mut self) -> usize {
+        self.chain_mut().record_create()
+    }
+
+    fn track_contract_gas(&self) -> bool {
+        self.chain().track_contract_gas()
+    }
+
+    fn contract_gas_budget(&mut self, address: Address) -> Option<u64> {
+        self.chain_mut().contract_gas_budget(address)
+    }
+
+    fn record_contract_gas(&mut self, address: Address, gas_used: u64) {
+        self.chain_mut().record_contract_gas(address, gas_used)
+    }
+
+    fn on_authorization(
+        &mut self,
+        authority: Option<Address>,
+        delegate: Address,
+        result: AuthResult,
+    ) {
+        self.chain_mut()
+            .on_authorization(authority, delegate, result)
+    }
+
+    fn gas_cost_multiplier(&self) -> u64 {
+        self.chain().gas_cost_multiplier()
+    }
+
+    fn new_account_cost(&self) -> u64 {
+        self.chain().new_account_cost().unwrap_or(gas::NEWACCOUNT)
+    }
+
+    fn min_forwarded_gas(&self) -> u64 {
+        self.chain().min_forwarded_gas()
+    }
+
+    fn forced_call_gas(&self, target: Address) -> Option<u64> {
+        self.chain().forced_call_gas(target)
+    }
+
+    fn strict_gas_forwarding(&self) -> bool {
+        self.chain().strict_gas_forwarding()
+    }
+
+    fn on_create_base_gas(&mut self, is_create2: bool, base: u64, hashing: u64) {
+        self.chain_mut()
+            .on_create_base_gas(is_create2, base, hashing)
+    }
+
+    fn on_code_deployed(&mut self, address: Address, code: &Bytecode) {
+        self.chain_mut().on_code_deployed(address, code)
+    }
+
+    fn on_stipend(&mut self, amount: u64) {
+        self.chain_mut().on_stipend(amount)
+    }
+
+    fn log_cost(&self, topics: usize, data_len: usize) -> u64 {
+        self.chain()
+            .log_cost(topics, data_len)
+            .unwrap_or_else(|| mainnet_log_cost(topics, data_len))
+    }
+
+    fn memory_expansion_cost(&self, current_words: u64, new_words: u64) -> u64 {
+        self.chain()
+            .memory_expansion_cost(current_words, new_words)
+            .unwrap_or_else(|| mainnet_memory_expansion_cost(current_words, new_words))
+    }
+
+    fn opcode_budget(&self) -> Option<u64> {
+        self.chain().opcode_budget()
+    }
+
+    fn record_opcodes(&mut self, count: u64) {
+        self.chain_mut().record_opcodes(count)
+    }
+
+    fn track_stack_high_water(&self) -> bool {
+        self.chain().track_stack_high_water()
+    }
+
+    fn on_stack_high_water(&mut self, frame_depth: usize, stack_depth: usize) {
+        self.chain_mut()
+            .on_stack_high_water(frame_depth, stack_depth)
+    }
+
+    fn on_precompile_input(&mut self, address: Address, input: &CallInput) {
+        match input {
+            CallInput::Bytes(bytes) => self.chain_mut().on_precompile_input(address, bytes),
+            CallInput::SharedBuffer(range) => {
+                // The buffer is reference counted, so the slice is borrowed without a copy while
+                // the chain context is borrowed mutably.
+                let buffer = self.local().shared_memory_buffer().clone();
+                let buffer = buffer.borrow();
+                let input = buffer.get(range.clone()).unwrap_or_default();
+                self.chain_mut().on_precompile_input(address, input)
+            }
+        }
+    }
+
+    fn on_precompile_result(
+        &mut self,
+        address: Address,
+        input: &CallInput,
+        output: Result<&Bytes, InstructionResult>,
+        gas_used: u64,
+    ) {
+        let output = output.map_err(|result| match SuccessOrHalt::<HaltReason>::from(result) {
+            SuccessOrHalt::Halt(reason) => reason,
+            _ => HaltReason::PrecompileError,
+        });
+        match input {
+            CallInput::Bytes(bytes) => self
+                .chain_mut()
+                .on_precompile_result(address, bytes, output, gas_used),
+            CallInput::SharedBuffer(range) => {
+                // See `on_precompile_input`.
+                let buffer = self.local().shared_memory_buffer().clone();
+                let buffer = buffer.borrow();
+                let input = buffer.get(range.clone()).unwrap_or_default();
+                self.chain_mut()
+                    .on_precompile_result(address, input, output, gas_used)
+            }
+        }
+    }
+
+    /* Call stack */
+
+    fn on_call_enter(&mut self, address: Address) {
+        if self.chain().track_call_stack() {
+            self.local_mut().push_call(address);
+        }
+    }
+
+    fn on_call_exit(&mut self) {
+        if self.chain().track_call_stack() {
+            self.local_mut().pop_call();
+        }
+    }
+
+    fn call_stack_occurrences(&self, address: Address) -> usize {
+        self.local()
+            .call_stack()
+            .iter()
+            .filter(|a| **a == address)
+            .count()
+    }
+
+    fn max_reentrancy(&self) -> usize {
+        self.chain().max_reentrancy()
+    }
+
     /* Database */
 
     fn block_hash(&mut self, requested_number: u64) -> Option<B256> {
@@ -245,8 +755,10 @@ impl<CTX: ContextTr> Host for CTX {
     }
 
     /// Emits a log owned by `address` with given `LogData`.
+    ///
+    /// The log is staged if staged logs are enabled in the journal.
     fn log(&mut self, log: Log) {
-        self.journal_mut().log(log);
+        self.journal_mut().log_staged(log);
     }
 
     /// Marks `address` to be deleted, with funds transferred to `target`.
@@ -264,6 +776,29 @@ impl<CTX: ContextTr> Host for CTX {
     }
 }
 
+/// Mainnet refund of the `SELFDESTRUCT` instruction, [`gas::SELFDESTRUCT`] for the first
+/// destruction before London (EIP-3529) and zero otherwise.
+#[inline]
+fn mainnet_selfdestruct_refund(spec: SpecId, newly_destroyed: bool) -> i64 {
+    if newly_destroyed && !spec.is_enabled_in(SpecId::LONDON) {
+        gas::SELFDESTRUCT
+    } else {
+        0
+    }
+}
+
+/// Mainnet cost of the `LOG` instructions, `u64::MAX` if it overflows.
+#[inline]
+fn mainnet_log_cost(topics: usize, data_len: usize) -> u64 {
+    gas::log_cost(topics as u8, data_len as u64).unwrap_or(u64::MAX)
+}
+
+/// Difference of the quadratic mainnet memory costs.
+#[inline]
+fn mainnet_memory_expansion_cost(current_words: u64, new_words: u64) -> u64 {
+    gas::memory_gas(new_words as usize).saturating_sub(gas::memory_gas(current_words as usize))
+}
+
 /// Dummy host that implements [`Host`] trait and  returns all default values.
 #[derive(Debug)]
 pub struct DummyHost;
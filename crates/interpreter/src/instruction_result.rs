@@ -80,6 +80,18 @@ pub enum InstructionResult {
     CreateInitCodeSizeLimit,
     /// Fatal external error. Returned by database.
     FatalExternalError,
+    /// Opcode budget of the host is exhausted, see [`Host::opcode_budget`](crate::Host::opcode_budget).
+    OpcodeBudgetExhausted,
+    /// Too many creates in the transaction, see [`Host::max_creates_per_tx`](crate::Host::max_creates_per_tx).
+    CreateLimitExceeded,
+    /// Gas budget of the called contract is exhausted, see [`Host::contract_gas_budget`](crate::Host::contract_gas_budget).
+    ContractGasBudgetExhausted,
+    /// Creation is vetoed by the host, see [`Host::can_create`](crate::Host::can_create).
+    CreateNotAllowed,
+    /// Call input is too large, see [`Host::max_call_input_size`](crate::Host::max_call_input_size).
+    CallInputSizeLimit,
+    /// Call reenters an address too many times, see [`Host::max_reentrancy`](crate::Host::max_reentrancy).
+    ReentrancyLimit,
 }
 
 impl From<TransferError> for InstructionResult {
@@ -131,6 +143,12 @@ impl From<HaltReason> for InstructionResult {
             HaltReason::CallNotAllowedInsideStatic => Self::CallNotAllowedInsideStatic,
             HaltReason::OutOfFunds => Self::OutOfFunds,
             HaltReason::CallTooDeep => Self::CallTooDeep,
+            HaltReason::OpcodeBudgetExhausted => Self::OpcodeBudgetExhausted,
+            HaltReason::CreateLimitExceeded => Self::CreateLimitExceeded,
+            HaltReason::ContractGasBudgetExhausted => Self::ContractGasBudgetExhausted,
+            HaltReason::CreateNotAllowed => Self::CreateNotAllowed,
+            HaltReason::CallInputSizeLimit => Self::CallInputSizeLimit,
+            HaltReason::ReentrancyLimit => Self::ReentrancyLimit,
         }
     }
 }
@@ -188,6 +206,12 @@ macro_rules! return_error {
             | $crate::InstructionResult::CreateContractStartingWithEF
             | $crate::InstructionResult::CreateInitCodeSizeLimit
             | $crate::InstructionResult::FatalExternalError
+            | $crate::InstructionResult::OpcodeBudgetExhausted
+            | $crate::InstructionResult::CreateLimitExceeded
+            | $crate::InstructionResult::ContractGasBudgetExhausted
+            | $crate::InstructionResult::CreateNotAllowed
+            | $crate::InstructionResult::CallInputSizeLimit
+            | $crate::InstructionResult::ReentrancyLimit
     };
 }
 
@@ -215,6 +239,20 @@ impl InstructionResult {
     pub const fn is_error(self) -> bool {
         matches!(self, return_error!())
     }
+
+    /// Returns whether the result is one of the out of gas errors.
+    #[inline]
+    pub const fn is_out_of_gas(self) -> bool {
+        matches!(
+            self,
+            Self::OutOfGas
+                | Self::MemoryOOG
+                | Self::MemoryLimitOOG
+                | Self::PrecompileOOG
+                | Self::InvalidOperandOOG
+                | Self::ReentrancySentryOOG
+        )
+    }
 }
 
 /// Internal results that are not exposed externally
@@ -341,6 +379,20 @@ impl<HaltReasonTr: From<HaltReason>> From<InstructionResult> for SuccessOrHalt<H
             // TODO : (EOF) Add proper Revert subtype.
             InstructionResult::InvalidEOFInitCode => Self::Revert,
             InstructionResult::FatalExternalError => Self::FatalExternalError,
+            InstructionResult::OpcodeBudgetExhausted => {
+                Self::Halt(HaltReason::OpcodeBudgetExhausted.into())
+            }
+            InstructionResult::CreateLimitExceeded => {
+                Self::Halt(HaltReason::CreateLimitExceeded.into())
+            }
+            InstructionResult::ContractGasBudgetExhausted => {
+                Self::Halt(HaltReason::ContractGasBudgetExhausted.into())
+            }
+            InstructionResult::CreateNotAllowed => Self::Halt(HaltReason::CreateNotAllowed.into()),
+            InstructionResult::CallInputSizeLimit => {
+                Self::Halt(HaltReason::CallInputSizeLimit.into())
+            }
+            InstructionResult::ReentrancyLimit => Self::Halt(HaltReason::ReentrancyLimit.into()),
             InstructionResult::InvalidExtDelegateCallTarget => {
                 Self::Internal(InternalResult::InvalidExtDelegateCallTarget)
             }
@@ -410,6 +462,12 @@ mod tests {
             InstructionResult::CreateContractStartingWithEF,
             InstructionResult::CreateInitCodeSizeLimit,
             InstructionResult::FatalExternalError,
+            InstructionResult::OpcodeBudgetExhausted,
+            InstructionResult::CreateLimitExceeded,
+            InstructionResult::ContractGasBudgetExhausted,
+            InstructionResult::CreateNotAllowed,
+            InstructionResult::CallInputSizeLimit,
+            InstructionResult::ReentrancyLimit,
         ];
 
         for result in error_results {
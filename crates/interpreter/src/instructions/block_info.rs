@@ -90,5 +90,9 @@ pub fn blob_basefee<WIRE: InterpreterTypes, H: Host + ?Sized>(
 ) {
     check!(context.interpreter, CANCUN);
     gas!(context.interpreter, gas::BASE);
-    push!(context.interpreter, context.host.blob_gasprice());
+    let blob_basefee = match context.host.blob_base_fee_override() {
+        Some(blob_basefee) => U256::from(blob_basefee),
+        None => context.host.blob_gasprice(),
+    };
+    push!(context.interpreter, blob_basefee);
 }
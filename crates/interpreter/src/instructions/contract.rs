@@ -1,6 +1,9 @@
 mod call_helpers;
 
-pub use call_helpers::{calc_call_gas, get_memory_input_and_out_ranges, resize_memory};
+pub use call_helpers::{
+    calc_call_gas, enter_call, get_memory_input_and_out_ranges, preflight_static_call_gas,
+    resize_memory,
+};
 
 use crate::{
     gas,
@@ -8,7 +11,7 @@ use crate::{
     interpreter_action::FrameInput,
     interpreter_types::{InputsTr, InterpreterTypes, LoopControl, MemoryTr, RuntimeFlag, StackTr},
     CallInput, CallInputs, CallScheme, CallValue, CreateInputs, Host, InstructionResult,
-    InterpreterAction,
+    InterpreterAction, StaticSensitiveOp,
 };
 use context_interface::CreateScheme;
 use primitives::{hardfork::SpecId, Address, Bytes, B256, U256};
@@ -30,7 +33,27 @@ pub fn create<WIRE: InterpreterTypes, const IS_CREATE2: bool, H: Host + ?Sized>(
     }
 
     popn!([value, code_offset, len], context.interpreter);
+    // EIP-1014: Skinny CREATE2
+    let scheme = if IS_CREATE2 {
+        popn!([salt], context.interpreter);
+        CreateScheme::Create2 { salt }
+    } else {
+        CreateScheme::Create
+    };
+
+    // Vetoed before any gas is charged.
+    if !context
+        .host
+        .can_create(context.interpreter.input.target_address(), &scheme)
+    {
+        context
+            .interpreter
+            .halt(InstructionResult::CreateNotAllowed);
+        return;
+    }
+
     let len = as_usize_or_fail!(context.interpreter, len);
+    let gas_cost_multiplier = context.host.gas_cost_multiplier();
 
     let mut code = Bytes::new();
     if len != 0 {
@@ -48,7 +71,10 @@ pub fn create<WIRE: InterpreterTypes, const IS_CREATE2: bool, H: Host + ?Sized>(
                     .halt(InstructionResult::CreateInitCodeSizeLimit);
                 return;
             }
-            gas!(context.interpreter, gas::initcode_cost(len));
+            gas!(
+                context.interpreter,
+                gas::initcode_cost(len).saturating_mul(gas_cost_multiplier)
+            );
         }
 
         let code_offset = as_usize_or_fail!(context.interpreter, code_offset);
@@ -62,25 +88,42 @@ pub fn create<WIRE: InterpreterTypes, const IS_CREATE2: bool, H: Host + ?Sized>(
         );
     }
 
-    // EIP-1014: Skinny CREATE2
-    let scheme = if IS_CREATE2 {
-        popn!([salt], context.interpreter);
+    if IS_CREATE2 {
         // SAFETY: `len` is reasonable in size as gas for it is already deducted.
-        gas_or_fail!(context.interpreter, gas::create2_cost(len));
-        CreateScheme::Create2 { salt }
+        gas_or_fail!(
+            context.interpreter,
+            gas::create2_cost(len).map(|cost| cost.saturating_mul(gas_cost_multiplier))
+        );
+        // Hashing cost can't overflow as the whole CREATE2 cost is already charged.
+        let hashing_cost = gas::create2_hashing_cost(len).unwrap_or_default();
+        context.host.on_create_base_gas(
+            true,
+            gas::CREATE.saturating_mul(gas_cost_multiplier),
+            hashing_cost.saturating_mul(gas_cost_multiplier),
+        );
     } else {
-        gas!(context.interpreter, gas::CREATE);
-        CreateScheme::Create
-    };
+        let base_cost = gas::CREATE.saturating_mul(gas_cost_multiplier);
+        gas!(context.interpreter, base_cost);
+        context.host.on_create_base_gas(false, base_cost, 0);
+    }
+
+    // Checked after the gas is charged, halt consumes the remaining gas of the frame.
+    if context.host.record_create() > context.host.max_creates_per_tx() {
+        context
+            .interpreter
+            .halt(InstructionResult::CreateLimitExceeded);
+        return;
+    }
 
     let mut gas_limit = context.interpreter.gas.remaining();
 
     // EIP-150: Gas cost changes for IO-heavy operations
-    if context
-        .interpreter
-        .runtime_flag
-        .spec_id()
-        .is_enabled_in(SpecId::TANGERINE)
+    if !context.host.strict_gas_forwarding()
+        && context
+            .interpreter
+            .runtime_flag
+            .spec_id()
+            .is_enabled_in(SpecId::TANGERINE)
     {
         // Take remaining gas and deduce l64 part of it.
         gas_limit -= gas_limit / 64
@@ -112,30 +155,41 @@ pub fn call<WIRE: InterpreterTypes, H: Host + ?Sized>(context: InstructionContex
     let local_gas_limit = u64::try_from(local_gas_limit).unwrap_or(u64::MAX);
 
     let has_transfer = !value.is_zero();
-    if context.interpreter.runtime_flag.is_static() && has_transfer {
+    if context.interpreter.runtime_flag.is_static()
+        && has_transfer
+        && context
+            .host
+            .is_static_violation(StaticSensitiveOp::CallWithValue)
+    {
         context
             .interpreter
             .halt(InstructionResult::CallNotAllowedInsideStatic);
         return;
     }
 
-    let Some((input, return_memory_offset)) = get_memory_input_and_out_ranges(context.interpreter)
+    let Some((input, return_memory_offset)) =
+        get_memory_input_and_out_ranges(context.interpreter, context.host)
     else {
         return;
     };
 
-    let Some(account_load) = context.host.load_account_delegated(to) else {
+    let Some(mut account_load) = context.host.load_account_delegated(to) else {
         context
             .interpreter
             .halt(InstructionResult::FatalExternalError);
         return;
     };
+    account_load.is_empty = context
+        .host
+        .is_account_empty_override(to, account_load.is_empty);
 
     let Some(mut gas_limit) = calc_call_gas(
         context.interpreter,
+        context.host,
         account_load,
         has_transfer,
         local_gas_limit,
+        to,
     ) else {
         return;
     };
@@ -145,6 +199,11 @@ pub fn call<WIRE: InterpreterTypes, H: Host + ?Sized>(context: InstructionContex
     // Add call stipend if there is value to be transferred.
     if has_transfer {
         gas_limit = gas_limit.saturating_add(gas::CALL_STIPEND);
+        context.host.on_stipend(gas::CALL_STIPEND);
+    }
+
+    if !enter_call(context.interpreter, context.host, to) {
+        return;
     }
 
     // Call host to interact with target contract
@@ -178,7 +237,8 @@ pub fn call_code<WIRE: InterpreterTypes, H: Host + ?Sized>(
     let local_gas_limit = u64::try_from(local_gas_limit).unwrap_or(u64::MAX);
 
     //pop!(context.interpreter, value);
-    let Some((input, return_memory_offset)) = get_memory_input_and_out_ranges(context.interpreter)
+    let Some((input, return_memory_offset)) =
+        get_memory_input_and_out_ranges(context.interpreter, context.host)
     else {
         return;
     };
@@ -192,9 +252,14 @@ pub fn call_code<WIRE: InterpreterTypes, H: Host + ?Sized>(
 
     // Set `is_empty` to false as we are not creating this account.
     load.is_empty = false;
-    let Some(mut gas_limit) =
-        calc_call_gas(context.interpreter, load, !value.is_zero(), local_gas_limit)
-    else {
+    let Some(mut gas_limit) = calc_call_gas(
+        context.interpreter,
+        context.host,
+        load,
+        !value.is_zero(),
+        local_gas_limit,
+        to,
+    ) else {
         return;
     };
 
@@ -203,6 +268,12 @@ pub fn call_code<WIRE: InterpreterTypes, H: Host + ?Sized>(
     // Add call stipend if there is value to be transferred.
     if !value.is_zero() {
         gas_limit = gas_limit.saturating_add(gas::CALL_STIPEND);
+        context.host.on_stipend(gas::CALL_STIPEND);
+    }
+
+    let target_address = context.interpreter.input.target_address();
+    if !enter_call(context.interpreter, context.host, target_address) {
+        return;
     }
 
     // Call host to interact with target contract
@@ -236,7 +307,8 @@ pub fn delegate_call<WIRE: InterpreterTypes, H: Host + ?Sized>(
     // Max gas limit is not possible in real ethereum situation.
     let local_gas_limit = u64::try_from(local_gas_limit).unwrap_or(u64::MAX);
 
-    let Some((input, return_memory_offset)) = get_memory_input_and_out_ranges(context.interpreter)
+    let Some((input, return_memory_offset)) =
+        get_memory_input_and_out_ranges(context.interpreter, context.host)
     else {
         return;
     };
@@ -250,12 +322,24 @@ pub fn delegate_call<WIRE: InterpreterTypes, H: Host + ?Sized>(
 
     // Set is_empty to false as we are not creating this account.
     load.is_empty = false;
-    let Some(gas_limit) = calc_call_gas(context.interpreter, load, false, local_gas_limit) else {
+    let Some(gas_limit) = calc_call_gas(
+        context.interpreter,
+        context.host,
+        load,
+        false,
+        local_gas_limit,
+        to,
+    ) else {
         return;
     };
 
     gas!(context.interpreter, gas_limit);
 
+    let target_address = context.interpreter.input.target_address();
+    if !enter_call(context.interpreter, context.host, target_address) {
+        return;
+    }
+
     // Call host to interact with target contract
     context
         .interpreter
@@ -287,7 +371,8 @@ pub fn static_call<WIRE: InterpreterTypes, H: Host + ?Sized>(
     // Max gas limit is not possible in real ethereum situation.
     let local_gas_limit = u64::try_from(local_gas_limit).unwrap_or(u64::MAX);
 
-    let Some((input, return_memory_offset)) = get_memory_input_and_out_ranges(context.interpreter)
+    let Some((input, return_memory_offset)) =
+        get_memory_input_and_out_ranges(context.interpreter, context.host)
     else {
         return;
     };
@@ -300,11 +385,22 @@ pub fn static_call<WIRE: InterpreterTypes, H: Host + ?Sized>(
     };
     // Set `is_empty` to false as we are not creating this account.
     load.is_empty = false;
-    let Some(gas_limit) = calc_call_gas(context.interpreter, load, false, local_gas_limit) else {
+    let Some(gas_limit) = calc_call_gas(
+        context.interpreter,
+        context.host,
+        load,
+        false,
+        local_gas_limit,
+        to,
+    ) else {
         return;
     };
     gas!(context.interpreter, gas_limit);
 
+    if !enter_call(context.interpreter, context.host, to) {
+        return;
+    }
+
     // Call host to interact with target contract
     context
         .interpreter
@@ -323,3 +419,1133 @@ pub fn static_call<WIRE: InterpreterTypes, H: Host + ?Sized>(
             },
         ))));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::preflight_static_call_gas;
+    use crate::{
+        gas,
+        host::Host,
+        instructions::instruction_table,
+        interpreter::{EthInterpreter, ExtBytecode, InputsImpl, SharedMemory},
+        CallValue, FrameInput, Interpreter, InterpreterAction, StaticSensitiveOp,
+    };
+    use bytecode::{opcode, Bytecode};
+    use context_interface::{
+        context::{SStoreResult, SelfDestructResult, StateLoad},
+        journaled_state::AccountLoad,
+        CreateScheme,
+    };
+    use primitives::{
+        address, hardfork::SpecId, Address, Bytes, Log, StorageKey, StorageValue, B256, U256,
+    };
+    use std::vec::Vec;
+
+    /// Host that records the hooks called by the contract instructions.
+    #[derive(Debug, Default)]
+    struct TestHost {
+        call_stack: Vec<Address>,
+        max_reentrancy: Option<usize>,
+        create_base_gas: Vec<(bool, u64, u64)>,
+        empty_override: Option<bool>,
+        empty_accounts: bool,
+        new_account_cost: Option<u64>,
+        gas_cost_multiplier: Option<u64>,
+        min_forwarded_gas: u64,
+        forced_call_gas: Option<(Address, u64)>,
+        strict_gas_forwarding: bool,
+        static_value_call_allowed: bool,
+        max_creates: Option<usize>,
+        creates: usize,
+        deployers: Option<Vec<Address>>,
+        max_call_input: Option<usize>,
+        selfdestruct: Option<SelfDestructResult>,
+        warm_slots: Vec<StorageKey>,
+        cold_sloads: Vec<(Address, StorageKey, u64)>,
+        stipends: Vec<u64>,
+        storage_accesses: Vec<(StorageKey, bool)>,
+    }
+
+    impl Host for TestHost {
+        fn basefee(&self) -> U256 {
+            U256::ZERO
+        }
+
+        fn blob_gasprice(&self) -> U256 {
+            U256::ZERO
+        }
+
+        fn gas_limit(&self) -> U256 {
+            U256::ZERO
+        }
+
+        fn difficulty(&self) -> U256 {
+            U256::ZERO
+        }
+
+        fn prevrandao(&self) -> Option<U256> {
+            None
+        }
+
+        fn block_number(&self) -> U256 {
+            U256::ZERO
+        }
+
+        fn timestamp(&self) -> U256 {
+            U256::ZERO
+        }
+
+        fn beneficiary(&self) -> Address {
+            Address::ZERO
+        }
+
+        fn chain_id(&self) -> U256 {
+            U256::ZERO
+        }
+
+        fn effective_gas_price(&self) -> U256 {
+            U256::ZERO
+        }
+
+        fn caller(&self) -> Address {
+            Address::ZERO
+        }
+
+        fn blob_hash(&self, _number: usize) -> Option<U256> {
+            None
+        }
+
+        fn max_initcode_size(&self) -> usize {
+            primitives::eip3860::MAX_INITCODE_SIZE
+        }
+
+        fn block_hash(&mut self, _number: u64) -> Option<B256> {
+            None
+        }
+
+        fn selfdestruct(
+            &mut self,
+            _address: Address,
+            _target: Address,
+        ) -> Option<StateLoad<SelfDestructResult>> {
+            self.selfdestruct
+                .clone()
+                .map(|result| StateLoad::new(result, false))
+        }
+
+        fn log(&mut self, _log: Log) {}
+
+        fn sstore(
+            &mut self,
+            _address: Address,
+            key: StorageKey,
+            value: StorageValue,
+        ) -> Option<StateLoad<SStoreResult>> {
+            let is_cold = !self.warm_slots.contains(&key);
+            if is_cold {
+                self.warm_slots.push(key);
+            }
+            let result = SStoreResult {
+                original_value: StorageValue::ZERO,
+                present_value: StorageValue::ZERO,
+                new_value: value,
+            };
+            Some(StateLoad::new(result, is_cold))
+        }
+
+        fn sload(&mut self, _address: Address, key: StorageKey) -> Option<StateLoad<StorageValue>> {
+            let is_cold = !self.warm_slots.contains(&key);
+            if is_cold {
+                self.warm_slots.push(key);
+            }
+            Some(StateLoad::new(StorageValue::ZERO, is_cold))
+        }
+
+        fn tstore(&mut self, _address: Address, _key: StorageKey, _value: StorageValue) {}
+
+        fn tload(&mut self, _address: Address, _key: StorageKey) -> StorageValue {
+            StorageValue::ZERO
+        }
+
+        fn balance(&mut self, _address: Address) -> Option<StateLoad<U256>> {
+            None
+        }
+
+        fn load_account_delegated(&mut self, _address: Address) -> Option<StateLoad<AccountLoad>> {
+            // Warm account so the call gas is easy to reason about.
+            let load = AccountLoad {
+                is_empty: self.empty_accounts,
+                ..Default::default()
+            };
+            Some(StateLoad::new(load, false))
+        }
+
+        fn load_account_code(&mut self, _address: Address) -> Option<StateLoad<Bytes>> {
+            None
+        }
+
+        fn load_account_code_hash(&mut self, _address: Address) -> Option<StateLoad<B256>> {
+            None
+        }
+
+        fn on_call_enter(&mut self, address: Address) {
+            self.call_stack.push(address);
+        }
+
+        fn on_call_exit(&mut self) {
+            self.call_stack.pop();
+        }
+
+        fn call_stack_occurrences(&self, address: Address) -> usize {
+            self.call_stack.iter().filter(|a| **a == address).count()
+        }
+
+        fn max_reentrancy(&self) -> usize {
+            self.max_reentrancy.unwrap_or(usize::MAX)
+        }
+
+        fn on_create_base_gas(&mut self, is_create2: bool, base: u64, hashing: u64) {
+            self.create_base_gas.push((is_create2, base, hashing));
+        }
+
+        fn on_cold_sload(&mut self, address: Address, key: StorageKey, gas: u64) {
+            self.cold_sloads.push((address, key, gas));
+        }
+
+        fn on_storage_access(&mut self, _address: Address, key: StorageKey, is_write: bool) {
+            self.storage_accesses.push((key, is_write));
+        }
+
+        fn on_stipend(&mut self, amount: u64) {
+            self.stipends.push(amount);
+        }
+
+        fn is_account_empty_override(&self, _address: Address, default: bool) -> bool {
+            self.empty_override.unwrap_or(default)
+        }
+
+        fn new_account_cost(&self) -> u64 {
+            self.new_account_cost.unwrap_or(gas::NEWACCOUNT)
+        }
+
+        fn gas_cost_multiplier(&self) -> u64 {
+            self.gas_cost_multiplier.unwrap_or(1)
+        }
+
+        fn min_forwarded_gas(&self) -> u64 {
+            self.min_forwarded_gas
+        }
+
+        fn is_static_violation(&self, op: StaticSensitiveOp) -> bool {
+            op != StaticSensitiveOp::CallWithValue || !self.static_value_call_allowed
+        }
+
+        fn forced_call_gas(&self, target: Address) -> Option<u64> {
+            self.forced_call_gas
+                .filter(|(forced_target, _)| *forced_target == target)
+                .map(|(_, gas)| gas)
+        }
+
+        fn strict_gas_forwarding(&self) -> bool {
+            self.strict_gas_forwarding
+        }
+
+        fn can_create(&self, caller: Address, _scheme: &CreateScheme) -> bool {
+            self.deployers
+                .as_ref()
+                .is_none_or(|deployers| deployers.contains(&caller))
+        }
+
+        fn max_call_input_size(&self) -> usize {
+            self.max_call_input.unwrap_or(usize::MAX)
+        }
+
+        fn max_creates_per_tx(&self) -> usize {
+            self.max_creates.unwrap_or(usize::MAX)
+        }
+
+        fn record_create(&mut self) -> usize {
+            self.creates += 1;
+            self.creates
+        }
+    }
+
+    fn new_interpreter(code: &'static [u8], target_address: Address) -> Interpreter {
+        Interpreter::<EthInterpreter>::new(
+            SharedMemory::new(),
+            ExtBytecode::new(Bytecode::new_legacy(Bytes::from_static(code))),
+            InputsImpl {
+                target_address,
+                ..Default::default()
+            },
+            false,
+            SpecId::PRAGUE,
+            1_000_000,
+        )
+    }
+
+    /// Runs `CALL` with the target set to the executing contract itself and returns the call target.
+    fn run_self_call(host: &mut TestHost, address: Address) -> Address {
+        let mut interpreter = new_interpreter(
+            &[
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::ADDRESS,
+                opcode::GAS,
+                opcode::CALL,
+                opcode::STOP,
+            ],
+            address,
+        );
+        let table = instruction_table::<EthInterpreter, TestHost>();
+        match interpreter.run_plain(&table, host) {
+            InterpreterAction::NewFrame(FrameInput::Call(inputs)) => inputs.target_address,
+            action => panic!("expected call frame, got {action:?}"),
+        }
+    }
+
+    #[test]
+    fn call_hooks_track_reentrancy() {
+        let contract = address!("0x1000000000000000000000000000000000000001");
+        let mut host = TestHost::default();
+        assert!(!host.call_stack_contains(contract));
+
+        // First call into the contract.
+        assert_eq!(run_self_call(&mut host, contract), contract);
+        assert_eq!(host.call_stack_occurrences(contract), 1);
+
+        // The contract calls itself again, which is a reentrant call.
+        assert_eq!(run_self_call(&mut host, contract), contract);
+        assert_eq!(host.call_stack_occurrences(contract), 2);
+
+        // Both frames return.
+        host.on_call_exit();
+        assert_eq!(host.call_stack_occurrences(contract), 1);
+        host.on_call_exit();
+        assert!(!host.call_stack_contains(contract));
+    }
+
+    #[test]
+    fn max_reentrancy_halts_call() {
+        let contract = address!("0x1000000000000000000000000000000000000001");
+        let mut host = TestHost {
+            max_reentrancy: Some(1),
+            ..Default::default()
+        };
+
+        // Entering once and reentering once are allowed.
+        run_self_call(&mut host, contract);
+        run_self_call(&mut host, contract);
+
+        // The second reentry halts without a new frame.
+        let mut interpreter = new_interpreter(
+            &[
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::ADDRESS,
+                opcode::GAS,
+                opcode::CALL,
+                opcode::STOP,
+            ],
+            contract,
+        );
+        let table = instruction_table::<EthInterpreter, TestHost>();
+        let action = interpreter.run_plain(&table, &mut host);
+        assert_eq!(
+            action.instruction_result(),
+            Some(crate::InstructionResult::ReentrancyLimit)
+        );
+        assert_eq!(host.call_stack_occurrences(contract), 2);
+    }
+
+    #[test]
+    fn create_base_gas_is_split() {
+        let table = instruction_table::<EthInterpreter, TestHost>();
+        let mut host = TestHost::default();
+
+        // CREATE2 with 64 bytes (two words) of init code and zero salt.
+        let mut interpreter = new_interpreter(
+            &[
+                opcode::PUSH0,
+                opcode::PUSH1,
+                64,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::CREATE2,
+                opcode::STOP,
+            ],
+            Address::ZERO,
+        );
+        let action = interpreter.run_plain(&table, &mut host);
+        assert!(matches!(
+            action,
+            InterpreterAction::NewFrame(FrameInput::Create(_))
+        ));
+
+        // CREATE with the same init code.
+        let mut interpreter = new_interpreter(
+            &[
+                opcode::PUSH1,
+                64,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::CREATE,
+                opcode::STOP,
+            ],
+            Address::ZERO,
+        );
+        let action = interpreter.run_plain(&table, &mut host);
+        assert!(matches!(
+            action,
+            InterpreterAction::NewFrame(FrameInput::Create(_))
+        ));
+
+        assert_eq!(
+            host.create_base_gas,
+            [(true, gas::CREATE, 12), (false, gas::CREATE, 0)]
+        );
+        let (_, base, hashing) = host.create_base_gas[0];
+        assert_eq!(Some(base + hashing), gas::create2_cost(64));
+    }
+
+    #[test]
+    fn create_gas_cost_matches_instruction() {
+        use opcode::{CREATE, CREATE2, PUSH1, STOP};
+
+        let table = instruction_table::<EthInterpreter, TestHost>();
+        for spec in [SpecId::LONDON, SpecId::SHANGHAI] {
+            // Code, gas of the pushes and whether it is `CREATE2`.
+            for (code, pushes, is_create2) in [
+                (&[PUSH1, 64, PUSH1, 0, PUSH1, 0, CREATE, STOP][..], 9, false),
+                (
+                    &[PUSH1, 0, PUSH1, 64, PUSH1, 0, PUSH1, 0, CREATE2, STOP],
+                    12,
+                    true,
+                ),
+            ] {
+                let mut interpreter = new_interpreter(code, Address::ZERO);
+                interpreter.runtime_flag.spec_id = spec;
+                let forwarded = match interpreter.run_plain(&table, &mut TestHost::default()) {
+                    InterpreterAction::NewFrame(FrameInput::Create(inputs)) => inputs.gas_limit,
+                    action => panic!("expected create frame, got {action:?}"),
+                };
+                // Pushes, memory expansion to two words and the forwarded gas are excluded.
+                let spent = interpreter.gas.spent()
+                    - pushes
+                    - interpreter.gas.memory().expansion_cost
+                    - forwarded;
+                assert_eq!(spent, gas::create_gas_cost(64, is_create2, spec));
+            }
+        }
+    }
+
+    /// Runs `CALL` that transfers one wei and returns the gas spent by the instruction.
+    fn value_call_gas_spent(host: &mut TestHost) -> u64 {
+        let mut interpreter = new_interpreter(
+            &[
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH1,
+                1,
+                opcode::PUSH1,
+                0xFF,
+                opcode::PUSH0,
+                opcode::CALL,
+                opcode::STOP,
+            ],
+            Address::ZERO,
+        );
+        let table = instruction_table::<EthInterpreter, TestHost>();
+        let _ = interpreter.run_plain(&table, host);
+        interpreter.gas.spent()
+    }
+
+    #[test]
+    fn account_empty_override() {
+        // Default keeps the emptiness reported by the host, the account is not empty.
+        let default_spent = value_call_gas_spent(&mut TestHost::default());
+        let not_empty_spent = value_call_gas_spent(&mut TestHost {
+            empty_override: Some(false),
+            ..Default::default()
+        });
+        assert_eq!(default_spent, not_empty_spent);
+
+        // Treating the account as empty charges the new account cost.
+        let empty_spent = value_call_gas_spent(&mut TestHost {
+            empty_override: Some(true),
+            ..Default::default()
+        });
+        assert_eq!(empty_spent, default_spent + gas::NEWACCOUNT);
+    }
+
+    #[test]
+    fn gas_cost_multiplier_scales_call_cost() {
+        // CALL to a warm account without value and without forwarding any gas.
+        let call_gas_spent = |host: &mut TestHost| {
+            let mut interpreter = new_interpreter(
+                &[
+                    opcode::PUSH0,
+                    opcode::PUSH0,
+                    opcode::PUSH0,
+                    opcode::PUSH0,
+                    opcode::PUSH0,
+                    opcode::PUSH0,
+                    opcode::PUSH0,
+                    opcode::CALL,
+                    opcode::STOP,
+                ],
+                Address::ZERO,
+            );
+            let table = instruction_table::<EthInterpreter, TestHost>();
+            let _ = interpreter.run_plain(&table, host);
+            // Exclude the cost of the pushes.
+            interpreter.gas.spent() - 7 * gas::BASE
+        };
+
+        assert_eq!(
+            call_gas_spent(&mut TestHost::default()),
+            gas::WARM_STORAGE_READ_COST
+        );
+        assert_eq!(
+            call_gas_spent(&mut TestHost {
+                gas_cost_multiplier: Some(2),
+                ..Default::default()
+            }),
+            2 * gas::WARM_STORAGE_READ_COST
+        );
+    }
+
+    #[test]
+    fn preflight_static_call_gas_matches_instruction() {
+        // Runs `STATICCALL` and returns the gas forwarded to the new frame.
+        let forwarded_gas = |code: &'static [u8]| {
+            let mut interpreter = new_interpreter(code, Address::ZERO);
+            let table = instruction_table::<EthInterpreter, TestHost>();
+            match interpreter.run_plain(&table, &mut TestHost::default()) {
+                InterpreterAction::NewFrame(FrameInput::Call(inputs)) => inputs.gas_limit,
+                action => panic!("expected call frame, got {action:?}"),
+            }
+        };
+        // Gas remaining before `STATICCALL`, after five `PUSH0` and one `PUSH3`.
+        let remaining = 1_000_000 - 5 * gas::BASE - gas::VERYLOW;
+        let account_load = || StateLoad::new(AccountLoad::default(), false);
+
+        // Local gas limit above 63/64 of the remaining gas is clamped.
+        let clamped =
+            preflight_static_call_gas(SpecId::PRAGUE, account_load(), 0xFFFFFF, remaining, 0);
+        assert!(clamped < 0xFFFFFF);
+        assert_eq!(
+            clamped,
+            forwarded_gas(&[
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH3,
+                0xFF,
+                0xFF,
+                0xFF,
+                opcode::STATICCALL,
+            ])
+        );
+
+        // Local gas limit below the clamp is forwarded as is.
+        let local = preflight_static_call_gas(SpecId::PRAGUE, account_load(), 0x1000, remaining, 0);
+        assert_eq!(local, 0x1000);
+        assert_eq!(
+            local,
+            forwarded_gas(&[
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH3,
+                0x00,
+                0x10,
+                0x00,
+                opcode::STATICCALL,
+            ])
+        );
+
+        // Memory expansion for the input is charged before the 63/64 rule.
+        let remaining = 1_000_000 - 4 * gas::BASE - 2 * gas::VERYLOW;
+        let expanded = preflight_static_call_gas(
+            SpecId::PRAGUE,
+            account_load(),
+            0xFFFFFF,
+            remaining,
+            gas::memory_gas(2),
+        );
+        assert!(expanded < clamped);
+        assert_eq!(
+            expanded,
+            forwarded_gas(&[
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH1,
+                0x40,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH3,
+                0xFF,
+                0xFF,
+                0xFF,
+                opcode::STATICCALL,
+            ])
+        );
+
+        // Remaining gas does not cover the call cost.
+        assert_eq!(
+            preflight_static_call_gas(SpecId::PRAGUE, account_load(), 0x1000, 10, 0),
+            0
+        );
+        assert_eq!(
+            preflight_static_call_gas(SpecId::PRAGUE, account_load(), 0x1000, 150, 100),
+            0
+        );
+    }
+
+    #[test]
+    fn memory_expansion_cost_matches_mainnet() {
+        let host = TestHost::default();
+        // Three gas per word plus the quadratic `words^2 / 512` part.
+        assert_eq!(host.memory_expansion_cost(0, 1), 3);
+        assert_eq!(host.memory_expansion_cost(0, 32), 98);
+        assert_eq!(host.memory_expansion_cost(32, 64), 102);
+        assert_eq!(host.memory_expansion_cost(0, 1024), 5120);
+        assert_eq!(host.memory_expansion_cost(64, 64), 0);
+
+        // Input memory of one word and output memory of 32 words.
+        let mut interpreter = new_interpreter(
+            &[
+                opcode::PUSH2,
+                0x04,
+                0x00,
+                opcode::PUSH0,
+                opcode::PUSH1,
+                0x20,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::CALL,
+            ],
+            Address::ZERO,
+        );
+        let table = instruction_table::<EthInterpreter, TestHost>();
+        let _ = interpreter.run_plain(&table, &mut TestHost::default());
+        let pushes = 2 * gas::VERYLOW + 5 * gas::BASE;
+        assert_eq!(
+            interpreter.gas.spent() - pushes - gas::WARM_STORAGE_READ_COST,
+            98
+        );
+        assert_eq!(interpreter.gas.memory().words_num, 32);
+    }
+
+    #[test]
+    fn min_forwarded_gas_halts_zero_gas_call() {
+        // Value transfer that forwards zero gas, the callee gets only the stipend.
+        let run = |host: &mut TestHost| {
+            let mut interpreter = new_interpreter(
+                &[
+                    opcode::PUSH0,
+                    opcode::PUSH0,
+                    opcode::PUSH0,
+                    opcode::PUSH0,
+                    opcode::PUSH1,
+                    1,
+                    opcode::PUSH1,
+                    0xFF,
+                    opcode::PUSH0,
+                    opcode::CALL,
+                ],
+                Address::ZERO,
+            );
+            let table = instruction_table::<EthInterpreter, TestHost>();
+            interpreter.run_plain(&table, host)
+        };
+
+        match run(&mut TestHost::default()) {
+            InterpreterAction::NewFrame(FrameInput::Call(inputs)) => {
+                assert_eq!(inputs.gas_limit, gas::CALL_STIPEND)
+            }
+            action => panic!("expected call frame, got {action:?}"),
+        }
+
+        // Stipend does not count toward the minimum.
+        let action = run(&mut TestHost {
+            min_forwarded_gas: 1,
+            ..Default::default()
+        });
+        assert_eq!(
+            action.instruction_result(),
+            Some(crate::InstructionResult::OutOfGas)
+        );
+    }
+
+    #[test]
+    fn forced_call_gas_replaces_forwarded_gas() {
+        let target = Address::with_last_byte(0xFF);
+        // `STATICCALL` to `target` with all remaining gas, returns forwarded and spent gas.
+        let run = |host: &mut TestHost| {
+            let mut interpreter = new_interpreter(
+                &[
+                    opcode::PUSH0,
+                    opcode::PUSH0,
+                    opcode::PUSH0,
+                    opcode::PUSH0,
+                    opcode::PUSH1,
+                    0xFF,
+                    opcode::GAS,
+                    opcode::STATICCALL,
+                ],
+                Address::ZERO,
+            );
+            let table = instruction_table::<EthInterpreter, TestHost>();
+            match interpreter.run_plain(&table, host) {
+                InterpreterAction::NewFrame(FrameInput::Call(inputs)) => {
+                    Some((inputs.gas_limit, interpreter.gas.spent()))
+                }
+                _ => None,
+            }
+        };
+
+        let (forwarded, spent) = run(&mut TestHost::default()).unwrap();
+        assert!(forwarded > 1_000);
+
+        // Call cost charged to the caller is unchanged, only the forwarded gas differs.
+        let (forced, forced_spent) = run(&mut TestHost {
+            forced_call_gas: Some((target, 1_000)),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(forced, 1_000);
+        assert_eq!(forced_spent - forced, spent - forwarded);
+
+        // Other targets are not affected.
+        let other = run(&mut TestHost {
+            forced_call_gas: Some((Address::ZERO, 1_000)),
+            ..Default::default()
+        });
+        assert_eq!(other, Some((forwarded, spent)));
+
+        // Forced gas above the 63/64 limit is still paid by the caller.
+        assert_eq!(
+            run(&mut TestHost {
+                forced_call_gas: Some((target, forwarded + 1)),
+                ..Default::default()
+            }),
+            Some((forwarded + 1, spent + 1))
+        );
+        assert_eq!(
+            run(&mut TestHost {
+                forced_call_gas: Some((target, 2_000_000)),
+                ..Default::default()
+            }),
+            None
+        );
+    }
+
+    #[test]
+    fn opcode_budget_halts_loop() {
+        let mut interpreter = new_interpreter(
+            &[opcode::JUMPDEST, opcode::PUSH0, opcode::JUMP],
+            Address::ZERO,
+        );
+        let mut budget = 30;
+        let table = instruction_table::<EthInterpreter, TestHost>();
+        let action = interpreter.run_plain_metered(&table, &mut TestHost::default(), &mut budget);
+
+        assert_eq!(
+            action.instruction_result(),
+            Some(crate::InstructionResult::OpcodeBudgetExhausted)
+        );
+        assert_eq!(budget, 0);
+        // Ten iterations of `JUMPDEST`, `PUSH0` and `JUMP` were executed.
+        assert_eq!(interpreter.gas.spent(), 10 * (1 + 2 + 8));
+    }
+
+    #[test]
+    fn static_violation_of_value_call() {
+        let run = |host: &mut TestHost| {
+            let mut interpreter = Interpreter::<EthInterpreter>::new(
+                SharedMemory::new(),
+                ExtBytecode::new(Bytecode::new_legacy(Bytes::from_static(&[
+                    opcode::PUSH0,
+                    opcode::PUSH0,
+                    opcode::PUSH0,
+                    opcode::PUSH0,
+                    opcode::PUSH1,
+                    1,
+                    opcode::PUSH1,
+                    0xFF,
+                    opcode::PUSH0,
+                    opcode::CALL,
+                ]))),
+                InputsImpl::default(),
+                true,
+                SpecId::PRAGUE,
+                1_000_000,
+            );
+            let table = instruction_table::<EthInterpreter, TestHost>();
+            interpreter.run_plain(&table, host)
+        };
+
+        // Value transfer in static context always violates by default.
+        assert_eq!(
+            run(&mut TestHost::default()).instruction_result(),
+            Some(crate::InstructionResult::CallNotAllowedInsideStatic)
+        );
+
+        match run(&mut TestHost {
+            static_value_call_allowed: true,
+            ..Default::default()
+        }) {
+            InterpreterAction::NewFrame(FrameInput::Call(inputs)) => {
+                assert!(inputs.is_static);
+                assert_eq!(inputs.value, CallValue::Transfer(U256::from(1)));
+            }
+            action => panic!("expected call frame, got {action:?}"),
+        }
+    }
+
+    #[test]
+    fn max_creates_halts_create_loop() {
+        let mut interpreter = new_interpreter(
+            &[
+                opcode::JUMPDEST,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::PUSH0,
+                opcode::CREATE,
+                opcode::POP,
+                opcode::PUSH0,
+                opcode::JUMP,
+            ],
+            Address::ZERO,
+        );
+        let mut host = TestHost {
+            max_creates: Some(3),
+            ..Default::default()
+        };
+        let table = instruction_table::<EthInterpreter, TestHost>();
+
+        let mut frames = 0;
+        let action = loop {
+            let action = interpreter.run_plain(&table, &mut host);
+            let InterpreterAction::NewFrame(FrameInput::Create(inputs)) = &action else {
+                break action;
+            };
+            // Resume as if the create failed without spending its gas.
+            frames += 1;
+            interpreter.gas.erase_cost(inputs.gas_limit);
+            let _ = interpreter.stack.push(U256::ZERO);
+        };
+
+        assert_eq!(frames, 3);
+        assert_eq!(host.creates, 4);
+        assert_eq!(
+            action.instruction_result(),
+            Some(crate::InstructionResult::CreateLimitExceeded)
+        );
+    }
+
+    #[test]
+    fn selfdestruct_refund_across_specs() {
+        let refund = |spec: SpecId, previously_destroyed: bool| {
+            let mut interpreter =
+                new_interpreter(&[opcode::PUSH1, 0, opcode::SELFDESTRUCT], Address::ZERO);
+            interpreter.runtime_flag.spec_id = spec;
+            let mut host = TestHost {
+                selfdestruct: Some(SelfDestructResult {
+                    had_value: false,
+                    target_exists: true,
+                    previously_destroyed,
+                }),
+                ..Default::default()
+            };
+            let table = instruction_table::<EthInterpreter, TestHost>();
+            let action = interpreter.run_plain(&table, &mut host);
+            assert_eq!(
+                action.instruction_result(),
+                Some(crate::InstructionResult::SelfDestruct)
+            );
+            interpreter.gas.refunded()
+        };
+
+        assert_eq!(refund(SpecId::ISTANBUL, false), gas::SELFDESTRUCT);
+        assert_eq!(refund(SpecId::BERLIN, false), gas::SELFDESTRUCT);
+        assert_eq!(refund(SpecId::LONDON, false), 0);
+        assert_eq!(refund(SpecId::PRAGUE, false), 0);
+
+        // Repeated destruction in the same transaction is not refunded.
+        assert_eq!(refund(SpecId::ISTANBUL, true), 0);
+        assert_eq!(refund(SpecId::LONDON, true), 0);
+    }
+
+    #[test]
+    fn cold_sload_reports_gas() {
+        let contract = address!("0x1000000000000000000000000000000000000001");
+        let run = |spec: SpecId| {
+            // Cold and warm load of slot 1.
+            let mut interpreter = new_interpreter(
+                &[
+                    opcode::PUSH1,
+                    1,
+                    opcode::SLOAD,
+                    opcode::PUSH1,
+                    1,
+                    opcode::SLOAD,
+                    opcode::STOP,
+                ],
+                contract,
+            );
+            interpreter.runtime_flag.spec_id = spec;
+            let mut host = TestHost::default();
+            let table = instruction_table::<EthInterpreter, TestHost>();
+            let _ = interpreter.run_plain(&table, &mut host);
+            host.cold_sloads
+        };
+
+        let key = StorageKey::from(1);
+        assert_eq!(run(SpecId::PRAGUE), [(contract, key, gas::COLD_SLOAD_COST)]);
+        assert_eq!(
+            run(SpecId::ISTANBUL),
+            [(contract, key, gas::ISTANBUL_SLOAD_GAS)]
+        );
+    }
+
+    #[test]
+    fn storage_access_order() {
+        // Load slot 1, store slot 2, load slot 1 again.
+        let mut interpreter = new_interpreter(
+            &[
+                opcode::PUSH1,
+                1,
+                opcode::SLOAD,
+                opcode::PUSH1,
+                2,
+                opcode::SSTORE,
+                opcode::PUSH1,
+                1,
+                opcode::SLOAD,
+                opcode::STOP,
+            ],
+            Address::ZERO,
+        );
+        let mut host = TestHost::default();
+        let table = instruction_table::<EthInterpreter, TestHost>();
+        let action = interpreter.run_plain(&table, &mut host);
+        assert_eq!(
+            action.instruction_result(),
+            Some(crate::InstructionResult::Stop)
+        );
+
+        let (one, two) = (StorageKey::from(1), StorageKey::from(2));
+        assert_eq!(
+            host.storage_accesses,
+            [(one, false), (two, true), (one, false)]
+        );
+    }
+
+    #[test]
+    fn can_create_rejects_non_whitelisted_caller() {
+        let deployer = address!("0x1000000000000000000000000000000000000001");
+        let code = &[
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::CREATE2,
+            opcode::STOP,
+        ];
+        let table = instruction_table::<EthInterpreter, TestHost>();
+        let mut host = TestHost {
+            deployers: Some(Vec::from([deployer])),
+            ..Default::default()
+        };
+
+        let mut interpreter = new_interpreter(code, Address::ZERO);
+        let action = interpreter.run_plain(&table, &mut host);
+        assert_eq!(
+            action.instruction_result(),
+            Some(crate::InstructionResult::CreateNotAllowed)
+        );
+        // Neither the create gas nor the create itself is recorded.
+        assert!(host.create_base_gas.is_empty());
+        assert_eq!(host.creates, 0);
+
+        let mut interpreter = new_interpreter(code, deployer);
+        match interpreter.run_plain(&table, &mut host) {
+            InterpreterAction::NewFrame(FrameInput::Create(inputs)) => {
+                assert_eq!(inputs.caller, deployer);
+                assert_eq!(inputs.scheme, CreateScheme::Create2 { salt: U256::ZERO });
+            }
+            action => panic!("expected create frame, got {action:?}"),
+        }
+    }
+
+    #[test]
+    fn max_call_input_size_halts_oversized_call() {
+        let run = |max_call_input: usize| {
+            // Calls itself with 64 bytes of input.
+            let mut interpreter = new_interpreter(
+                &[
+                    opcode::PUSH0,
+                    opcode::PUSH0,
+                    opcode::PUSH1,
+                    0x40,
+                    opcode::PUSH0,
+                    opcode::PUSH0,
+                    opcode::ADDRESS,
+                    opcode::GAS,
+                    opcode::CALL,
+                    opcode::STOP,
+                ],
+                Address::ZERO,
+            );
+            let mut host = TestHost {
+                max_call_input: Some(max_call_input),
+                ..Default::default()
+            };
+            let table = instruction_table::<EthInterpreter, TestHost>();
+            let action = interpreter.run_plain(&table, &mut host);
+            (action, interpreter.memory.len())
+        };
+
+        let (action, memory_size) = run(63);
+        assert_eq!(
+            action.instruction_result(),
+            Some(crate::InstructionResult::CallInputSizeLimit)
+        );
+        // Rejected before the memory is expanded.
+        assert_eq!(memory_size, 0);
+
+        match run(64) {
+            (InterpreterAction::NewFrame(FrameInput::Call(inputs)), 64) => {
+                assert_eq!(inputs.input.len(), 64);
+            }
+            (action, _) => panic!("expected call frame, got {action:?}"),
+        }
+    }
+
+    #[test]
+    fn stipend_of_value_call() {
+        use opcode::{CALL, CALLCODE, PUSH0, PUSH1, PUSH2};
+
+        let run = |code: &'static [u8]| {
+            let mut interpreter = new_interpreter(code, Address::ZERO);
+            let mut host = TestHost::default();
+            let table = instruction_table::<EthInterpreter, TestHost>();
+            let gas_limit = match interpreter.run_plain(&table, &mut host) {
+                InterpreterAction::NewFrame(FrameInput::Call(inputs)) => inputs.gas_limit,
+                action => panic!("expected call frame, got {action:?}"),
+            };
+            (gas_limit, host.stipends)
+        };
+        // Forwards 1000 gas with a value of one, or no value.
+        let stipend = (1000 + gas::CALL_STIPEND, vec![gas::CALL_STIPEND]);
+        assert_eq!(
+            run(&[PUSH0, PUSH0, PUSH0, PUSH0, PUSH1, 1, PUSH1, 0xFF, PUSH2, 0x03, 0xE8, CALL]),
+            stipend
+        );
+        assert_eq!(
+            run(&[PUSH0, PUSH0, PUSH0, PUSH0, PUSH1, 1, PUSH1, 0xFF, PUSH2, 0x03, 0xE8, CALLCODE]),
+            stipend
+        );
+        assert_eq!(
+            run(&[PUSH0, PUSH0, PUSH0, PUSH0, PUSH0, PUSH1, 0xFF, PUSH2, 0x03, 0xE8, CALL]),
+            (1000, vec![])
+        );
+    }
+
+    #[test]
+    fn new_account_cost_of_value_call() {
+        use opcode::{CALL, CALLCODE, DELEGATECALL, PUSH0, PUSH1, STATICCALL, STOP};
+
+        let spent = |code: &'static [u8], empty_accounts: bool| {
+            let mut interpreter = new_interpreter(code, Address::ZERO);
+            let mut host = TestHost {
+                empty_accounts,
+                new_account_cost: Some(1000),
+                ..Default::default()
+            };
+            let table = instruction_table::<EthInterpreter, TestHost>();
+            let _ = interpreter.run_plain(&table, &mut host);
+            interpreter.gas.spent()
+        };
+
+        // Value transfer to an empty account charges the configured cost.
+        let call = &[
+            PUSH0, PUSH0, PUSH0, PUSH0, PUSH1, 1, PUSH1, 0xFF, PUSH0, CALL, STOP,
+        ];
+        assert_eq!(spent(call, true), spent(call, false) + 1000);
+
+        // Other calls don't create the account.
+        for code in [
+            &[
+                PUSH0, PUSH0, PUSH0, PUSH0, PUSH1, 1, PUSH1, 0xFF, PUSH0, CALLCODE, STOP,
+            ][..],
+            &[
+                PUSH0,
+                PUSH0,
+                PUSH0,
+                PUSH0,
+                PUSH1,
+                0xFF,
+                PUSH0,
+                DELEGATECALL,
+                STOP,
+            ],
+            &[
+                PUSH0, PUSH0, PUSH0, PUSH0, PUSH1, 0xFF, PUSH0, STATICCALL, STOP,
+            ],
+        ] {
+            assert_eq!(spent(code, true), spent(code, false));
+        }
+    }
+
+    #[test]
+    fn strict_gas_forwarding_of_call_and_create() {
+        use opcode::{CALL, CREATE, PUSH0, PUSH1, PUSH4};
+
+        // Call requesting more gas than available, and create.
+        let call = &[
+            PUSH0, PUSH0, PUSH0, PUSH0, PUSH0, PUSH1, 0xFF, PUSH4, 0xFF, 0xFF, 0xFF, 0xFF, CALL,
+        ];
+        let create = &[PUSH0, PUSH0, PUSH0, CREATE];
+        let run = |code: &'static [u8], strict_gas_forwarding: bool| {
+            let mut interpreter = new_interpreter(code, Address::ZERO);
+            let mut host = TestHost {
+                strict_gas_forwarding,
+                ..Default::default()
+            };
+            let table = instruction_table::<EthInterpreter, TestHost>();
+            let forwarded = match interpreter.run_plain(&table, &mut host) {
+                InterpreterAction::NewFrame(FrameInput::Call(inputs)) => inputs.gas_limit,
+                InterpreterAction::NewFrame(FrameInput::Create(inputs)) => inputs.gas_limit,
+                action => panic!("expected new frame, got {action:?}"),
+            };
+            (forwarded, interpreter.gas.remaining())
+        };
+
+        for code in [&call[..], &create[..]] {
+            // 63/64 rule keeps a 64th of the gas available to the frame.
+            let (forwarded, kept) = run(code, false);
+            assert_eq!(kept, (forwarded + kept) / 64);
+
+            // Strict forwarding gives all of it.
+            assert_eq!(run(code, true), (forwarded + kept, 0));
+        }
+    }
+}
@@ -1,42 +1,91 @@
 use crate::{
     gas,
-    interpreter::Interpreter,
+    interpreter::{num_words, Interpreter},
     interpreter_types::{InterpreterTypes, MemoryTr, RuntimeFlag, StackTr},
+    Host, InstructionResult,
 };
 use context_interface::{context::StateLoad, journaled_state::AccountLoad};
 use core::{cmp::min, ops::Range};
-use primitives::{hardfork::SpecId::*, U256};
+use primitives::{
+    hardfork::SpecId::{self, *},
+    Address, U256,
+};
 
 /// Gets memory input and output ranges for call instructions.
+///
+/// Memory expansion is charged with [`Host::memory_expansion_cost`]. Input larger than
+/// [`Host::max_call_input_size`] halts before memory is expanded.
 #[inline]
 pub fn get_memory_input_and_out_ranges(
     interpreter: &mut Interpreter<impl InterpreterTypes>,
+    host: &(impl Host + ?Sized),
 ) -> Option<(Range<usize>, Range<usize>)> {
     popn!([in_offset, in_len, out_offset, out_len], interpreter, None);
 
-    let mut in_range = resize_memory(interpreter, in_offset, in_len)?;
+    if in_len > U256::from(host.max_call_input_size()) {
+        interpreter.halt(InstructionResult::CallInputSizeLimit);
+        return None;
+    }
+
+    let mut in_range = resize_memory(interpreter, host, in_offset, in_len)?;
 
     if !in_range.is_empty() {
         let offset = interpreter.memory.local_memory_offset();
         in_range = in_range.start.saturating_add(offset)..in_range.end.saturating_add(offset);
     }
 
-    let ret_range = resize_memory(interpreter, out_offset, out_len)?;
+    let ret_range = resize_memory(interpreter, host, out_offset, out_len)?;
     Some((in_range, ret_range))
 }
 
+/// Pushes `address` to the call stack of the host with [`Host::on_call_enter`].
+///
+/// Returns `false` and halts with [`InstructionResult::ReentrancyLimit`] if `address` is already
+/// in the call stack more than [`Host::max_reentrancy`] times.
+#[inline]
+pub fn enter_call(
+    interpreter: &mut Interpreter<impl InterpreterTypes>,
+    host: &mut (impl Host + ?Sized),
+    address: Address,
+) -> bool {
+    let max_reentrancy = host.max_reentrancy();
+    if max_reentrancy != usize::MAX && host.call_stack_occurrences(address) > max_reentrancy {
+        interpreter.halt(InstructionResult::ReentrancyLimit);
+        return false;
+    }
+    host.on_call_enter(address);
+    true
+}
+
 /// Resize memory and return range of memory.
 /// If `len` is 0 dont touch memory and return `usize::MAX` as offset and 0 as length.
+///
+/// Memory expansion is charged with [`Host::memory_expansion_cost`].
 #[inline]
 pub fn resize_memory(
     interpreter: &mut Interpreter<impl InterpreterTypes>,
+    host: &(impl Host + ?Sized),
     offset: U256,
     len: U256,
 ) -> Option<Range<usize>> {
     let len = as_usize_or_fail_ret!(interpreter, len, None);
     let offset = if len != 0 {
         let offset = as_usize_or_fail_ret!(interpreter, offset, None);
-        resize_memory!(interpreter, offset, len, None);
+        let words_num = num_words(offset.saturating_add(len));
+        match interpreter
+            .gas
+            .record_memory_expansion_with(words_num, |current, new| {
+                host.memory_expansion_cost(current, new)
+            }) {
+            gas::MemoryExtensionResult::Extended => {
+                interpreter.memory.resize(words_num * 32);
+            }
+            gas::MemoryExtensionResult::OutOfGas => {
+                interpreter.halt(InstructionResult::MemoryOOG);
+                return None;
+            }
+            gas::MemoryExtensionResult::Same => (),
+        };
         offset
     } else {
         usize::MAX //unrealistic value so we are sure it is not used
@@ -45,27 +94,90 @@ pub fn resize_memory(
 }
 
 /// Calculates gas cost and limit for call instructions.
+///
+/// The gas knobs of the host are applied:
+/// - The call cost is scaled by [`Host::gas_cost_multiplier`] and the new account cost is
+///   [`Host::new_account_cost`].
+/// - [`Host::forced_call_gas`] of `target`, the address popped from the stack, replaces the
+///   forwarded gas computed with the 63/64 rule. The call cost is still charged.
+/// - With [`Host::strict_gas_forwarding`] the requested gas is forwarded up to the remaining
+///   gas, without the 63/64 reservation.
+/// - If the forwarded gas is below [`Host::min_forwarded_gas`] the interpreter halts with out
+///   of gas.
 #[inline]
 pub fn calc_call_gas(
     interpreter: &mut Interpreter<impl InterpreterTypes>,
+    host: &(impl Host + ?Sized),
     account_load: StateLoad<AccountLoad>,
     has_transfer: bool,
     local_gas_limit: u64,
+    target: Address,
 ) -> Option<u64> {
-    let call_cost = gas::call_cost(
+    let call_cost = gas::call_cost_with_new_account_cost(
         interpreter.runtime_flag.spec_id(),
         has_transfer,
         account_load,
+        host.new_account_cost(),
+    );
+    gas!(
+        interpreter,
+        call_cost.saturating_mul(host.gas_cost_multiplier()),
+        None
     );
-    gas!(interpreter, call_cost, None);
 
     // EIP-150: Gas cost changes for IO-heavy operations
-    let gas_limit = if interpreter.runtime_flag.spec_id().is_enabled_in(TANGERINE) {
+    let gas_limit = if let Some(forced_call_gas) = host.forced_call_gas(target) {
+        forced_call_gas
+    } else if host.strict_gas_forwarding() {
+        min(interpreter.gas.remaining(), local_gas_limit)
+    } else if interpreter.runtime_flag.spec_id().is_enabled_in(TANGERINE) {
         // Take l64 part of gas_limit
         min(interpreter.gas.remaining_63_of_64_parts(), local_gas_limit)
     } else {
         local_gas_limit
     };
 
+    if gas_limit < host.min_forwarded_gas() {
+        interpreter.halt(InstructionResult::OutOfGas);
+        return None;
+    }
+
     Some(gas_limit)
 }
+
+/// Returns the gas that `STATICCALL` forwards to the called frame, without executing it.
+///
+/// `remaining` is the gas remaining before the instruction and `local_gas_limit` is the gas
+/// limit popped from the stack. Same as in the instruction, the account is not considered empty
+/// and value is not transferred.
+///
+/// `memory_expansion_cost` is the cost of expanding memory to cover the input and output ranges,
+/// zero if they are empty or already in memory. On mainnet it is
+/// `gas::memory_gas(new_words) - gas::memory_gas(current_words)`. The instruction charges it
+/// before the call cost and the 63/64 rule, so it reduces the forwarded gas.
+///
+/// Returns zero if `remaining` does not cover the memory expansion and the call cost, in which
+/// case the instruction halts with out of gas.
+#[inline]
+pub fn preflight_static_call_gas(
+    spec: SpecId,
+    mut account_load: StateLoad<AccountLoad>,
+    local_gas_limit: u64,
+    remaining: u64,
+    memory_expansion_cost: u64,
+) -> u64 {
+    account_load.data.is_empty = false;
+    let Some(remaining) = remaining
+        .checked_sub(memory_expansion_cost)
+        .and_then(|remaining| remaining.checked_sub(gas::call_cost(spec, false, account_load)))
+    else {
+        return 0;
+    };
+
+    // EIP-150: Gas cost changes for IO-heavy operations
+    if spec.is_enabled_in(TANGERINE) {
+        min(remaining - remaining / 64, local_gas_limit)
+    } else {
+        local_gas_limit
+    }
+}
@@ -192,20 +192,21 @@ pub fn blockhash<WIRE: InterpreterTypes, H: Host + ?Sized>(
 pub fn sload<WIRE: InterpreterTypes, H: Host + ?Sized>(context: InstructionContext<'_, H, WIRE>) {
     popn_top!([], index, context.interpreter);
 
-    let Some(value) = context
-        .host
-        .sload(context.interpreter.input.target_address(), *index)
-    else {
+    let address = context.interpreter.input.target_address();
+    let Some(value) = context.host.sload(address, *index) else {
         context
             .interpreter
             .halt(InstructionResult::FatalExternalError);
         return;
     };
+    context.host.on_storage_access(address, *index, false);
 
-    gas!(
-        context.interpreter,
-        gas::sload_cost(context.interpreter.runtime_flag.spec_id(), value.is_cold)
-    );
+    let cost = gas::sload_cost(context.interpreter.runtime_flag.spec_id(), value.is_cold)
+        .saturating_mul(context.host.gas_cost_multiplier());
+    gas!(context.interpreter, cost);
+    if value.is_cold {
+        context.host.on_cold_sload(address, *index, cost);
+    }
     *index = value.data;
 }
 
@@ -217,16 +218,14 @@ pub fn sstore<WIRE: InterpreterTypes, H: Host + ?Sized>(context: InstructionCont
 
     popn!([index, value], context.interpreter);
 
-    let Some(state_load) =
-        context
-            .host
-            .sstore(context.interpreter.input.target_address(), index, value)
-    else {
+    let address = context.interpreter.input.target_address();
+    let Some(state_load) = context.host.sstore(address, index, value) else {
         context
             .interpreter
             .halt(InstructionResult::FatalExternalError);
         return;
     };
+    context.host.on_storage_access(address, index, true);
 
     // EIP-1706 Disable SSTORE with gasleft lower than call stipend
     if context
@@ -248,6 +247,7 @@ pub fn sstore<WIRE: InterpreterTypes, H: Host + ?Sized>(context: InstructionCont
             &state_load.data,
             state_load.is_cold
         )
+        .saturating_mul(context.host.gas_cost_multiplier())
     );
 
     context.interpreter.gas.record_refund(gas::sstore_refund(
@@ -293,7 +293,7 @@ pub fn log<const N: usize, H: Host + ?Sized>(
 
     popn!([offset, len], context.interpreter);
     let len = as_usize_or_fail!(context.interpreter, len);
-    gas_or_fail!(context.interpreter, gas::log_cost(N as u8, len as u64));
+    gas!(context.interpreter, context.host.log_cost(N, len));
     let data = if len == 0 {
         Bytes::new()
     } else {
@@ -329,7 +329,7 @@ pub fn selfdestruct<WIRE: InterpreterTypes, H: Host + ?Sized>(
     popn!([target], context.interpreter);
     let target = target.into_address();
 
-    let Some(res) = context
+    let Some(mut res) = context
         .host
         .selfdestruct(context.interpreter.input.target_address(), target)
     else {
@@ -338,17 +338,16 @@ pub fn selfdestruct<WIRE: InterpreterTypes, H: Host + ?Sized>(
             .halt(InstructionResult::FatalExternalError);
         return;
     };
+    res.data.target_exists = !context
+        .host
+        .is_account_empty_override(target, !res.target_exists);
 
     // EIP-3529: Reduction in refunds
-    if !context
-        .interpreter
-        .runtime_flag
-        .spec_id()
-        .is_enabled_in(LONDON)
-        && !res.previously_destroyed
-    {
-        context.interpreter.gas.record_refund(gas::SELFDESTRUCT)
-    }
+    let refund = context.host.selfdestruct_refund(
+        context.interpreter.runtime_flag.spec_id(),
+        !res.previously_destroyed,
+    );
+    context.interpreter.gas.record_refund(refund);
 
     gas!(
         context.interpreter,
@@ -137,7 +137,7 @@ pub fn calldatasize<WIRE: InterpreterTypes, H: ?Sized>(context: InstructionConte
     gas!(context.interpreter, gas::BASE);
     push!(
         context.interpreter,
-        U256::from(context.interpreter.input.input().len())
+        U256::from(context.interpreter.input.input_len())
     );
 }
 
@@ -107,7 +107,11 @@ impl<EXT: Default> Interpreter<EthInterpreter<EXT>> {
             return_data: Default::default(),
             memory,
             input,
-            runtime_flag: RuntimeFlags { is_static, spec_id },
+            runtime_flag: RuntimeFlags {
+                is_static,
+                spec_id,
+                depth: 0,
+            },
             extend: Default::default(),
         }
     }
@@ -143,7 +147,11 @@ impl<EXT: Default> Interpreter<EthInterpreter<EXT>> {
         return_data.0.clear();
         *memory_ref = memory;
         *input_ref = input;
-        *runtime_flag = RuntimeFlags { spec_id, is_static };
+        *runtime_flag = RuntimeFlags {
+            spec_id,
+            is_static,
+            depth: 0,
+        };
         *extend = EXT::default();
     }
 
@@ -261,6 +269,43 @@ impl<IW: InterpreterTypes> Interpreter<IW> {
 
         self.take_next_action()
     }
+
+    /// Executes the interpreter until it returns or stops, metering the executed instructions.
+    ///
+    /// `budget` is decremented for each executed instruction. If it reaches zero before the
+    /// interpreter stops, it halts with [`InstructionResult::OpcodeBudgetExhausted`]. The stack
+    /// length is recorded with [`StackTr::record_high_water`] after each instruction.
+    #[inline]
+    pub fn run_plain_metered<H: ?Sized>(
+        &mut self,
+        instruction_table: &InstructionTable<IW, H>,
+        host: &mut H,
+        budget: &mut u64,
+    ) -> InterpreterAction {
+        while self.bytecode.is_not_end() {
+            if *budget == 0 {
+                self.halt(InstructionResult::OpcodeBudgetExhausted);
+                break;
+            }
+            *budget -= 1;
+
+            // Get current opcode.
+            let opcode = self.bytecode.opcode();
+
+            // SAFETY: See `run_plain`.
+            self.bytecode.relative_jump(1);
+            let context = InstructionContext {
+                interpreter: self,
+                host,
+            };
+            // Execute instruction.
+            instruction_table[opcode as usize](context);
+            self.stack.record_high_water();
+        }
+        self.bytecode.revert_to_previous_pointer();
+
+        self.take_next_action()
+    }
 }
 
 /// The result of an interpreter operation.
@@ -359,4 +404,72 @@ mod tests {
             "Program counter should be preserved"
         );
     }
+
+    #[test]
+    fn input_len_matches_calldatasize() {
+        use super::*;
+        use crate::{host::DummyHost, instructions::instruction_table, CallInput};
+        use bytecode::{opcode, Bytecode};
+        use primitives::{Bytes, U256};
+
+        let calldatasize = |input: CallInput| {
+            let bytecode =
+                Bytecode::new_legacy(Bytes::from_static(&[opcode::CALLDATASIZE, opcode::STOP]));
+            let mut interpreter = Interpreter::<EthInterpreter>::new(
+                SharedMemory::new(),
+                ExtBytecode::new(bytecode),
+                InputsImpl {
+                    input,
+                    ..Default::default()
+                },
+                false,
+                SpecId::default(),
+                u64::MAX,
+            );
+            let table = instruction_table::<EthInterpreter, DummyHost>();
+            let _ = interpreter.run_plain(&table, &mut DummyHost);
+            let size = interpreter.stack.pop().unwrap();
+            assert_eq!(size, U256::from(interpreter.input.input_len()));
+            interpreter.input.input_len()
+        };
+
+        assert_eq!(
+            calldatasize(CallInput::Bytes(Bytes::from_static(&[1, 2, 3]))),
+            3
+        );
+        assert_eq!(calldatasize(CallInput::SharedBuffer(4..36)), 32);
+        assert_eq!(calldatasize(CallInput::default()), 0);
+    }
+
+    #[test]
+    fn log2_cost() {
+        use super::*;
+        use crate::{host::DummyHost, instructions::instruction_table};
+        use bytecode::{opcode, Bytecode};
+        use primitives::Bytes;
+
+        let bytecode = Bytecode::new_legacy(Bytes::from_static(&[
+            opcode::PUSH0,
+            opcode::PUSH0,
+            opcode::PUSH1,
+            32,
+            opcode::PUSH0,
+            opcode::LOG2,
+            opcode::STOP,
+        ]));
+        let mut interpreter = Interpreter::<EthInterpreter>::new(
+            SharedMemory::new(),
+            ExtBytecode::new(bytecode),
+            InputsImpl::default(),
+            false,
+            SpecId::default(),
+            u64::MAX,
+        );
+        assert_eq!(DummyHost.log_cost(2, 32), 375 + 2 * 375 + 8 * 32);
+
+        let table = instruction_table::<EthInterpreter, DummyHost>();
+        let _ = interpreter.run_plain(&table, &mut DummyHost);
+        // Pushes and one word of memory expansion.
+        assert_eq!(interpreter.gas.spent(), 3 * 2 + 3 + 1381 + 3);
+    }
 }
@@ -12,6 +12,8 @@ pub struct RuntimeFlags {
     pub is_static: bool,
     /// The current EVM specification ID.
     pub spec_id: SpecId,
+    /// Call depth of the frame, zero for the initial transaction frame.
+    pub depth: usize,
 }
 
 impl RuntimeFlag for RuntimeFlags {
@@ -22,4 +24,8 @@ impl RuntimeFlag for RuntimeFlags {
     fn spec_id(&self) -> SpecId {
         self.spec_id
     }
+
+    fn depth(&self) -> usize {
+        self.depth
+    }
 }
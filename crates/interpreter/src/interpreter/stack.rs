@@ -14,6 +14,9 @@ pub const STACK_LIMIT: usize = 1024;
 pub struct Stack {
     /// The underlying data of the stack.
     data: Vec<U256>,
+    /// Highest length of the stack recorded with [`StackTr::record_high_water`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    high_water: usize,
 }
 
 impl fmt::Display for Stack {
@@ -43,6 +46,7 @@ impl Clone for Stack {
         // STACK_LIMIT capacity, which is crucial for the safety and correctness of other methods.
         let mut new_stack = Self::new();
         new_stack.data.extend_from_slice(&self.data);
+        new_stack.high_water = self.high_water;
         new_stack
     }
 }
@@ -55,6 +59,12 @@ impl StackTr for Stack {
     #[inline]
     fn clear(&mut self) {
         self.data.clear();
+        self.high_water = 0;
+    }
+
+    #[inline]
+    fn record_high_water(&mut self) {
+        self.high_water = self.high_water.max(self.data.len());
     }
 
     #[inline]
@@ -95,13 +105,24 @@ impl Stack {
         Self {
             // SAFETY: Expansion functions assume that capacity is `STACK_LIMIT`.
             data: Vec::with_capacity(STACK_LIMIT),
+            high_water: 0,
         }
     }
 
     /// Instantiate a new invalid Stack.
     #[inline]
     pub fn invalid() -> Self {
-        Self { data: Vec::new() }
+        Self {
+            data: Vec::new(),
+            high_water: 0,
+        }
+    }
+
+    /// Returns the highest length of the stack recorded with [`StackTr::record_high_water`]
+    /// since it was created or cleared.
+    #[inline]
+    pub fn high_water(&self) -> usize {
+        self.high_water
     }
 
     /// Returns the length of the stack in words.
@@ -383,7 +404,8 @@ impl<'de> serde::Deserialize<'de> for Stack {
             )));
         }
         data.reserve(STACK_LIMIT - data.len());
-        Ok(Self { data })
+        let high_water = data.len();
+        Ok(Self { data, high_water })
     }
 }
 
@@ -448,6 +470,27 @@ mod tests {
         });
     }
 
+    #[test]
+    fn high_water_is_recorded() {
+        run(|stack| {
+            assert!(stack.push(U256::from(1)));
+            assert!(stack.dup(1));
+            stack.push_slice(&[0; 64]).unwrap();
+            // Pushes are not tracked until the length is recorded.
+            assert_eq!(stack.high_water(), 0);
+            stack.record_high_water();
+            assert_eq!(stack.high_water(), 4);
+
+            stack.pop().unwrap();
+            stack.pop().unwrap();
+            stack.record_high_water();
+            assert_eq!(stack.high_water(), 4);
+
+            StackTr::clear(stack);
+            assert_eq!(stack.high_water(), 0);
+        });
+    }
+
     #[test]
     fn stack_clone() {
         // Test cloning an empty stack
@@ -50,6 +50,13 @@ pub trait InputsTr {
     fn input(&self) -> &CallInput;
     /// Returns call value of the call.
     fn call_value(&self) -> U256;
+    /// Returns length of the input of the call, as pushed by [`bytecode::opcode::CALLDATASIZE`].
+    ///
+    /// The input is not read, so this is cheap for [`CallInput::SharedBuffer`] as well.
+    #[inline]
+    fn input_len(&self) -> usize {
+        self.input().len()
+    }
 }
 
 /// Trait needed for legacy bytecode.
@@ -161,6 +168,15 @@ pub trait StackTr {
     /// Clears the stack.
     fn clear(&mut self);
 
+    /// Records the current length as the highest length of the stack if it is higher.
+    ///
+    /// Called at instruction boundaries by [`Interpreter::run_plain_metered`](crate::Interpreter::run_plain_metered)
+    /// when the host tracks the stack high water, pushes themselves don't track it.
+    ///
+    /// Default implementation does not record it.
+    #[inline]
+    fn record_high_water(&mut self) {}
+
     /// Pushes values to the stack.
     ///
     /// Returns `true` if push was successful, `false` if stack overflow.
@@ -271,6 +287,20 @@ pub trait RuntimeFlag {
     fn is_static(&self) -> bool;
     /// Returns the current EVM specification ID.
     fn spec_id(&self) -> SpecId;
+    /// Returns the call depth of the current frame, zero for the initial transaction frame.
+    ///
+    /// Journal checkpoint depth is one higher as every frame, the initial one included,
+    /// is executed inside its own checkpoint.
+    ///
+    /// Default implementation returns zero, for flags that don't track the call depth every
+    /// frame is reported as the initial one.
+    fn depth(&self) -> usize {
+        0
+    }
+    /// Returns true if the current frame is the initial transaction frame.
+    fn is_top_level(&self) -> bool {
+        self.depth() == 0
+    }
 }
 
 /// Trait for interpreter execution.
@@ -33,7 +33,7 @@ pub use context_interface::{
     CreateScheme,
 };
 pub use gas::{Gas, InitialAndFloorGas};
-pub use host::Host;
+pub use host::{AuthResult, Host, StaticSensitiveOp};
 pub use instruction_context::InstructionContext;
 pub use instruction_result::*;
 pub use instructions::{instruction_table, Instruction, InstructionTable};
@@ -16,11 +16,11 @@ use revm::{
         evm::FrameTr,
         handler::EvmTrError,
         post_execution::{self, reimburse_caller},
-        pre_execution::validate_account_nonce_and_code,
+        pre_execution::{gas_balance_spending, validate_account_nonce_and_code},
         EthFrame, EvmTr, FrameResult, Handler, MainnetHandler,
     },
     inspector::{Inspector, InspectorEvmTr, InspectorHandler},
-    interpreter::{interpreter::EthInterpreter, interpreter_action::FrameInit, Gas},
+    interpreter::{interpreter::EthInterpreter, interpreter_action::FrameInit, Gas, Host},
     primitives::{hardfork::SpecId, U256},
 };
 use std::boxed::Box;
@@ -101,13 +101,16 @@ where
         let ctx = evm.ctx();
 
         let basefee = ctx.block().basefee() as u128;
-        let blob_price = ctx.block().blob_gasprice().unwrap_or_default();
+        let blob_price = ctx
+            .blob_base_fee_override()
+            .unwrap_or_else(|| ctx.block().blob_gasprice().unwrap_or_default());
         let is_deposit = ctx.tx().tx_type() == DEPOSIT_TRANSACTION_TYPE;
         let spec = ctx.cfg().spec();
         let block_number = ctx.block().number();
         let is_balance_check_disabled = ctx.cfg().is_balance_check_disabled();
         let is_eip3607_disabled = ctx.cfg().is_eip3607_disabled();
         let is_nonce_check_disabled = ctx.cfg().is_nonce_check_disabled();
+        let min_priority_fee = ctx.min_priority_fee().saturating_to();
 
         let mint = if is_deposit {
             ctx.tx().mint().unwrap_or_default()
@@ -187,13 +190,9 @@ where
             }
             .into());
         } else {
-            let effective_balance_spending =
-                tx.effective_balance_spending(basefee, blob_price).expect(
-                    "effective balance is always smaller than max balance so it can't overflow",
-                );
-
             // subtracting max balance spending with value that is going to be deducted later in the call.
-            let gas_balance_spending = effective_balance_spending - tx.value();
+            let gas_balance_spending =
+                gas_balance_spending(tx, basefee, blob_price, min_priority_fee);
 
             // If the transaction is not a deposit transaction, subtract the L1 data fee from the
             // caller's balance directly after minting the requested amount of ETH.
@@ -306,6 +305,10 @@ where
         frame_result: &mut <<Self::Evm as EvmTr>::Frame as FrameTr>::FrameResult,
         eip7702_refund: i64,
     ) {
+        if !self.refunds_enabled(evm) {
+            frame_result.gas_mut().set_refund(0);
+            return;
+        }
         frame_result.gas_mut().record_refund(eip7702_refund);
 
         let is_deposit = evm.ctx().tx().tx_type() == DEPOSIT_TRANSACTION_TYPE;
@@ -505,7 +508,7 @@ mod tests {
         database_interface::EmptyDB,
         handler::EthFrame,
         interpreter::{CallOutcome, InstructionResult, InterpreterResult},
-        primitives::{bytes, Address, Bytes, B256},
+        primitives::{bytes, Address, Bytes, TxKind, B256},
         state::AccountInfo,
     };
     use rstest::rstest;
@@ -1035,6 +1038,64 @@ mod tests {
             .is_touched());
     }
 
+    #[test]
+    fn test_min_priority_fee_is_charged_and_rewarded() {
+        const CALLER: Address = Address::with_last_byte(1);
+        const BALANCE: u64 = 10_000_000;
+
+        let mut db = InMemoryDB::default();
+        db.insert_account_info(
+            CALLER,
+            AccountInfo {
+                balance: U256::from(BALANCE),
+                ..Default::default()
+            },
+        );
+        let mut evm = Context::op()
+            .with_db(db)
+            .modify_block_chained(|block| block.basefee = 10)
+            .modify_cfg_chained(|cfg| cfg.min_priority_fee = 5)
+            .with_tx(
+                OpTransaction::builder()
+                    .base(
+                        TxEnv::builder()
+                            .caller(CALLER)
+                            .kind(TxKind::Call(Address::with_last_byte(2)))
+                            .gas_limit(100_000)
+                            .gas_price(30)
+                            .gas_priority_fee(Some(1)),
+                    )
+                    .enveloped_tx(Some(bytes!("FACADE")))
+                    .source_hash(B256::ZERO)
+                    .build()
+                    .unwrap(),
+            )
+            .build_op();
+
+        let mut handler =
+            OpHandler::<_, EVMError<_, OpTransactionError>, EthFrame<EthInterpreter>>::new();
+        let gas_used = U256::from(handler.run(&mut evm).unwrap().gas_used());
+
+        let mut balance = |address| {
+            evm.ctx()
+                .journal_mut()
+                .load_account(address)
+                .unwrap()
+                .info
+                .balance
+        };
+        let paid = U256::from(BALANCE) - balance(CALLER);
+        let reward = balance(Address::ZERO);
+        let fees = balance(BASE_FEE_RECIPIENT)
+            + balance(L1_FEE_RECIPIENT)
+            + balance(OPERATOR_FEE_RECIPIENT);
+
+        // Tip of one is raised to the floor of five, the caller pays what is distributed.
+        assert_eq!(paid, U256::from(15) * gas_used);
+        assert_eq!(reward, U256::from(5) * gas_used);
+        assert_eq!(paid, reward + fees);
+    }
+
     #[rstest]
     #[case::deposit(true)]
     #[case::dyn_fee(false)]
@@ -10,6 +10,7 @@ use crate::{
     OpSpecId,
 };
 use revm::{
+    context_interface::HostHooks,
     database_interface::Database,
     interpreter::{
         gas::{get_tokens_in_calldata, NON_ZERO_BYTE_MULTIPLIER_ISTANBUL, STANDARD_TOKEN_COST},
@@ -54,6 +55,9 @@ pub struct L1BlockInfo {
     pub tx_l1_cost: Option<U256>,
 }
 
+/// Optimism keeps the mainnet behavior of the interpreter host.
+impl HostHooks for L1BlockInfo {}
+
 impl L1BlockInfo {
     /// Try to fetch the L1 block info from the database.
     pub fn try_fetch<DB: Database>(
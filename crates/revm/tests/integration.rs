@@ -2,15 +2,17 @@
 mod common;
 
 use common::compare_or_save_testdata;
-use context::ContextTr;
+use context::{result::EVMError, ContextSetters, ContextTr, JournalTr};
+use core::convert::Infallible;
 use database::BENCH_CALLER;
 use primitives::{address, b256, hardfork::SpecId, Bytes, TxKind, KECCAK_EMPTY};
 use revm::{
     bytecode::opcode,
     context::TxEnv,
-    database::{BenchmarkDB, BENCH_TARGET},
-    primitives::U256,
-    state::Bytecode,
+    database::{BenchmarkDB, CacheDB, EmptyDB, BENCH_TARGET},
+    handler::{Handler, MainnetHandler},
+    primitives::{StorageKey, StorageValue, U256},
+    state::{AccountInfo, Bytecode},
     Context, ExecuteEvm, MainBuilder, MainContext,
 };
 use state::AccountStatus;
@@ -66,6 +68,48 @@ fn test_selfdestruct_multi_tx() {
     );
 }
 
+/// Tests that a CALL in the next transaction to an account selfdestructed in the previous
+/// transaction sees an empty account with cleared storage.
+#[test]
+fn test_call_selfdestructed_account_multi_tx() {
+    let mut db = CacheDB::new(EmptyDB::default());
+    db.insert_account_info(
+        BENCH_TARGET,
+        AccountInfo::default().with_code(Bytecode::new_legacy(SELFDESTRUCT_BYTECODE.into())),
+    );
+    db.insert_account_storage(BENCH_TARGET, StorageKey::from(1), StorageValue::from(7))
+        .unwrap();
+    let mut evm = Context::mainnet()
+        .modify_cfg_chained(|cfg| cfg.spec = SpecId::LONDON)
+        .with_db(db)
+        .build_mainnet();
+
+    // trigger selfdestruct
+    let result1 = evm
+        .transact_one(TxEnv::builder_for_bench().build_fill())
+        .unwrap();
+    assert!(result1.is_success());
+
+    // call on destroyed account executes empty code.
+    let result2 = evm
+        .transact_one(TxEnv::builder_for_bench().nonce(1).build_fill())
+        .unwrap();
+    assert!(result2.is_success());
+    assert!(result2.output().unwrap().is_empty());
+
+    let journal = evm.ctx.journal_mut();
+    let destroyed_acc = journal.state.get(&BENCH_TARGET).unwrap();
+    assert_eq!(destroyed_acc.info.code_hash, KECCAK_EMPTY);
+    assert!(destroyed_acc.storage.is_empty());
+    assert_eq!(
+        journal
+            .sload(BENCH_TARGET, StorageKey::from(1))
+            .unwrap()
+            .data,
+        StorageValue::ZERO
+    );
+}
+
 /// Tests multiple transactions with contract creation.
 /// Verifies that created contracts persist correctly across transactions
 /// and that their state is properly maintained.
@@ -223,3 +267,34 @@ fn test_frame_stack_index() {
     assert_eq!(evm.frame_stack.index(), None);
     compare_or_save_testdata("test_frame_stack_index.json", result1);
 }
+
+#[test]
+fn test_run_with_spec() {
+    let run = |spec| {
+        let mut evm = Context::mainnet()
+            .with_db(BenchmarkDB::new_bytecode(Bytecode::new_legacy(
+                SELFDESTRUCT_BYTECODE.into(),
+            )))
+            .build_mainnet();
+        let default_spec = evm.ctx.cfg.spec;
+        evm.ctx.set_tx(TxEnv::builder_for_bench().build_fill());
+        let mut handler = MainnetHandler::<_, EVMError<Infallible>, _>::default();
+        let result = handler.run_with_spec(&mut evm, spec).unwrap();
+
+        // Spec is restored after the run.
+        assert_eq!(evm.ctx.cfg.spec, default_spec);
+        assert_eq!(evm.ctx.journal().spec, default_spec);
+
+        let is_destroyed = evm.ctx.journal().state[&BENCH_TARGET].is_selfdestructed();
+        (result, is_destroyed)
+    };
+
+    let (berlin, berlin_destroyed) = run(SpecId::BERLIN);
+    let (cancun, cancun_destroyed) = run(SpecId::CANCUN);
+    assert!(berlin.is_success() && cancun.is_success());
+
+    // Before EIP-6780 account is destroyed and the selfdestruct refund is given.
+    assert!(berlin_destroyed);
+    assert!(!cancun_destroyed);
+    assert!(berlin.gas_used() < cancun.gas_used());
+}
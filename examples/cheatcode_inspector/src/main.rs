@@ -251,6 +251,10 @@ impl JournalTr for Backend {
         self.journaled_state.take_logs()
     }
 
+    fn changes_state(&self, excluded: &[Address]) -> bool {
+        self.journaled_state.changes_state(excluded)
+    }
+
     fn commit_tx(&mut self) {
         self.journaled_state.commit_tx()
     }